@@ -0,0 +1,63 @@
+use std::fs::File;
+use std::hint::black_box;
+use std::io::BufReader;
+use criterion::{criterion_group, criterion_main, Criterion};
+use eta_graph::algorithms::dfs_bfs::bfs;
+use eta_graph::algorithms::dfs_bfs::ControlFlow::Resume;
+use eta_graph::algorithms::dinic::DinicGraph;
+use eta_graph::algorithms::path_finding::dijkstra;
+use eta_graph::formats::{dimacs, matrix_market, snap};
+use eta_graph::handles::types::VHandle;
+use eta_graph::handles::vh_pack;
+use eta_graph::traits::StoreVertex;
+
+/// Runs on the file in `ETA_GRAPH_DATASET`, the format is chosen by the extension:
+/// `.gr` and `.max` for DIMACS, `.mtx` for Matrix Market and anything else as a SNAP edge list.
+/// Without the variable only the empty `dataset_skipped` benchmark is run.
+fn dataset_benchmark(c: &mut Criterion){
+    let Ok(path) = std::env::var("ETA_GRAPH_DATASET") else {
+        c.bench_function("dataset_skipped", |b| b.iter(|| black_box(())));
+        return;
+    };
+    let reader = BufReader::new(File::open(&path).expect("Dataset not found"));
+
+    if path.ends_with(".max") {
+        let problem = dimacs::read_max_flow(reader).expect("Invalid dataset");
+        let graph = &problem.graph.graph;
+        c.bench_function("dataset_dinic", |b| {
+            b.iter(|| {
+                black_box(DinicGraph::from(&graph.vertices, &graph.edge_storage, problem.source, problem.sink))
+            })
+        });
+        return;
+    }
+
+    let mut graph = if path.ends_with(".gr") {
+        dimacs::read_shortest_path(reader).expect("Invalid dataset")
+    } else if path.ends_with(".mtx") {
+        matrix_market::read(reader).expect("Invalid dataset")
+    } else {
+        snap::read(reader, true).expect("Invalid dataset").graph
+    };
+    let vertices_len = graph.graph.vertices.len();
+    let last = (vertices_len - 1) as VHandle;
+
+    c.bench_function("dataset_bfs", |b| {
+        b.iter(|| {
+            let mut sum = 0;
+            bfs(&mut graph.graph.edge_storage, vh_pack(0), vertices_len, |_, _| {
+                sum += 1;
+                Resume
+            });
+            black_box(sum)
+        })
+    });
+    c.bench_function("dataset_dijkstra", |b| {
+        b.iter(|| {
+            black_box(dijkstra(&mut graph.graph.edge_storage, 0, last, vertices_len))
+        })
+    });
+}
+
+criterion_group!(datasets, dataset_benchmark);
+criterion_main!(datasets);
\ No newline at end of file
@@ -3,7 +3,6 @@ use criterion::{criterion_group, criterion_main, Criterion};
 use eta_graph::handles::types::Weight;
 
 fn tree_graph_benchmark(c: &mut Criterion){
-    use eta_graph::traits::EdgeStore;
     use eta_graph::weighted_graph::WeightedGraph;
     use eta_algorithms::data_structs::queue::Queue;
     use eta_graph::handles::types::VHandle;
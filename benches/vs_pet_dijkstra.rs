@@ -4,7 +4,6 @@ use criterion::measurement::WallTime;
 use eta_graph::handles::types::{Ci, Weight};
 
 fn tree_graph_eta_benchmark(children_count: usize, elements_to_generate: usize, c: &mut BenchmarkGroup<WallTime>){
-    use eta_graph::traits::EdgeStore;
     use eta_graph::weighted_graph::WeightedGraph;
     use eta_algorithms::data_structs::queue::Queue;
     use eta_graph::handles::types::VHandle;
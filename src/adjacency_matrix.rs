@@ -0,0 +1,334 @@
+use std::ops::{Index, IndexMut};
+use std::slice;
+use eta_algorithms::data_structs::fat_ptr::{FatPtr, FatPtrMut};
+use crate::graph::Error;
+use crate::handles::types::{Ci, Edge, VHandle, Weight};
+use crate::handles::{pack, set_vh, set_wgt, vh, wgt, Generation, Generations, NONE};
+use crate::traits::{EdgeConnect, EdgeManipulate, EdgeStore, Generational, WeightStore, WeightedEdgeConnect, WeightedEdgeManipulate};
+
+/// Dense edge storage for small graphs where most pairs are connected. Every vertex owns a row of
+/// `stride` edges with its present edges packed at the front, and a row of slots with the position
+/// of the edge to every target, so lookups are O(1) and rows iterate like [EdgeStorage](crate::edge_storage::EdgeStorage) blocks.
+///
+/// There is at most one edge per pair, connecting an existing pair replaces its weight.
+/// Edge targets must not be changed through [IndexMut] or the mutable slices, only the weights.
+/// Every edge needs an existing target, so there are no [NONE] placeholders and the matrix
+/// can't back a [Tree](crate::views::tree::Tree), see [PlaceholderEdges](crate::traits::PlaceholderEdges).
+#[derive(Clone, Default)]
+pub struct AdjacencyMatrix {
+    stride: usize,
+    lens: Vec<Ci>,
+    edges: Vec<Edge>,
+    slots: Vec<Ci>,
+    generations: Generations,
+}
+
+const EMPTY: Ci = Ci::MAX;
+
+impl AdjacencyMatrix {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Allocates the rows of `vertices` vertices up front.
+    pub fn with_capacity(vertices: usize) -> Self {
+        AdjacencyMatrix {
+            stride: vertices,
+            lens: Vec::with_capacity(vertices),
+            edges: Vec::with_capacity(vertices * vertices),
+            slots: Vec::with_capacity(vertices * vertices),
+            generations: Generations::new(),
+        }
+    }
+
+    /// Targets outside of the matrix have no edge.
+    #[inline(always)]
+    fn slot(&self, from: VHandle, to: VHandle) -> Ci {
+        if to as usize >= self.lens.len() {
+            return EMPTY;
+        }
+        self.slots[from as usize * self.stride + to as usize]
+    }
+
+    #[inline(always)]
+    fn check_handle(&self, handle: VHandle) -> Result<(), Error> {
+        if handle as usize >= self.lens.len() {
+            return Err(Error::InvalidHandle(handle));
+        }
+        Ok(())
+    }
+
+    /// Doubles the row length and moves every row to its new offset.
+    fn grow(&mut self) {
+        let stride = (self.stride * 2).max(4);
+        let rows = self.lens.len();
+        let mut edges = vec![0; rows * stride];
+        let mut slots = vec![EMPTY; rows * stride];
+        for row in 0..rows {
+            let (old, new) = (row * self.stride, row * stride);
+            edges[new..new + self.stride].copy_from_slice(&self.edges[old..old + self.stride]);
+            slots[new..new + self.stride].copy_from_slice(&self.slots[old..old + self.stride]);
+        }
+        self.stride = stride;
+        self.edges = edges;
+        self.slots = slots;
+    }
+
+    fn insert(&mut self, from: VHandle, edge: Edge) {
+        assert!((from as usize) < self.lens.len(), "Vertex {} is not in the matrix", from);
+        assert!((vh(edge) as usize) < self.lens.len(), "Target {} is not in the matrix", vh(edge));
+        let row = from as usize * self.stride;
+        let slot = self.slots[row + vh(edge) as usize];
+        if slot != EMPTY {
+            self.edges[row + slot as usize] = set_wgt(self.edges[row + slot as usize], wgt(edge));
+            return;
+        }
+        let len = self.lens[from as usize];
+        self.edges[row + len as usize] = edge;
+        self.slots[row + vh(edge) as usize] = len;
+        self.lens[from as usize] = len + 1;
+    }
+}
+
+impl EdgeConnect for AdjacencyMatrix {
+    fn connect_edges(&mut self, from: VHandle, to: &[Edge]) {
+        for edge in to {
+            self.insert(from, *edge);
+        }
+    }
+
+    /// Moves the last edge of the row into the freed position.
+    fn disconnect(&mut self, from: VHandle, to: VHandle) -> bool {
+        let slot = self.slot(from, to);
+        if slot == EMPTY {
+            return false;
+        }
+        let row = from as usize * self.stride;
+        let last = self.lens[from as usize] - 1;
+        let moved = self.edges[row + last as usize];
+        self.edges[row + slot as usize] = moved;
+        self.slots[row + vh(moved) as usize] = slot;
+        self.slots[row + to as usize] = EMPTY;
+        self.lens[from as usize] = last;
+        true
+    }
+
+    #[inline(always)]
+    fn connect(&mut self, from: VHandle, to: VHandle) {
+        self.insert(from, pack(to, 0));
+    }
+
+    fn try_connect_edges(&mut self, from: VHandle, to: &[Edge]) -> Result<(), Error> {
+        self.check_handle(from)?;
+        for edge in to {
+            self.check_handle(vh(*edge))?;
+        }
+        self.connect_edges(from, to);
+        Ok(())
+    }
+
+    fn try_disconnect(&mut self, from: VHandle, to: VHandle) -> Result<(), Error> {
+        self.check_handle(from)?;
+        self.check_handle(to)?;
+        match self.disconnect(from, to) {
+            true => Ok(()),
+            false => Err(Error::NoEdge { from, to }),
+        }
+    }
+
+    #[inline(always)]
+    fn try_connect(&mut self, from: VHandle, to: VHandle) -> Result<(), Error> {
+        self.try_connect_edges(from, &[pack(to, 0)])
+    }
+}
+
+impl WeightedEdgeConnect for AdjacencyMatrix {
+    #[inline(always)]
+    fn connect_weighted(&mut self, from: VHandle, to: VHandle, weight: Weight) {
+        self.insert(from, pack(to, weight));
+    }
+
+    fn try_connect_weighted<T: TryInto<Weight>>(&mut self, from: VHandle, to: VHandle, weight: T) -> Result<(), Error> {
+        let weight = weight.try_into().map_err(|_| Error::WeightOutOfRange)?;
+        self.try_connect_edges(from, &[pack(to, weight)])
+    }
+}
+
+impl EdgeStore for AdjacencyMatrix {
+    /// Every row has room for an edge to each vertex, so `size` is ignored.
+    fn create_vertex_entry(&mut self, _size: Ci) -> VHandle {
+        if self.lens.len() == self.stride {
+            self.grow();
+        }
+        self.lens.push(0);
+        self.edges.resize(self.lens.len() * self.stride, 0);
+        self.slots.resize(self.lens.len() * self.stride, EMPTY);
+        self.generations.push(self.lens.len() - 1);
+        (self.lens.len() - 1) as VHandle
+    }
+
+    fn try_create_vertex_entry(&mut self, size: Ci) -> Result<VHandle, Error> {
+        if self.lens.len() >= NONE as usize || self.lens.len() >= EMPTY as usize {
+            return Err(Error::HandleSpaceExhausted);
+        }
+        Ok(self.create_vertex_entry(size))
+    }
+
+    fn remove_vertex_entry(&mut self, handle: VHandle) -> VHandle {
+        let last = (self.lens.len() - 1) as VHandle;
+        for row in 0..self.lens.len() as VHandle {
+            self.disconnect(row, handle);
+        }
+        if handle != last {
+            let (to, from) = (handle as usize * self.stride, last as usize * self.stride);
+            self.edges.copy_within(from..from + self.stride, to);
+            self.slots.copy_within(from..from + self.stride, to);
+            self.lens[handle as usize] = self.lens[last as usize];
+            for row in 0..last as usize {
+                let row = row * self.stride;
+                let slot = self.slots[row + last as usize];
+                if slot != EMPTY {
+                    self.edges[row + slot as usize] = set_vh(self.edges[row + slot as usize], handle);
+                    self.slots[row + handle as usize] = slot;
+                    self.slots[row + last as usize] = EMPTY;
+                }
+            }
+        }
+        self.lens.pop();
+        self.edges.truncate(self.lens.len() * self.stride);
+        self.slots.truncate(self.lens.len() * self.stride);
+        self.generations.swap_remove(handle as usize, last as usize);
+        if handle == last {
+            return NONE;
+        }
+        last
+    }
+
+    #[inline(always)]
+    fn vertices_len(&self) -> usize {
+        self.lens.len()
+    }
+
+    #[inline(always)]
+    fn edges_as_slice(&self, handle: VHandle) -> &[Edge] {
+        let row = handle as usize * self.stride;
+        &self.edges[row..row + self.lens[handle as usize] as usize]
+    }
+
+    #[inline(always)]
+    fn edges_as_mut_slice(&mut self, handle: VHandle) -> &mut [Edge] {
+        let row = handle as usize * self.stride;
+        &mut self.edges[row..row + self.stride]
+    }
+
+    #[inline(always)]
+    fn edges_as_ptr(&self, handle: VHandle) -> FatPtr<Edge> {
+        let edges = self.edges_as_slice(handle).as_ptr_range();
+        FatPtr::new(edges.start, edges.end)
+    }
+
+    #[inline(always)]
+    fn edges_as_mut_ptr(&mut self, handle: VHandle) -> FatPtrMut<Edge> {
+        let len = self.lens[handle as usize] as usize;
+        let edges = self.edges_as_mut_slice(handle)[..len].as_mut_ptr_range();
+        FatPtrMut::new(edges.start, edges.end)
+    }
+
+    #[inline(always)]
+    fn edges_is_empty(&self, handle: VHandle) -> bool {
+        self.lens[handle as usize] == 0
+    }
+
+    #[inline(always)]
+    fn edges_len(&self, handle: VHandle) -> usize {
+        self.lens[handle as usize] as usize
+    }
+
+    #[inline(always)]
+    fn edges_capacity(&self, _handle: VHandle) -> usize {
+        self.stride
+    }
+
+    #[inline(always)]
+    fn edges_index(&self, handle: VHandle) -> usize {
+        handle as usize * self.stride
+    }
+
+    fn iter(&self) -> impl Iterator<Item=&Edge> {
+        (0..self.lens.len() as VHandle).flat_map(|handle| self.edges_as_slice(handle).iter())
+    }
+
+    fn iter_mut(&mut self) -> impl Iterator<Item=&mut Edge> {
+        let (stride, lens) = (self.stride, &self.lens);
+        self.edges.chunks_mut(stride.max(1))
+            .zip(lens.iter())
+            .flat_map(|(row, len)| row[..*len as usize].iter_mut())
+    }
+
+    #[inline(always)]
+    fn edges_iter(&self, handle: VHandle) -> impl Iterator<Item=&Edge> {
+        self.edges_as_slice(handle).iter()
+    }
+
+    #[inline(always)]
+    fn edges_iter_mut(&mut self, handle: VHandle) -> impl Iterator<Item=&mut Edge> {
+        let len = self.lens[handle as usize] as usize;
+        self.edges_as_mut_slice(handle)[..len].iter_mut()
+    }
+
+    unsafe fn edges_iter_mut_unchecked(&mut self, handle: VHandle) -> impl Iterator<Item=&mut Edge> {
+        let start = self.edges.as_mut_ptr().add(handle as usize * self.stride);
+        slice::from_raw_parts_mut(start, self.lens[handle as usize] as usize).iter_mut()
+    }
+
+    #[inline(always)]
+    fn find_edge(&self, from: VHandle, to: VHandle) -> Option<usize> {
+        match self.slot(from, to) {
+            EMPTY => None,
+            slot => Some(from as usize * self.stride + slot as usize),
+        }
+    }
+}
+
+impl Index<usize> for AdjacencyMatrix {
+    type Output = Edge;
+    #[inline(always)]
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.edges[index]
+    }
+}
+
+impl IndexMut<usize> for AdjacencyMatrix {
+    #[inline(always)]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.edges[index]
+    }
+}
+
+impl Generational for AdjacencyMatrix {
+    #[inline(always)]
+    fn generation(&self, handle: VHandle) -> Option<Generation> {
+        self.generations.get(handle as usize, self.lens.len())
+    }
+
+    #[inline(always)]
+    fn slot_generation(&self, handle: VHandle) -> Option<Generation> {
+        self.generations.slot(handle as usize)
+    }
+}
+
+impl EdgeManipulate for AdjacencyMatrix {}
+
+impl WeightedEdgeManipulate for AdjacencyMatrix {}
+
+impl WeightStore for AdjacencyMatrix {
+    type WeightType = Weight;
+    #[inline(always)]
+    fn weight(&self, index: usize) -> Weight {
+        wgt(self.edges[index])
+    }
+    #[inline(always)]
+    fn set_weight(&mut self, index: usize, weight: Weight) {
+        self.edges[index] = set_wgt(self.edges[index], weight);
+    }
+}
\ No newline at end of file
@@ -1,8 +1,7 @@
-use crate::handles::types::{Edge, VHandle, Weight};
-use crate::handles::{vh};
+use crate::handles::types::Weight;
+use crate::handles::Width;
 use crate::traits::EdgeStore;
 use eta_algorithms::data_structs::array::Array;
-use eta_algorithms::data_structs::fat_ptr::{FatPtrMut};
 use eta_algorithms::data_structs::queue::Queue;
 use eta_algorithms::data_structs::stack::Stack;
 
@@ -14,20 +13,22 @@ pub enum ControlFlow {
 }
 
 
-pub fn bfs<PreOrderFunc, Edges>(edge_storage: &mut Edges, start: Edge, vertices_count: usize, mut pre_order: PreOrderFunc)
+/// Works with storages of any [Width], the width is inferred from the storage.
+pub fn bfs<PreOrderFunc, Edges, W>(edge_storage: &mut Edges, start: W::Edge, vertices_count: usize, mut pre_order: PreOrderFunc)
 where
-    PreOrderFunc: FnMut(&mut Edge, Weight) -> ControlFlow,
-    Edges: EdgeStore,
+    PreOrderFunc: FnMut(&mut W::Edge, Weight) -> ControlFlow,
+    Edges: EdgeStore<W>,
+    W: Width,
 {
     let mut was_queued_flags = Array::new_default_bytes(vertices_count, 0);
 
     // Uses more memory than necessary. But rotates very quickly. Might be worth considering version with smaller memory footprint.
-    let mut visit_queue = Queue::<VHandle>::new_pow2_sized(vertices_count);
+    let mut visit_queue = Queue::<W::VHandle>::new_pow2_sized(vertices_count);
     let mut end = 1;
     let mut next_layer = 1;
     let mut layer = 0;
-    visit_queue.push(vh(start));
-    was_queued_flags[0] = true;
+    visit_queue.push(W::vh(start));
+    was_queued_flags[W::index(W::vh(start))] = true;
     let mut i = 0;
 
     //Initial call
@@ -48,10 +49,10 @@ where
         let handle = visit_queue.dequeue().unwrap();
 
         for edge in edge_storage.edges_iter_mut(handle) {
-            if unsafe { *was_queued_flags.index_unchecked(vh(*edge) as usize) } {
+            if unsafe { *was_queued_flags.index_unchecked(W::index(W::vh(*edge))) } {
                 continue;
             }
-            unsafe { *was_queued_flags.index_unchecked_mut(vh(*edge) as usize) = true };
+            unsafe { *was_queued_flags.index_unchecked_mut(W::index(W::vh(*edge))) = true };
 
             match pre_order(edge, layer + 1) {
                 ControlFlow::End => {
@@ -67,7 +68,7 @@ where
                 ControlFlow::Resume => {}
             }
 
-            visit_queue.push(vh(*edge));
+            visit_queue.push(W::vh(*edge));
             end += 1;
         }
         i += 1;
@@ -80,46 +81,52 @@ where
 }
 
 #[cfg_attr(not(debug_assertions), inline(always))]
-pub fn dfs<PreOrderFunc, PostOrderFunc, Edges>(edge_storage: &mut Edges, start: Edge, vertices_count: usize, pre_order_func: PreOrderFunc,
-                                               post_order_func: PostOrderFunc)
+pub fn dfs<PreOrderFunc, PostOrderFunc, Edges, W>(edge_storage: &mut Edges, start: W::Edge, vertices_count: usize, pre_order_func: PreOrderFunc,
+                                                  post_order_func: PostOrderFunc)
 where
-    PreOrderFunc: FnMut(&mut Edge) -> ControlFlow,
-    PostOrderFunc: FnMut(&mut Edge),
-    Edges: EdgeStore,
+    PreOrderFunc: FnMut(&mut W::Edge) -> ControlFlow,
+    PostOrderFunc: FnMut(&mut W::Edge),
+    Edges: EdgeStore<W>,
+    W: Width,
 {
     let mut flags = Array::new_default_bytes(vertices_count, 0);
     dfs_custom_flags(edge_storage, start, vertices_count, |to_visit| {
-        let was_visited = flags[vh(to_visit) as usize];
-        flags[vh(to_visit) as usize] = true;
+        let was_visited = flags[W::index(W::vh(to_visit))];
+        flags[W::index(W::vh(to_visit))] = true;
         was_visited
     }, pre_order_func, post_order_func);
 }
 
-pub fn dfs_custom_flags<VisitedFunc, PreOrderFunc, PostOrderFunc, Edges>(edge_storage: &mut Edges, start: Edge, vertex_count: usize,
-                                                                         mut is_visited: VisitedFunc, mut pre_order_func: PreOrderFunc,
-                                                                         mut post_order_func: PostOrderFunc)
+pub fn dfs_custom_flags<VisitedFunc, PreOrderFunc, PostOrderFunc, Edges, W>(edge_storage: &mut Edges, start: W::Edge, vertex_count: usize,
+                                                                            mut is_visited: VisitedFunc, mut pre_order_func: PreOrderFunc,
+                                                                            mut post_order_func: PostOrderFunc)
 where
-    VisitedFunc: FnMut(Edge) -> bool,
-    PreOrderFunc: FnMut(&mut Edge) -> ControlFlow,
-    PostOrderFunc: FnMut(&mut Edge),
-    Edges: EdgeStore,
+    VisitedFunc: FnMut(W::Edge) -> bool,
+    PreOrderFunc: FnMut(&mut W::Edge) -> ControlFlow,
+    PostOrderFunc: FnMut(&mut W::Edge),
+    Edges: EdgeStore<W>,
+    W: Width,
 {
     let mut start_edge = start;
-    let mut stack = Stack::<(FatPtrMut<Edge>, *mut Edge)>::new(vertex_count);
-    stack.push((edge_storage.edges_as_mut_ptr(vh(start)), (&mut start_edge) as *mut Edge));
+    // Every frame holds the vertex, the index to continue its edges from and the edge it was reached by
+    let mut stack = Stack::<(W::VHandle, usize, *mut W::Edge)>::new(vertex_count);
+    stack.push((W::vh(start), edge_storage.edges_index(W::vh(start)), (&mut start_edge) as *mut W::Edge));
     if let ControlFlow::End = pre_order_func(&mut start_edge) {
         return;
     }
 
     while !stack.is_empty() {
-        let (next_edges_ptr, current_edge) = stack.top_mut().unwrap();
-        let next = next_edges_ptr.next();
+        let (handle, cursor, current_edge) = stack.top_mut().unwrap();
+        let next = edge_storage.next_edge_index(*handle, *cursor);
         if next.is_none() {
             post_order_func(unsafe { (*current_edge).as_mut().unwrap() });
             stack.pop();
             continue;
         }
-        let next = next.unwrap();
+        let index = next.unwrap();
+        *cursor = index + 1;
+        // Safety: the storage is not resized during the traversal, so the edge outlives its frame
+        let next = unsafe { ((&mut edge_storage[index]) as *mut W::Edge).as_mut().unwrap() };
 
         if is_visited(*next) {
             continue;
@@ -138,13 +145,13 @@ where
             ControlFlow::Resume => {}
         }
 
-        let next_edges = edge_storage.edges_as_mut_ptr(vh(*next));
-        stack.push((next_edges, next));
+        let target = W::vh(*next);
+        stack.push((target, edge_storage.edges_index(target), next as *mut W::Edge));
     }
 
     // Return back to the src without exploring further
     while !stack.is_empty() {
-        let (_, packed_edge) = stack.pop().unwrap();
+        let (_, _, packed_edge) = stack.pop().unwrap();
         post_order_func(unsafe { packed_edge.as_mut().unwrap() });
     }
 }
\ No newline at end of file
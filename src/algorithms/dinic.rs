@@ -1,31 +1,49 @@
+use std::marker::PhantomData;
 use eta_algorithms::data_structs::array::Array;
 use eta_algorithms::data_structs::queue::Queue;
 use eta_algorithms::data_structs::stack::Stack;
-use crate::handles::types::{Edge, VHandle, Weight};
-use crate::handles::{pack, set_wgt, vh, vhu, wgt};
-use crate::traits::{StoreVertex, WeightedEdgeManipulate};
-pub struct DinicGraph<'a, VertexType, VertexStorageType, EdgeStorageType>
+use crate::handles::{Width, W64};
+use crate::traits::{EdgeStore, EdgeWeight, StoreVertex, WeightStore};
+
+/// Frame of the augmenting path search. `edge` is the index of the edge leading into `vertex`,
+/// `None` for the source. `next..end` are the outgoing edges which were not explored yet.
+#[derive(Clone, Copy)]
+struct SearchFrame<Handle> {
+    vertex: Handle,
+    edge: Option<usize>,
+    next: usize,
+    end: usize,
+}
+
+/// Computes the flow with the capacities read through [WeightStore], so packed integer capacities
+/// and wide or float capacities in the edge data both work. After construction the weights of
+/// `edge_storage` hold the flow through each edge. The handles are those of the storage width, see [Width].
+/// `layer_data` holds the level of every vertex in the last level graph, `usize::MAX` if it was not reached.
+pub struct DinicGraph<'a, VertexType, VertexStorageType, EdgeStorageType, W: Width = W64>
 where
     VertexStorageType: StoreVertex<VertexType=VertexType>,
-    EdgeStorageType: WeightedEdgeManipulate,
+    EdgeStorageType: EdgeStore<W> + WeightStore<W> + Clone,
 {
     pub vertices: &'a VertexStorageType,
     pub edge_storage: EdgeStorageType,
-    pub layer_data: Array<Weight>,
+    pub layer_data: Array<usize>,
+    width: PhantomData<W>,
 }
 
-impl<'a, VertexType, VertexStorageType, EdgeStorageType> DinicGraph<'a, VertexType, VertexStorageType, EdgeStorageType>
+impl<'a, VertexType, VertexStorageType, EdgeStorageType, W> DinicGraph<'a, VertexType, VertexStorageType, EdgeStorageType, W>
 where
     VertexStorageType: StoreVertex<VertexType=VertexType>,
-    EdgeStorageType: WeightedEdgeManipulate,
+    EdgeStorageType: EdgeStore<W> + WeightStore<W> + Clone,
     VertexType: std::fmt::Debug + std::fmt::Display,
+    W: Width,
 {
-    pub fn from(vertices: &'a VertexStorageType, edge_storage: &EdgeStorageType, src_handle: VHandle, sink_handle: VHandle) -> Self {
+    pub fn from(vertices: &'a VertexStorageType, edge_storage: &EdgeStorageType, src_handle: W::VHandle, sink_handle: W::VHandle) -> Self {
         let vertices_len = vertices.len();
         let mut dinic_graph = DinicGraph {
             vertices,
             edge_storage: edge_storage.clone(),
             layer_data: Array::new(vertices_len),
+            width: PhantomData,
         };
 
         dinic_graph.perform_search(src_handle, sink_handle);
@@ -33,73 +51,78 @@ where
         dinic_graph
     }
 
-    fn finalize_flow_calc(&mut self, original_edges: &EdgeStorageType)
-    where
-        VertexStorageType: StoreVertex<VertexType=VertexType>,
-        EdgeStorageType: WeightedEdgeManipulate,
-        VertexType: std::fmt::Debug + std::fmt::Display,
-    {
-        let zipped_iters = original_edges.iter().zip(self.edge_storage.iter_mut());
-        for edges in zipped_iters {
-            let (original_edge, dinic_edge) = edges;
-            let original_wgt = wgt(*original_edge);
-            let current_wgt = wgt(*dinic_edge);
-            *dinic_edge = set_wgt(*dinic_edge, original_wgt - current_wgt);
+    fn finalize_flow_calc(&mut self, original_edges: &EdgeStorageType) {
+        for vertex in (0..self.vertices.len()).map(W::handle) {
+            let index = self.edge_storage.edges_index(vertex);
+            for i in index..index + self.edge_storage.edges_len(vertex) {
+                let flow = original_edges.weight(i).minus(self.edge_storage.weight(i));
+                self.edge_storage.set_weight(i, flow);
+            }
         }
     }
 
-    pub fn perform_search(&mut self, src_handle: VHandle, sink_handle: VHandle) {
+    #[inline(always)]
+    fn frame(&self, vertex: W::VHandle, edge: Option<usize>) -> SearchFrame<W::VHandle> {
+        let next = self.edge_storage.edges_index(vertex);
+        SearchFrame { vertex, edge, next, end: next + self.edge_storage.edges_len(vertex) }
+    }
+
+    pub fn perform_search(&mut self, src_handle: W::VHandle, sink_handle: W::VHandle) {
         let mut stack = Stack::new(self.vertices.len());
-        let mut queue = Queue::<VHandle>::new_pow2_sized(self.vertices.len()); // Direct pointer access is faster than offsets
-        self.layer_data.fill(Weight::MAX);
+        let mut queue = Queue::<W::VHandle>::new_pow2_sized(self.vertices.len()); // Direct pointer access is faster than offsets
+        self.layer_data.fill(usize::MAX);
 
         while mark_levels(src_handle, sink_handle, &mut self.edge_storage, &mut queue, &mut self.layer_data).is_ok() {
             loop {
-                let start_edges = self.edge_storage.edges_as_mut_ptr(src_handle);
-                let mut root_edge = pack(src_handle, Weight::MAX);
-                stack.push((start_edges, (&mut root_edge) as *mut Edge));
+                stack.push(self.frame(src_handle, None));
 
                 let mut augmented_path_found = false;
-                let mut bottleneck_value = Weight::MAX;
+                let mut bottleneck_value = EdgeStorageType::WeightType::infinity();
                 let mut current_layer;
 
                 while !stack.is_empty() {
-                    let (next_edges_ptr, edge_ptr) = stack.top_mut().unwrap();
-                    let outgoing_edge_val = unsafe { **edge_ptr };
-                    current_layer = self.layer_data[vh(outgoing_edge_val) as usize];
-
-                    if vh(outgoing_edge_val) == sink_handle {
-                        augmented_path_found = true;
-                    }
+                    let frame = stack.top_mut().unwrap();
+                    let (vertex, edge) = (frame.vertex, frame.edge);
+                    current_layer = self.layer_data[W::index(vertex)];
 
                     // In case of augmented path found, we need to backtrack and ignore everything else
                     if augmented_path_found {
-                        unsafe {
-                            let modified_edge = set_wgt(**edge_ptr, wgt(**edge_ptr) - bottleneck_value);
-                            (*edge_ptr).write(modified_edge);
-                        };
+                        if let Some(edge) = edge {
+                            let residual = self.edge_storage.weight(edge).minus(bottleneck_value);
+                            self.edge_storage.set_weight(edge, residual);
+                        }
                         stack.pop();
                         continue;
                     }
 
-                    if wgt(outgoing_edge_val) < bottleneck_value {
-                        bottleneck_value = wgt(outgoing_edge_val);
+                    let capacity = match edge {
+                        Some(edge) => self.edge_storage.weight(edge),
+                        None => EdgeStorageType::WeightType::infinity(),
+                    };
+                    if capacity < bottleneck_value {
+                        bottleneck_value = capacity;
+                    }
+
+                    // The edge into the sink limits the path as well, so it is checked after the bottleneck update
+                    if vertex == sink_handle {
+                        augmented_path_found = true;
+                        continue;
                     }
 
-                    let next = next_edges_ptr.next();
                     // Backtracking
-                    if next.is_none() {
+                    if frame.next == frame.end {
                         stack.pop();
                         continue;
                     }
-                    let next = next.unwrap();
+                    let next = frame.next;
+                    frame.next += 1;
 
-                    let next_edges = self.edge_storage.edges_as_mut_ptr(vh(*next));
-                    let next_edge_layer = self.layer_data[vh(*next) as usize];
+                    let next_vertex = W::vh(self.edge_storage[next]);
+                    let next_edge_layer = self.layer_data[W::index(next_vertex)];
 
                     // Exploring deeper
-                    if wgt(*next) != 0 && next_edge_layer > current_layer {
-                        stack.push((next_edges, next));
+                    if self.edge_storage.weight(next) != EdgeStorageType::WeightType::zero() && next_edge_layer > current_layer {
+                        stack.push(self.frame(next_vertex, Some(next)));
                     }
                 }
 
@@ -111,15 +134,16 @@ where
     }
 }
 
-pub(in crate) fn mark_levels<EdgeStorageType>(
-    src_handle: VHandle,
-    sink_handle: VHandle,
+pub(in crate) fn mark_levels<EdgeStorageType, W>(
+    src_handle: W::VHandle,
+    sink_handle: W::VHandle,
     edge_storage: &mut EdgeStorageType,
-    queue: &mut Queue<VHandle>,
-    layer_data: &mut Array<Weight>,
+    queue: &mut Queue<W::VHandle>,
+    layer_data: &mut Array<usize>,
 ) -> Result<(), &'static str>
 where
-    EdgeStorageType: WeightedEdgeManipulate,
+    EdgeStorageType: WeightStore<W>,
+    W: Width,
 {
     let mut found_sink = false;
     queue.push(src_handle);
@@ -128,7 +152,7 @@ where
     let mut sibling_counter = 0;
     let mut last_sibling_in_layer = 1;
     let mut next_last_sibling_in_layer = 1;
-    layer_data[src_handle as usize] = 0;
+    layer_data[W::index(src_handle)] = 0;
 
     while !queue.is_empty() {
         let v_handle = queue.dequeue().unwrap();
@@ -136,18 +160,20 @@ where
             found_sink = true;
         }
 
-        for next_edge in edge_storage.edges_iter_mut(v_handle){
-            let next_edge_layer = unsafe{*layer_data.index_unchecked(vhu(*next_edge))};
-            if next_edge_layer != Weight::MAX {
+        let index = edge_storage.edges_index(v_handle);
+        for i in index..index + edge_storage.edges_len(v_handle) {
+            let next_edge = edge_storage[i];
+            let next_edge_layer = unsafe{*layer_data.index_unchecked(W::index(W::vh(next_edge)))};
+            if next_edge_layer != usize::MAX {
                 continue;
             }
 
-            if wgt(*next_edge) == 0 {
+            if edge_storage.weight(i) == EdgeStorageType::WeightType::zero() {
                 continue;
             }
-            layer_data[vh(*next_edge) as usize] = layer + 1;
+            layer_data[W::index(W::vh(next_edge))] = layer + 1;
 
-            queue.push(vh(*next_edge));
+            queue.push(W::vh(next_edge));
             next_last_sibling_in_layer += 1;
         }
         sibling_counter += 1;
@@ -161,5 +187,4 @@ where
         return Err("Sink not found");
     }
     Ok(())
-}
-
+}
\ No newline at end of file
@@ -2,23 +2,22 @@ use std::cmp::Ordering;
 use std::collections::{BinaryHeap};
 use eta_algorithms::data_structs::array::Array;
 use eta_algorithms::data_structs::stack::Stack;
-use crate::handles::types::{Edge, VHandle, Weight};
-use crate::handles::{vh, wgt};
-use crate::traits::EdgeStore;
+use crate::handles::Width;
+use crate::traits::{EdgeWeight, WeightStore};
 
-struct MinHeapPair {
-    pub vertex: VHandle,
-    pub f_score: Weight,
+struct MinHeapPair<WeightType, Handle> {
+    pub vertex: Handle,
+    pub f_score: WeightType,
 }
 
 #[derive(Clone, Copy)]
-struct PathVertex {
-    pub from: VHandle,
-    pub f_score: Weight,
+struct PathVertex<WeightType, Handle> {
+    pub from: Handle,
+    pub f_score: WeightType,
 }
 
-impl MinHeapPair {
-    pub fn new(vertex: VHandle, f_score: Weight) -> Self {
+impl<WeightType, Handle> MinHeapPair<WeightType, Handle> {
+    pub fn new(vertex: Handle, f_score: WeightType) -> Self {
         MinHeapPair {
             vertex,
             f_score,
@@ -26,32 +25,33 @@ impl MinHeapPair {
     }
 }
 
-impl Eq for MinHeapPair {}
+impl<WeightType: EdgeWeight, Handle> Eq for MinHeapPair<WeightType, Handle> {}
 
-impl PartialEq<Self> for MinHeapPair {
+impl<WeightType: EdgeWeight, Handle> PartialEq<Self> for MinHeapPair<WeightType, Handle> {
     fn eq(&self, other: &Self) -> bool {
         self.f_score == other.f_score
     }
 }
 
-impl PartialOrd<Self> for MinHeapPair {
+impl<WeightType: EdgeWeight, Handle> PartialOrd<Self> for MinHeapPair<WeightType, Handle> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl Ord for MinHeapPair {
+impl<WeightType: EdgeWeight, Handle> Ord for MinHeapPair<WeightType, Handle> {
+    /// Incomparable scores (NaN) are treated as equal.
     fn cmp(&self, other: &Self) -> Ordering {
-        other.f_score.cmp(&self.f_score)
+        other.f_score.partial_cmp(&self.f_score).unwrap_or(Ordering::Equal)
     }
 }
 
-fn reconstruct_path(paths: &mut Array<PathVertex>, start: VHandle, goal: VHandle) -> Stack<VHandle> {
+fn reconstruct_path<WeightType: Copy, W: Width>(paths: &mut Array<PathVertex<WeightType, W::VHandle>>, start: W::VHandle, goal: W::VHandle) -> Stack<W::VHandle> {
     let mut path = Stack::new(paths.capacity());
     let mut current = goal;
     path.push(current);
     while current != start {
-        let prev = paths[current as usize].from;
+        let prev = paths[W::index(current)].from;
         path.push(prev);
         current = prev;
     }
@@ -62,43 +62,47 @@ fn reconstruct_path(paths: &mut Array<PathVertex>, start: VHandle, goal: VHandle
 /// f_scores are sums of (distances + heuristic) from start to current vertex
 /// h_scores are heuristic values from start to current vertex
 /// g_scores sums of distances from start to current vertex. Not used purely in this implementation, but summed up to f_scores
+/// Weights are read through [WeightStore], so packed integer weights and float or wide weights in the edge data both work.
+/// The handles are those of the storage width, see [Width].
 ///
 // TODO Parallelization potential. Split the graph into multiple subgraphs, and run A* on each subgraph in parallel.
 // Put together the resulting paths
-pub fn a_star<Edges, Heuristic>(edge_storage: &mut Edges, start: VHandle, goal: VHandle, vertices_count: usize, h_score: Heuristic) -> Option<Stack<VHandle>>
+pub fn a_star<Edges, Heuristic, W>(edge_storage: &mut Edges, start: W::VHandle, goal: W::VHandle, vertices_count: usize, h_score: Heuristic) -> Option<Stack<W::VHandle>>
 where
-    Edges: EdgeStore,
-    Heuristic: Fn(VHandle, Edge) -> Weight,
+    Edges: WeightStore<W>,
+    Heuristic: Fn(W::VHandle, W::Edge) -> Edges::WeightType,
+    W: Width,
 {
-    let mut explore_list = BinaryHeap::<MinHeapPair>::with_capacity(vertices_count);
+    let mut explore_list = BinaryHeap::<MinHeapPair<Edges::WeightType, W::VHandle>>::with_capacity(vertices_count);
 
-    let mut f_scores = Array::<PathVertex>::new_with_default(vertices_count, PathVertex{from: 0, f_score: Weight::MAX});
-    explore_list.push(MinHeapPair {vertex: start, f_score: 0});
+    let mut f_scores = Array::new_with_default(vertices_count, PathVertex{from: W::VHandle::default(), f_score: Edges::WeightType::infinity()});
+    explore_list.push(MinHeapPair {vertex: start, f_score: Edges::WeightType::zero()});
 
     while let Some(current_vertex) = explore_list.pop() {
         if current_vertex.vertex == goal{
-            return Some(reconstruct_path(&mut f_scores, start, goal))
+            return Some(reconstruct_path::<_, W>(&mut f_scores, start, goal))
         }
 
-        let neighbors = edge_storage.edges_as_slice(current_vertex.vertex);
-        for neighbor in neighbors {
-            let neighbor_f_score = wgt(*neighbor) + current_vertex.f_score + h_score(current_vertex.vertex, *neighbor);
-            if f_scores[vh(*neighbor) as usize].f_score < neighbor_f_score {
+        for i in edge_storage.edge_indices(current_vertex.vertex) {
+            let neighbor = edge_storage[i];
+            let neighbor_f_score = edge_storage.weight(i).plus(current_vertex.f_score).plus(h_score(current_vertex.vertex, neighbor));
+            if f_scores[W::index(W::vh(neighbor))].f_score < neighbor_f_score {
                 continue;
             }
-            explore_list.push(MinHeapPair::new(vh(*neighbor), neighbor_f_score));
-            f_scores[vh(*neighbor) as usize] = PathVertex{from: current_vertex.vertex, f_score: neighbor_f_score };
+            explore_list.push(MinHeapPair::new(W::vh(neighbor), neighbor_f_score));
+            f_scores[W::index(W::vh(neighbor))] = PathVertex{from: current_vertex.vertex, f_score: neighbor_f_score };
         }
     }
     None
 }
 
 #[inline(always)]
-pub fn dijkstra<Edges>(edge_storage: &mut Edges, start: VHandle, goal: VHandle, vertices_count: usize) -> Option<Stack<VHandle>>
+pub fn dijkstra<Edges, W>(edge_storage: &mut Edges, start: W::VHandle, goal: W::VHandle, vertices_count: usize) -> Option<Stack<W::VHandle>>
 where
-    Edges: EdgeStore
+    Edges: WeightStore<W>,
+    W: Width,
 {
     a_star(edge_storage, start, goal, vertices_count, |_, _| {
-        0
+        Edges::WeightType::zero()
     })
 }
\ No newline at end of file
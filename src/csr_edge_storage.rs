@@ -0,0 +1,182 @@
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+use eta_algorithms::data_structs::array::Array;
+use eta_algorithms::data_structs::fat_ptr::{FatPtr, FatPtrMut};
+use crate::edge_storage::{EdgeStorage, GrowthPolicy, Packed};
+use crate::graph::Error;
+use crate::handles::types::Ci;
+use crate::handles::{Generations, Width, W64};
+use crate::traits::{EdgeStore, WeightSource, WeightStore};
+
+/// Immutable compressed sparse row storage. The edges of vertex `v` are `edges[offsets[v]..offsets[v + 1]]`,
+/// there is no reserve between the blocks. Edges and their weights can be modified in place,
+/// adding or removing vertices and edges requires [CsrEdgeStorage::thaw].
+pub struct CsrEdgeStorage<EdgeType = (), W: Width = W64, Source = Packed> {
+    pub offsets: Vec<Ci>,
+    pub edges: Array<W::Edge>,
+    pub(in crate) edge_data: Vec<EdgeType>,
+    pub(in crate) growth_policy: GrowthPolicy,
+    pub(in crate) sorted: bool,
+    pub(in crate) generations: Generations,
+    pub(in crate) source: PhantomData<Source>,
+}
+
+impl<EdgeType: Default + Clone, W: Width, Source> CsrEdgeStorage<EdgeType, W, Source> {
+    /// Converts back into the mutable storage. Every vertex gets exactly the capacity of its edges,
+    /// the growth policy, sorted adjacency and vertex generations of the frozen storage are restored.
+    pub fn thaw(self) -> EdgeStorage<EdgeType, W, Source> {
+        EdgeStorage::from_csr(self)
+    }
+
+    /// Returns the payloads of the edges of the vertex, in the same order as [EdgeStore::edges_as_slice].
+    #[inline(always)]
+    pub fn edge_data_as_slice(&self, handle: W::VHandle) -> &[EdgeType] {
+        &self.edge_data[self.edges_index(handle)..self.edges_end(handle)]
+    }
+
+    #[inline(always)]
+    pub fn edges_with_data_iter(&self, handle: W::VHandle) -> impl Iterator<Item=(&W::Edge, &EdgeType)> {
+        self.edges_as_slice(handle).iter().zip(self.edge_data_as_slice(handle))
+    }
+
+    #[inline(always)]
+    fn edges_end(&self, handle: W::VHandle) -> usize {
+        self.offsets[W::index(handle) + 1] as usize
+    }
+}
+
+impl<EdgeType: Default + Clone, W: Width, Source> EdgeStore<W> for CsrEdgeStorage<EdgeType, W, Source> {
+    fn create_vertex_entry(&mut self, _size: Ci) -> W::VHandle {
+        panic!("Frozen edge storage can't be modified");
+    }
+
+    #[inline(always)]
+    fn try_create_vertex_entry(&mut self, _size: Ci) -> Result<W::VHandle, Error> {
+        Err(Error::Frozen)
+    }
+
+    fn remove_vertex_entry(&mut self, _handle: W::VHandle) -> W::VHandle {
+        panic!("Frozen edge storage can't be modified");
+    }
+
+    #[inline(always)]
+    fn vertices_len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    #[inline(always)]
+    fn edges_as_slice(&self, handle: W::VHandle) -> &[W::Edge] {
+        &self.edges.as_slice()[self.edges_index(handle)..self.edges_end(handle)]
+    }
+
+    #[inline(always)]
+    fn edges_as_mut_slice(&mut self, handle: W::VHandle) -> &mut [W::Edge] {
+        let range = self.edges_index(handle)..self.edges_end(handle);
+        &mut self.edges.as_mut_slice()[range]
+    }
+
+    #[inline(always)]
+    fn edges_as_ptr(&self, handle: W::VHandle) -> FatPtr<W::Edge> {
+        unsafe {
+            let start = self.edges.as_ptr().add(self.edges_index(handle));
+            let end = self.edges.as_ptr().add(self.edges_end(handle));
+            FatPtr::new(start, end)
+        }
+    }
+
+    #[inline(always)]
+    fn edges_as_mut_ptr(&mut self, handle: W::VHandle) -> FatPtrMut<W::Edge> {
+        let (index, end) = (self.edges_index(handle), self.edges_end(handle));
+        unsafe {
+            let start = self.edges.as_mut_ptr().add(index);
+            let end = self.edges.as_mut_ptr().add(end);
+            FatPtrMut::new(start, end)
+        }
+    }
+
+    #[inline(always)]
+    fn edges_is_empty(&self, handle: W::VHandle) -> bool {
+        self.edges_len(handle) == 0
+    }
+
+    #[inline(always)]
+    fn edges_len(&self, handle: W::VHandle) -> usize {
+        self.edges_end(handle) - self.edges_index(handle)
+    }
+
+    #[inline(always)]
+    fn edges_capacity(&self, handle: W::VHandle) -> usize {
+        self.edges_len(handle)
+    }
+
+    #[inline(always)]
+    fn edges_index(&self, handle: W::VHandle) -> usize {
+        self.offsets[W::index(handle)] as usize
+    }
+
+    #[inline(always)]
+    fn iter(&self) -> impl Iterator<Item=&W::Edge> {
+        self.edges.as_slice().iter()
+    }
+
+    #[inline(always)]
+    fn iter_mut(&mut self) -> impl Iterator<Item=&mut W::Edge> {
+        self.edges.as_mut_slice().iter_mut()
+    }
+
+    #[inline(always)]
+    fn edges_iter(&self, handle: W::VHandle) -> impl Iterator<Item=&W::Edge> {
+        self.edges.iter_range(self.edges_index(handle), self.edges_end(handle))
+    }
+
+    #[inline(always)]
+    fn edges_iter_mut(&mut self, handle: W::VHandle) -> impl Iterator<Item=&mut W::Edge> {
+        let (index, end) = (self.edges_index(handle), self.edges_end(handle));
+        self.edges.iter_range_mut(index, end)
+    }
+
+    unsafe fn edges_iter_mut_unchecked(&mut self, handle: W::VHandle) -> impl Iterator<Item=&mut W::Edge> {
+        let (index, end) = (self.edges_index(handle), self.edges_end(handle));
+        self.edges.iter_range_mut_unchecked(index, end)
+    }
+}
+
+impl<EdgeType: Clone, W: Width, Source> Clone for CsrEdgeStorage<EdgeType, W, Source> {
+    fn clone(&self) -> Self {
+        CsrEdgeStorage {
+            offsets: self.offsets.clone(),
+            edges: self.edges.clone(),
+            edge_data: self.edge_data.clone(),
+            growth_policy: self.growth_policy,
+            sorted: self.sorted,
+            generations: self.generations.clone(),
+            source: PhantomData,
+        }
+    }
+}
+
+impl<EdgeType, W: Width, Source> Index<usize> for CsrEdgeStorage<EdgeType, W, Source> {
+    type Output = W::Edge;
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.edges[index]
+    }
+}
+
+impl<EdgeType, W: Width, Source> IndexMut<usize> for CsrEdgeStorage<EdgeType, W, Source> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.edges[index]
+    }
+}
+
+/// See the [WeightStore] implementation of [EdgeStorage].
+impl<EdgeType: Default + Clone, W: Width, Source: WeightSource<EdgeType, W>> WeightStore<W> for CsrEdgeStorage<EdgeType, W, Source> {
+    type WeightType = Source::WeightType;
+    #[inline(always)]
+    fn weight(&self, index: usize) -> Source::WeightType {
+        Source::weight(self.edges[index], &self.edge_data[index])
+    }
+    #[inline(always)]
+    fn set_weight(&mut self, index: usize, weight: Source::WeightType) {
+        Source::set_weight(&mut self.edges[index], &mut self.edge_data[index], weight);
+    }
+}
\ No newline at end of file
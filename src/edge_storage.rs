@@ -1,49 +1,80 @@
+use std::marker::PhantomData;
+use std::mem::{size_of, take};
 use std::ops::{Index, IndexMut};
 use eta_algorithms::data_structs::array::Array;
 use eta_algorithms::data_structs::fat_ptr::{FatPtr, FatPtrMut};
-use crate::handles::{pack, vh};
-use crate::handles::types::{VHandle, Weight, Edge, Ci};
-use crate::traits::{EdgeManipulate, EdgeConnect, EdgeStore, WeightedEdgeManipulate, WeightedEdgeConnect};
+use crate::csr_edge_storage::CsrEdgeStorage;
+use crate::graph::Error;
+use crate::handles::{GHandle, Generation, Generations, Width, W64};
+use crate::handles::types::{VHandle, Weight, Ci};
+use crate::traits::{EdgeManipulate, EdgeConnect, EdgeStore, EdgeWeight, Generational, PlaceholderEdges, WeightSource, WeightStore, WeightedEdgeManipulate, WeightedEdgeConnect};
+/// `repr(C)` so snapshots can map the entry table directly, see [crate::snapshot].
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
 pub struct VertexEntry {
     pub len: Ci,
     pub capacity: Ci,
     pub offset: Ci,
 }
 
-pub struct EdgeStorageIter<'a> {
-    edges: &'a Array<Edge>,
-    current: *const Edge,
-    end: *const Edge,
-    entries_iter: core::slice::Iter<'a, VertexEntry>,
+/// Controls what happens when the edge block of a vertex is full.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GrowthPolicy {
+    /// Panics once the capacity of the vertex is used up.
+    Strict,
+    /// Moves the block into a released hole or to the end of the edges with at least double the capacity.
+    Double,
 }
-impl<'a> EdgeStorageIter<'a> {
-    pub fn new(edge_storage: &'a EdgeStorage) -> Self {
-        let mut entries_iter = edge_storage.vertex_entries.iter();
-        let next = entries_iter.next().unwrap();
-        let current = unsafe { edge_storage.edges.as_ptr().add(next.offset as usize) };
-        let end = unsafe { current.add(next.len as usize) };
+
+/// Released block in the edges array which can be handed out to a new vertex entry.
+#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct FreeBlock {
+    offset: Ci,
+    capacity: Ci,
+}
+
+/// Iterates all edges, block by block. Empty storages and empty blocks are skipped.
+pub struct EdgeStorageIter<'a, W: Width = W64> {
+    edges: &'a Array<W::Edge>,
+    current: *const W::Edge,
+    end: *const W::Edge,
+    source: W::VHandle,
+    entries_iter: core::iter::Enumerate<core::slice::Iter<'a, VertexEntry>>,
+}
+impl<'a, W: Width> EdgeStorageIter<'a, W> {
+    pub fn new<EdgeType, Source>(edge_storage: &'a EdgeStorage<EdgeType, W, Source>) -> Self {
+        let current = edge_storage.edges.as_ptr();
         EdgeStorageIter {
             edges: &edge_storage.edges,
             current,
-            end,
-            entries_iter,
+            end: current,
+            source: W::VHandle::default(),
+            entries_iter: edge_storage.vertex_entries.iter().enumerate(),
         }
     }
 }
 
 macro_rules! edge_storage_iter_impl {
     ($impl_name:ident $(,$mut_type:ident)?) => {
-        impl<'a> Iterator for $impl_name<'a> {
-            type Item = &'a $($mut_type)? Edge;
+        impl<'a, W: Width> $impl_name<'a, W> {
+            /// Source vertex of the edge returned last.
+            #[inline(always)]
+            pub fn source(&self) -> W::VHandle {
+                self.source
+            }
+        }
+
+        impl<'a, W: Width> Iterator for $impl_name<'a, W> {
+            type Item = &'a $($mut_type)? W::Edge;
             fn next(&mut self) -> Option<Self::Item> {
                 while self.current == self.end {
-                    let result = self.entries_iter.next();
-                    result?;
-
-                    let next = result.unwrap();
+                    let (source, next) = self.entries_iter.next()?;
                     edge_storage_iter_impl!(@get_current self, next $($mut_type)?);
 
+                    self.source = W::handle(source);
                     self.end = unsafe { self.current.add(next.len as usize) };
                 }
                 let result = edge_storage_iter_impl!(@get_result self, next $($mut_type)?);
@@ -69,38 +100,83 @@ macro_rules! edge_storage_iter_impl {
     };
 }
 edge_storage_iter_impl!(EdgeStorageIter);
-pub struct EdgeStorageIterMut<'a> {
-    edges: &'a mut Array<Edge>,
-    current: *mut Edge,
-    end: *mut Edge,
-    entries_iter: core::slice::Iter<'a, VertexEntry>,
+pub struct EdgeStorageIterMut<'a, W: Width = W64> {
+    edges: &'a mut Array<W::Edge>,
+    current: *mut W::Edge,
+    end: *mut W::Edge,
+    source: W::VHandle,
+    entries_iter: core::iter::Enumerate<core::slice::Iter<'a, VertexEntry>>,
 }
-impl<'a> EdgeStorageIterMut<'a> {
-    pub fn new(edge_storage: & 'a mut EdgeStorage) -> Self {
-        let mut entries_iter = edge_storage.vertex_entries.iter();
-        let next = entries_iter.next().unwrap();
-        let current = unsafe { edge_storage.edges.as_mut_ptr().add(next.offset as usize) };
-        let end = unsafe { current.add(next.len as usize) };
+impl<'a, W: Width> EdgeStorageIterMut<'a, W> {
+    pub fn new<EdgeType, Source>(edge_storage: & 'a mut EdgeStorage<EdgeType, W, Source>) -> Self {
+        let current = edge_storage.edges.as_mut_ptr();
         EdgeStorageIterMut {
             edges: &mut edge_storage.edges,
             current,
-            end,
-            entries_iter,
+            end: current,
+            source: W::VHandle::default(),
+            entries_iter: edge_storage.vertex_entries.iter().enumerate(),
         }
     }
 }
 edge_storage_iter_impl!(EdgeStorageIterMut, mut);
 
-pub struct EdgeStorage {
+/// Adjacency storage of packed edges. `EdgeType` is an optional payload stored for every edge in `edge_data`,
+/// parallel to `edges` and addressed by the same offsets. `W` is the width of the packed edges, see [Width].
+/// `Source` selects where [WeightStore] reads the weights from, see [WeightSource].
+pub struct EdgeStorage<EdgeType = (), W: Width = W64, Source = Packed> {
     pub(in crate) reserve: Ci,
-    pub edges: Array<Edge>,
+    pub edges: Array<W::Edge>,
+    edge_data: Vec<EdgeType>,
     vertex_entries: Vec<VertexEntry>,
+    free_blocks: Vec<FreeBlock>,
+    generations: Generations,
+    growth_policy: GrowthPolicy,
+    in_edges: Option<Box<EdgeStorage<(), W>>>,
+    sorted: bool,
+    source: PhantomData<Source>,
+}
+
+/// Reads the weight packed into the edge, for any payload. The default [WeightSource].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Packed;
+
+/// Reads the payload as the weight, the weight packed into the edge is ignored. Lets `dijkstra`, `a_star` and
+/// [DinicGraph](crate::algorithms::dinic::DinicGraph) use float or wide weights.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Payload;
+
+/// Storage whose payloads are the weights of the edges, see [Payload].
+pub type PayloadWeighted<WeightType, W = W64> = EdgeStorage<WeightType, W, Payload>;
+
+impl<EdgeType, W: Width> WeightSource<EdgeType, W> for Packed {
+    type WeightType = W::Weight;
+    #[inline(always)]
+    fn weight(edge: W::Edge, _data: &EdgeType) -> W::Weight {
+        W::wgt(edge)
+    }
+    #[inline(always)]
+    fn set_weight(edge: &mut W::Edge, _data: &mut EdgeType, weight: W::Weight) {
+        *edge = W::set_wgt(*edge, weight);
+    }
 }
 
-impl Default for EdgeStorage {
+impl<WeightType: EdgeWeight, W: Width> WeightSource<WeightType, W> for Payload {
+    type WeightType = WeightType;
+    #[inline(always)]
+    fn weight(_edge: W::Edge, data: &WeightType) -> WeightType {
+        *data
+    }
+    #[inline(always)]
+    fn set_weight(_edge: &mut W::Edge, data: &mut WeightType, weight: WeightType) {
+        *data = weight;
+    }
+}
+
+impl<W: Width> Default for EdgeStorage<(), W> {
     #[inline(always)]
     fn default() -> Self {
-        Self::new()
+        Self::with_edge_data(0)
     }
 }
 
@@ -111,7 +187,14 @@ impl EdgeStorage {
         EdgeStorage {
             reserve: 50,
             edges: Array::new(0),
+            edge_data: Vec::new(),
             vertex_entries: Vec::new(),
+            free_blocks: Vec::new(),
+            generations: Generations::new(),
+            growth_policy: GrowthPolicy::Double,
+            in_edges: None,
+            sorted: false,
+            source: PhantomData,
         }
     }
     /// Creates a new graph with a custom reserve
@@ -119,7 +202,14 @@ impl EdgeStorage {
         EdgeStorage {
             reserve: capacity,
             edges: Array::new(0),
+            edge_data: Vec::new(),
             vertex_entries: Vec::new(),
+            free_blocks: Vec::new(),
+            generations: Generations::new(),
+            growth_policy: GrowthPolicy::Double,
+            in_edges: None,
+            sorted: false,
+            source: PhantomData,
         }
     }
 
@@ -128,74 +218,677 @@ impl EdgeStorage {
         EdgeStorage {
             reserve: 0,
             edges: Array::new(0),
+            edge_data: Vec::new(),
             vertex_entries: Vec::new(),
+            free_blocks: Vec::new(),
+            generations: Generations::new(),
+            growth_policy: GrowthPolicy::Double,
+            in_edges: None,
+            sorted: false,
+            source: PhantomData,
         }
     }
+
+    /// Builds the storage for `vertex_count` vertices from `(from, to, weight)` triples, see [EdgeStorage::from_edges_with_width].
+    #[inline(always)]
+    pub fn from_edges<Edges: IntoIterator<Item=(VHandle, VHandle, Weight)>>(vertex_count: usize, edges: Edges) -> Result<Self, Error> {
+        Self::from_edges_with_width(vertex_count, edges)
+    }
 }
 
-impl EdgeConnect for EdgeStorage {
-    fn connect_edges(&mut self, from: VHandle, to: &[Edge]) {
+impl<W: Width> EdgeStorage<(), W> {
+    /// Builds the storage of the width `W` for `vertex_count` vertices from `(from, to, weight)` triples. Out-degrees are counted first,
+    /// so every block is allocated with exactly the capacity of its edges. Edges keep the order of the input per vertex.
+    pub fn from_edges_with_width<Edges: IntoIterator<Item=(W::VHandle, W::VHandle, W::Weight)>>(vertex_count: usize, edges: Edges) -> Result<Self, Error> {
+        if vertex_count > W::index(W::NONE) {
+            return Err(Error::HandleSpaceExhausted);
+        }
+        let check = |handle: W::VHandle| match W::index(handle) < vertex_count {
+            true => Ok(handle),
+            false => Err(Error::InvalidHandle(handle.into())),
+        };
+        let mut degrees = vec![0usize; vertex_count];
+        let edges = edges.into_iter().map(|(from, to, weight)| {
+            let from = check(from)?;
+            degrees[W::index(from)] += 1;
+            Ok((from, W::pack(check(to)?, weight)))
+        }).collect::<Result<Vec<_>, Error>>()?;
+        if edges.len() > Ci::MAX as usize {
+            return Err(Error::HandleSpaceExhausted);
+        }
+
+        let mut offsets = Vec::with_capacity(vertex_count + 1);
+        let mut offset = 0;
+        offsets.push(0);
+        for degree in degrees.iter_mut() {
+            let start = offset;
+            offset += *degree;
+            offsets.push(offset as Ci);
+            *degree = start; // From now on the next free slot of the vertex
+        }
+        let mut generations = Generations::new();
+        for index in 0..vertex_count {
+            generations.push(index);
+        }
+        let mut rows = Array::new(edges.len());
+        for (from, edge) in edges.iter() {
+            let slot = &mut degrees[W::index(*from)];
+            rows[*slot] = *edge;
+            *slot += 1;
+        }
+        Ok(Self::from_csr(CsrEdgeStorage {
+            offsets,
+            edges: rows,
+            edge_data: vec![(); edges.len()],
+            growth_policy: GrowthPolicy::Double,
+            sorted: false,
+            generations,
+            source: PhantomData,
+        }))
+    }
+}
+
+impl<EdgeType: Default + Clone, W: Width, Source> EdgeStorage<EdgeType, W, Source> {
+    /// Creates a new graph storing an `EdgeType` value for every edge, with a custom reserve.
+    pub fn with_edge_data(reserve: Ci) -> Self {
+        EdgeStorage {
+            reserve,
+            edges: Array::new(0),
+            edge_data: Vec::new(),
+            vertex_entries: Vec::new(),
+            free_blocks: Vec::new(),
+            generations: Generations::new(),
+            growth_policy: GrowthPolicy::Double,
+            in_edges: None,
+            sorted: false,
+            source: PhantomData,
+        }
+    }
+
+    /// Packs the blocks into compressed sparse rows, dropping the reserve. The growth policy,
+    /// sorted adjacency and vertex generations are kept for [CsrEdgeStorage::thaw]. See [CsrEdgeStorage].
+    pub fn freeze(mut self) -> CsrEdgeStorage<EdgeType, W, Source> {
+        let total: usize = self.vertex_entries.iter().map(|entry| entry.len as usize).sum();
+        let mut offsets = Vec::with_capacity(self.vertex_entries.len() + 1);
+        let mut edges = Array::new(total);
+        let mut edge_data = Vec::with_capacity(total);
+        let mut offset = 0;
+        offsets.push(0);
+        for entry in self.vertex_entries.iter() {
+            let start = entry.offset as usize;
+            let len = entry.len as usize;
+            edges.as_mut_slice()[offset..offset + len].copy_from_slice(&self.edges.as_slice()[start..start + len]);
+            edge_data.extend(self.edge_data[start..start + len].iter_mut().map(take));
+            offset += len;
+            offsets.push(offset as Ci);
+        }
+        CsrEdgeStorage {
+            offsets,
+            edges,
+            edge_data,
+            growth_policy: self.growth_policy,
+            sorted: self.sorted,
+            generations: self.generations,
+            source: PhantomData,
+        }
+    }
+
+    /// Takes over the rows as blocks with exactly the capacity of their edges.
+    pub(in crate) fn from_csr(csr: CsrEdgeStorage<EdgeType, W, Source>) -> Self {
+        let vertex_entries: Vec<VertexEntry> = csr.offsets.windows(2).map(|row| VertexEntry {
+            len: row[1] - row[0],
+            capacity: row[1] - row[0],
+            offset: row[0],
+        }).collect();
+        EdgeStorage {
+            reserve: 0,
+            edges: csr.edges,
+            edge_data: csr.edge_data,
+            vertex_entries,
+            free_blocks: Vec::new(),
+            generations: csr.generations,
+            growth_policy: csr.growth_policy,
+            in_edges: None,
+            sorted: csr.sorted,
+            source: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    pub fn growth_policy(&self) -> GrowthPolicy {
+        self.growth_policy
+    }
+
+    /// Sets the behaviour when the edge block of a vertex is full. Defaults to [GrowthPolicy::Double].
+    #[inline(always)]
+    pub fn set_growth_policy(&mut self, policy: GrowthPolicy) {
+        self.growth_policy = policy;
+    }
+
+    /// Moves the blocks next to each other, dropping the holes left by removed and grown vertices.
+    /// Spare capacity of the blocks is kept. Returns the number of bytes reclaimed.
+    pub fn compact(&mut self) -> usize {
+        self.relayout(true)
+    }
+
+    /// Moves the blocks next to each other and trims every block to its length.
+    /// Any later connect has to relocate the block, see [GrowthPolicy]. Returns the number of bytes reclaimed.
+    pub fn shrink_to_fit(&mut self) -> usize {
+        self.relayout(false)
+    }
+
+    fn relayout(&mut self, keep_spare: bool) -> usize {
+        let block_capacity = |entry: &VertexEntry| if keep_spare { entry.capacity } else { entry.len };
+        let total: usize = self.vertex_entries.iter().map(|entry| block_capacity(entry) as usize).sum();
+        let old_bytes = self.edges.capacity() * (size_of::<W::Edge>() + size_of::<EdgeType>())
+            + self.vertex_entries.capacity() * size_of::<VertexEntry>()
+            + self.free_blocks.capacity() * size_of::<FreeBlock>();
+
+        let mut edges = Array::new(total);
+        let mut edge_data = Vec::with_capacity(total);
+        let mut offset = 0;
+        for entry in self.vertex_entries.iter_mut() {
+            let start = entry.offset as usize;
+            let len = entry.len as usize;
+            edges.as_mut_slice()[offset..offset + len].copy_from_slice(&self.edges.as_slice()[start..start + len]);
+            edge_data.extend(self.edge_data[start..start + len].iter_mut().map(take));
+            entry.offset = offset as Ci;
+            entry.capacity = block_capacity(entry);
+            offset += entry.capacity as usize;
+            edge_data.resize(offset, EdgeType::default());
+        }
+        self.edges = edges;
+        self.edge_data = edge_data;
+        self.free_blocks = Vec::new();
+        self.vertex_entries.shrink_to_fit();
+
+        let new_bytes = self.edges.capacity() * (size_of::<W::Edge>() + size_of::<EdgeType>())
+            + self.vertex_entries.capacity() * size_of::<VertexEntry>();
+        let in_edges_reclaimed = match self.in_edges.as_mut() {
+            Some(in_edges) => in_edges.relayout(keep_spare),
+            None => 0,
+        };
+        old_bytes - new_bytes + in_edges_reclaimed
+    }
+
+    /// Returns the offset of a block of `capacity` edges. Released blocks are reused first fit,
+    /// the remainder of a reused block stays free.
+    fn allocate_block(&mut self, capacity: Ci) -> Ci {
+        if capacity > 0 {
+            if let Some(index) = self.free_blocks.iter().position(|block| block.capacity >= capacity) {
+                let block = &mut self.free_blocks[index];
+                let offset = block.offset;
+                block.offset += capacity;
+                block.capacity -= capacity;
+                if block.capacity == 0 {
+                    self.free_blocks.swap_remove(index);
+                }
+                return offset;
+            }
+        }
+        assert!(self.edges.capacity() + capacity as usize <= Ci::MAX as usize, "Edge storage exceeds the handle width");
+        let offset = self.edges.capacity() as Ci;
+        self.edges.extend_by(capacity as usize);
+        self.edge_data.resize(self.edges.capacity(), EdgeType::default());
+        offset
+    }
+
+    /// Returns the edges of the vertex if the handle still refers to a live vertex.
+    #[inline(always)]
+    pub fn edges_checked(&self, handle: GHandle<W::VHandle>) -> Result<&[W::Edge], Error> {
+        let handle = self.validate(handle)?;
+        Ok(self.edges_as_slice(handle))
+    }
+
+    /// Returns the edges of the vertex if the handle still refers to a live vertex.
+    #[inline(always)]
+    pub fn edges_checked_mut(&mut self, handle: GHandle<W::VHandle>) -> Result<&mut [W::Edge], Error> {
+        let handle = self.validate(handle)?;
+        let entry = self.vertex_entries[W::index(handle)];
+        Ok(&mut self.edges.as_mut_slice()[entry.offset as usize..(entry.offset + entry.len) as usize])
+    }
+
+    /// Builds the transpose in one pass. Every edge `a -> b` becomes `b -> a` with the same weight and data
+    /// and every block is allocated with exactly the in-degree of its vertex.
+    pub fn transposed(&self) -> EdgeStorage<EdgeType, W, Source> {
+        self.transposed_with(EdgeType::clone)
+    }
+
+    fn transposed_with<TargetType: Default + Clone, TargetSource>(&self, map_data: impl Fn(&EdgeType) -> TargetType) -> EdgeStorage<TargetType, W, TargetSource> {
+        let vertex_count = self.vertex_entries.len();
+        let mut transposed = EdgeStorage::with_edge_data(0);
+        let mut in_degrees = vec![0 as Ci; vertex_count];
+        for vertex in 0..vertex_count {
+            for edge in self.edges_iter(W::handle(vertex)) {
+                if let Some(degree) = in_degrees.get_mut(W::index(W::vh(*edge))) {
+                    *degree += 1;
+                }
+            }
+        }
+        for degree in in_degrees {
+            transposed.create_vertex_entry(degree);
+        }
+        for vertex in 0..vertex_count {
+            for (edge, data) in self.edges_with_data_iter(W::handle(vertex)) {
+                if transposed.contains_handle(W::vh(*edge)) {
+                    transposed.insert_edges(W::vh(*edge), &[W::set_vh(*edge, W::handle(vertex))], |_| map_data(data));
+                }
+            }
+        }
+        transposed
+    }
+
+    /// Enables the reverse adjacency index. From now on [EdgeConnect], [WeightedEdgeConnect], vertex removal
+    /// and compaction keep it in sync. Edges modified directly through indexing or the mutable iterators are not tracked.
+    pub fn enable_in_edges(&mut self) {
+        if self.in_edges.is_none() {
+            self.in_edges = Some(Box::new(self.transposed_with(|_| ())));
+        }
+    }
+
+    #[inline(always)]
+    pub fn disable_in_edges(&mut self) {
+        self.in_edges = None;
+    }
+
+    #[inline(always)]
+    pub fn has_in_edges(&self) -> bool {
+        self.in_edges.is_some()
+    }
+
+    /// Iterates the incoming edges of the vertex. Every edge packs the source handle and the weight.
+    /// Panics if the index is not enabled, see [EdgeStorage::enable_in_edges].
+    #[inline(always)]
+    pub fn in_edges_iter(&self, handle: W::VHandle) -> impl Iterator<Item=&W::Edge> {
+        self.in_edges.as_ref().expect("In-edge index is not enabled").edges_iter(handle)
+    }
+
+    #[inline(always)]
+    pub fn in_edges_len(&self, handle: W::VHandle) -> usize {
+        self.in_edges.as_ref().expect("In-edge index is not enabled").edges_len(handle)
+    }
+
+    /// In sorted mode every block is kept ordered by target handle, so [EdgeStore::find_edge] uses binary search.
+    /// Connecting and disconnecting shift the block instead of appending and swap removing.
+    /// Enabling the mode sorts all blocks, reordering edges written directly is up to the caller.
+    pub fn set_sorted_adjacency(&mut self, sorted: bool) {
+        if sorted && !self.sorted {
+            for handle in 0..self.vertex_entries.len() {
+                self.sort_block(W::handle(handle));
+            }
+        }
+        self.sorted = sorted;
+    }
+
+    #[inline(always)]
+    pub fn is_sorted_adjacency(&self) -> bool {
+        self.sorted
+    }
+
+    #[inline(always)]
+    pub fn vertices_len(&self) -> usize {
+        self.vertex_entries.len()
+    }
+
+    /// Sorts the block of the vertex by target handle, keeping the data next to its edge.
+    fn sort_block(&mut self, handle: W::VHandle) {
+        let entry = self.vertex_entries[W::index(handle)];
+        let range = entry.offset as usize..(entry.offset + entry.len) as usize;
+        let edges = &mut self.edges.as_mut_slice()[range.clone()];
+        let data = &mut self.edge_data[range];
+        let mut pairs: Vec<(W::Edge, EdgeType)> = edges.iter().copied().zip(data.iter_mut().map(take)).collect();
+        pairs.sort_by_key(|(edge, _)| W::vh(*edge));
+        for (i, (edge, edge_data)) in pairs.into_iter().enumerate() {
+            edges[i] = edge;
+            data[i] = edge_data;
+        }
+    }
+
+    /// Returns the payloads of the edges of the vertex, in the same order as [EdgeStore::edges_as_slice].
+    #[inline(always)]
+    pub fn edge_data_as_slice(&self, handle: W::VHandle) -> &[EdgeType] {
+        let entry = self.vertex_entries[W::index(handle)];
+        &self.edge_data[entry.offset as usize..(entry.offset + entry.len) as usize]
+    }
+
+    #[inline(always)]
+    pub fn edge_data_as_mut_slice(&mut self, handle: W::VHandle) -> &mut [EdgeType] {
+        let entry = self.vertex_entries[W::index(handle)];
+        &mut self.edge_data[entry.offset as usize..(entry.offset + entry.len) as usize]
+    }
+
+    /// Returns the payload of the edge at the global index, see [EdgeStore::edges_index].
+    #[inline(always)]
+    pub fn edge_data(&self, index: usize) -> &EdgeType {
+        &self.edge_data[index]
+    }
+
+    #[inline(always)]
+    pub fn edge_data_mut(&mut self, index: usize) -> &mut EdgeType {
+        &mut self.edge_data[index]
+    }
+
+    /// Iterates the edges of the vertex together with their payloads.
+    #[inline(always)]
+    pub fn edges_with_data_iter(&self, handle: W::VHandle) -> impl Iterator<Item=(&W::Edge, &EdgeType)> {
+        self.edges_as_slice(handle).iter().zip(self.edge_data_as_slice(handle))
+    }
+
+    #[inline(always)]
+    pub fn edges_with_data_iter_mut(&mut self, handle: W::VHandle) -> impl Iterator<Item=(&mut W::Edge, &mut EdgeType)> {
+        let entry = self.vertex_entries[W::index(handle)];
+        let range = entry.offset as usize..(entry.offset + entry.len) as usize;
+        self.edges.as_mut_slice()[range.clone()].iter_mut().zip(self.edge_data[range].iter_mut())
+    }
+
+    /// Connects `from -> to` and stores `data` next to the edge.
+    #[inline(always)]
+    pub fn connect_with_data(&mut self, from: W::VHandle, to: W::VHandle, weight: W::Weight, data: EdgeType) {
+        let mut data = Some(data);
+        self.insert_edges(from, &[W::pack(to, weight)], |_| data.take().unwrap_or_default());
+    }
+
+    /// Checked variant of [EdgeStorage::connect_with_data], see [EdgeConnect::try_connect_edges].
+    pub fn try_connect_with_data(&mut self, from: W::VHandle, to: W::VHandle, weight: W::Weight, data: EdgeType) -> Result<(), Error> {
+        self.check_insert(from, &[W::pack(to, weight)])?;
+        self.connect_with_data(from, to, weight, data);
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn contains_handle(&self, handle: W::VHandle) -> bool {
+        W::index(handle) < self.vertex_entries.len()
+    }
+
+    /// Removes the first edge of the vertex matching the predicate together with its data.
+    /// Swap removes unless the adjacency is sorted.
+    fn remove_edge<Predicate: Fn(W::Edge) -> bool>(&mut self, from: W::VHandle, predicate: Predicate) -> Option<(W::Edge, EdgeType)> {
+        let position = self.edges_as_slice(from).iter().position(|edge| predicate(*edge))?;
+        Some(self.remove_edge_at(from, position))
+    }
+
+    /// Removes the edge at `position` within the block of the vertex, see [EdgeStorage::remove_edge].
+    fn remove_edge_at(&mut self, from: W::VHandle, position: usize) -> (W::Edge, EdgeType) {
+        let entry = self.vertex_entries[W::index(from)];
+        let range = entry.offset as usize..(entry.offset + entry.len) as usize;
+        let edges = &mut self.edges.as_mut_slice()[range.clone()];
+        let data = &mut self.edge_data[range];
+        let removed = (edges[position], take(&mut data[position]));
+        let last = edges.len() - 1;
+        if self.sorted {
+            edges.copy_within(position + 1.., position);
+            data[position..].rotate_left(1);
+        } else {
+            edges[position] = edges[last]; // Swap the last element for the empty one
+            data.swap(position, last);
+        }
+        self.vertex_entries[W::index(from)].len -= 1;
+        removed
+    }
+
+    /// Drops the reverse of the removed edge from the in-edge index.
+    fn remove_in_edge(&mut self, from: W::VHandle, edge: W::Edge) {
+        if let Some(in_edges) = self.in_edges.as_mut() {
+            if in_edges.contains_handle(W::vh(edge)) {
+                let reversed = W::set_vh(edge, from);
+                in_edges.remove_edge(W::vh(edge), |in_edge| in_edge == reversed);
+            }
+        }
+    }
+
+    /// Checks the handles and that the edges fit, the same way [EdgeConnect::try_connect_edges] documents.
+    fn check_insert(&self, from: W::VHandle, to: &[W::Edge]) -> Result<(), Error> {
+        self.check_handle(from)?;
+        for edge in to {
+            self.check_handle(W::vh(*edge))?;
+        }
+
+        let entry = self.vertex_entries[W::index(from)];
+        let required = entry.len as usize + to.len();
+        if required > entry.capacity as usize {
+            if self.growth_policy == GrowthPolicy::Strict {
+                return Err(Error::CapacityExceeded {
+                    handle: from.into(),
+                    capacity: entry.capacity as usize,
+                    required,
+                });
+            }
+            // Assumes the grown block is appended, reusing a hole would only make it fit better
+            let capacity = required.max(entry.capacity as usize * 2);
+            if self.edges.capacity() + capacity > Ci::MAX as usize {
+                return Err(Error::HandleSpaceExhausted);
+            }
+        }
+        Ok(())
+    }
+
+    /// Appends the edges, or inserts them in order in sorted mode. `data` returns the payload of the i-th edge.
+    fn insert_edges(&mut self, from: W::VHandle, to: &[W::Edge], mut data: impl FnMut(usize) -> EdgeType) {
         let len = self.edges_len(from);
         let new_size = len + to.len();
 
         if new_size > self.edges_capacity(from) {
-            panic!("Edge size is greater than the allocated size");
+            match self.growth_policy {
+                GrowthPolicy::Strict => panic!("Edge size is greater than the allocated size"),
+                GrowthPolicy::Double => self.grow(from, new_size),
+            }
         }
 
-        let data = self.edges_as_mut_slice(from);
-        data[len..new_size].copy_from_slice(to);
-        self.vertex_entries[from as usize].len = new_size as Ci;
-    }
+        let entry = self.vertex_entries[W::index(from)];
+        let range = entry.offset as usize..(entry.offset + entry.capacity) as usize;
+        let edges = &mut self.edges.as_mut_slice()[range.clone()];
+        let edge_data = &mut self.edge_data[range];
+        for (inserted, edge) in to.iter().enumerate() {
+            let end = len + inserted;
+            edges[end] = *edge;
+            edge_data[end] = data(inserted);
+            if self.sorted {
+                let position = edges[..end].partition_point(|existing| W::vh(*existing) <= W::vh(*edge));
+                edges[position..=end].rotate_right(1);
+                edge_data[position..=end].rotate_right(1);
+            }
+        }
+        self.vertex_entries[W::index(from)].len = new_size as Ci;
 
-    fn disconnect(&mut self, from: VHandle, to: VHandle) {
-        let data = self.edges_as_mut_ptr(from);
-        let len = &mut self.vertex_entries[from as usize].len;
-        unsafe {
-            for edge in data {
-                if vh(*edge) == to {
-                    *edge = *data.end.offset(-1); // Swap the last element for the empty one
-                    *len -= 1;
-                    break;
+        if let Some(in_edges) = self.in_edges.as_mut() {
+            for edge in to {
+                if in_edges.contains_handle(W::vh(*edge)) {
+                    in_edges.connect_edges(W::vh(*edge), &[W::set_vh(*edge, from)]);
                 }
             }
         }
     }
+
     #[inline(always)]
-    fn connect(&mut self, from: VHandle, to: VHandle) {
-        self.connect_edges(from, &[pack(to, 0)]);
+    fn check_handle(&self, handle: W::VHandle) -> Result<(), Error> {
+        if !self.contains_handle(handle) {
+            return Err(Error::InvalidHandle(handle.into()));
+        }
+        Ok(())
+    }
+
+    /// Moves the edges of the vertex into a block which fits at least `required` edges.
+    fn grow(&mut self, handle: W::VHandle, required: usize) {
+        let entry = self.vertex_entries[W::index(handle)];
+        let capacity = required.max(entry.capacity as usize * 2) as Ci;
+        let offset = self.allocate_block(capacity);
+        let start = entry.offset as usize;
+        self.edges.as_mut_slice().copy_within(start..start + entry.len as usize, offset as usize);
+        for i in 0..entry.len as usize {
+            self.edge_data[offset as usize + i] = take(&mut self.edge_data[start + i]);
+        }
+        self.release_block(entry.offset, entry.capacity);
+
+        let entry = &mut self.vertex_entries[W::index(handle)];
+        entry.offset = offset;
+        entry.capacity = capacity;
+    }
+
+    /// Returns the block to the free list, merging it with adjacent free blocks.
+    fn release_block(&mut self, offset: Ci, capacity: Ci) {
+        if capacity == 0 {
+            return;
+        }
+        self.edge_data[offset as usize..(offset + capacity) as usize].fill_with(EdgeType::default);
+        let mut released = FreeBlock { offset, capacity };
+        let mut i = 0;
+        while i < self.free_blocks.len() {
+            let block = self.free_blocks[i];
+            if block.offset + block.capacity == released.offset {
+                released.offset = block.offset;
+                released.capacity += block.capacity;
+            } else if released.offset + released.capacity == block.offset {
+                released.capacity += block.capacity;
+            } else {
+                i += 1;
+                continue;
+            }
+            self.free_blocks.swap_remove(i);
+        }
+        self.free_blocks.push(released);
+    }
+}
+
+impl<EdgeType: Default + Clone, W: Width, Source> EdgeConnect<W> for EdgeStorage<EdgeType, W, Source> {
+    /// The payload of the new edges is `EdgeType::default()`.
+    #[inline(always)]
+    fn connect_edges(&mut self, from: W::VHandle, to: &[W::Edge]) {
+        self.insert_edges(from, to, |_| EdgeType::default());
+    }
+
+    fn disconnect(&mut self, from: W::VHandle, to: W::VHandle) -> bool {
+        match self.remove_edge(from, |edge| W::vh(edge) == to) {
+            Some((edge, _)) => {
+                self.remove_in_edge(from, edge);
+                true
+            }
+            None => false,
+        }
+    }
+    #[inline(always)]
+    fn connect(&mut self, from: W::VHandle, to: W::VHandle) {
+        self.connect_edges(from, &[W::pack(to, W::Weight::default())]);
+    }
+
+    fn try_connect_edges(&mut self, from: W::VHandle, to: &[W::Edge]) -> Result<(), Error> {
+        self.check_insert(from, to)?;
+        self.connect_edges(from, to);
+        Ok(())
+    }
+
+    fn try_disconnect(&mut self, from: W::VHandle, to: W::VHandle) -> Result<(), Error> {
+        self.check_handle(from)?;
+        self.check_handle(to)?;
+        match self.disconnect(from, to) {
+            true => Ok(()),
+            false => Err(Error::NoEdge { from: from.into(), to: to.into() }),
+        }
+    }
+
+    #[inline(always)]
+    fn try_connect(&mut self, from: W::VHandle, to: W::VHandle) -> Result<(), Error> {
+        self.try_connect_edges(from, &[W::pack(to, W::Weight::default())])
     }
 }
 
-impl WeightedEdgeConnect for EdgeStorage {
-    fn connect_weighted(&mut self, from: VHandle, to: VHandle, weight: Weight) {
-        self.connect_edges(from, &[pack(to, weight)]);
+impl<EdgeType: Default + Clone, W: Width, Source> WeightedEdgeConnect<W> for EdgeStorage<EdgeType, W, Source> {
+    fn connect_weighted(&mut self, from: W::VHandle, to: W::VHandle, weight: W::Weight) {
+        self.connect_edges(from, &[W::pack(to, weight)]);
+    }
+
+    fn try_connect_weighted<T: TryInto<W::Weight>>(&mut self, from: W::VHandle, to: W::VHandle, weight: T) -> Result<(), Error> {
+        let weight = weight.try_into().map_err(|_| Error::WeightOutOfRange)?;
+        self.try_connect_edges(from, &[W::pack(to, weight)])
     }
 }
-impl EdgeStore for EdgeStorage {
-    fn create_vertex_entry(&mut self, size: Ci) -> VHandle {
-        let offset = self.edges.capacity() as Ci;
-        self.edges.extend_by((size + self.reserve) as usize);
+impl<EdgeType: Default + Clone, W: Width, Source> EdgeStore<W> for EdgeStorage<EdgeType, W, Source> {
+    fn create_vertex_entry(&mut self, size: Ci) -> W::VHandle {
+        let offset = self.allocate_block(size + self.reserve);
         self.vertex_entries.push(VertexEntry {
             len: 0,
             capacity: self.reserve + size,
             offset,
         });
-        (self.vertex_entries.len() - 1) as VHandle
+        self.generations.push(self.vertex_entries.len() - 1);
+        if let Some(in_edges) = self.in_edges.as_mut() {
+            in_edges.create_vertex_entry(0);
+        }
+        W::handle(self.vertex_entries.len() - 1)
+    }
+
+    fn try_create_vertex_entry(&mut self, size: Ci) -> Result<W::VHandle, Error> {
+        if self.vertex_entries.len() >= W::index(W::NONE) {
+            return Err(Error::HandleSpaceExhausted);
+        }
+        let capacity = size as usize + self.reserve as usize;
+        if self.edges.capacity() + capacity > Ci::MAX as usize {
+            return Err(Error::HandleSpaceExhausted);
+        }
+        Ok(self.create_vertex_entry(size))
+    }
+
+    fn remove_vertex_entry(&mut self, handle: W::VHandle) -> W::VHandle {
+        let last = W::handle(self.vertex_entries.len() - 1);
+        for vertex in 0..self.vertex_entries.len() {
+            let entry = &mut self.vertex_entries[vertex];
+            let range = entry.offset as usize..(entry.offset + entry.len) as usize;
+            let edges = &mut self.edges.as_mut_slice()[range.clone()];
+            let data = &mut self.edge_data[range];
+            let mut i = 0;
+            let mut len = edges.len();
+            let mut modified = false;
+            while i < len {
+                let target = W::vh(edges[i]);
+                if target == handle {
+                    len -= 1;
+                    edges[i] = edges[len]; // Swap the last element for the removed one
+                    data.swap(i, len);
+                    data[len] = EdgeType::default();
+                    modified = true;
+                    continue;
+                }
+                if target == last {
+                    edges[i] = W::set_vh(edges[i], handle);
+                    modified = true;
+                }
+                i += 1;
+            }
+            entry.len = len as Ci;
+            if self.sorted && modified {
+                self.sort_block(W::handle(vertex));
+            }
+        }
+
+        let removed = self.vertex_entries.swap_remove(W::index(handle));
+        self.generations.swap_remove(W::index(handle), W::index(last));
+        self.release_block(removed.offset, removed.capacity);
+        if let Some(in_edges) = self.in_edges.as_mut() {
+            in_edges.remove_vertex_entry(handle);
+        }
+        if handle == last {
+            return W::NONE;
+        }
+        last
     }
     #[inline(always)]
-    fn edges_as_slice(&self, vertex: VHandle) -> &[Edge] {
-        let edge_chunk_meta = self.vertex_entries[vertex as usize];
+    fn vertices_len(&self) -> usize {
+        self.vertex_entries.len()
+    }
+
+    #[inline(always)]
+    fn edges_as_slice(&self, vertex: W::VHandle) -> &[W::Edge] {
+        let edge_chunk_meta = self.vertex_entries[W::index(vertex)];
         &self.edges.as_slice()[edge_chunk_meta.offset as usize..(edge_chunk_meta.offset + edge_chunk_meta.len) as usize]
     }
     #[inline(always)]
-    fn edges_as_mut_slice(&mut self, vertex: VHandle) -> &mut [Edge] {
-        let edge_chunk_meta = self.vertex_entries[vertex as usize];
+    fn edges_as_mut_slice(&mut self, vertex: W::VHandle) -> &mut [W::Edge] {
+        let edge_chunk_meta = self.vertex_entries[W::index(vertex)];
         &mut self.edges.as_mut_slice()[ edge_chunk_meta.offset as usize..(edge_chunk_meta.offset + edge_chunk_meta.capacity) as usize]
     }
 
     #[inline(always)]
-    fn edges_as_ptr(&self, vertex: VHandle) -> FatPtr<Edge> {
-        let edge_chunk_meta = self.vertex_entries[vertex as usize];
+    fn edges_as_ptr(&self, vertex: W::VHandle) -> FatPtr<W::Edge> {
+        let edge_chunk_meta = self.vertex_entries[W::index(vertex)];
         unsafe{
             let start = self.edges.as_ptr().add(edge_chunk_meta.offset as usize);
             let end = start.add(edge_chunk_meta.len as usize);
@@ -204,8 +897,8 @@ impl EdgeStore for EdgeStorage {
     }
 
     #[inline(always)]
-    fn edges_as_mut_ptr(&mut self, vertex: VHandle) -> FatPtrMut<Edge> {
-        let edge_chunk_meta = self.vertex_entries[vertex as usize];
+    fn edges_as_mut_ptr(&mut self, vertex: W::VHandle) -> FatPtrMut<W::Edge> {
+        let edge_chunk_meta = self.vertex_entries[W::index(vertex)];
         unsafe{
             let start = self.edges.as_mut_ptr().add(edge_chunk_meta.offset as usize);
             let end = start.add(edge_chunk_meta.len as usize);
@@ -213,79 +906,302 @@ impl EdgeStore for EdgeStorage {
         }
     }
     #[inline(always)]
-    fn edges_is_empty(&self, handle: VHandle) -> bool {
+    fn edges_is_empty(&self, handle: W::VHandle) -> bool {
         self.edges_len(handle) == 0
     }
 
     #[inline(always)]
-    fn edges_len(&self, handle: VHandle) -> usize {
-        self.vertex_entries[handle as usize].len as usize
+    fn edges_len(&self, handle: W::VHandle) -> usize {
+        self.vertex_entries[W::index(handle)].len as usize
     }
     #[inline(always)]
-    fn edges_capacity(&self, handle: VHandle) -> usize {
-        self.vertex_entries[handle as usize].capacity as usize
+    fn edges_capacity(&self, handle: W::VHandle) -> usize {
+        self.vertex_entries[W::index(handle)].capacity as usize
     }
     #[inline(always)]
-    fn edges_index(&self, vertex: VHandle) -> usize {
-        self.vertex_entries[vertex as usize].offset as usize
+    fn edges_index(&self, vertex: W::VHandle) -> usize {
+        self.vertex_entries[W::index(vertex)].offset as usize
     }
     #[inline(always)]
-    fn iter(&self) -> impl Iterator<Item=&Edge> {
+    fn iter(&self) -> impl Iterator<Item=&W::Edge> {
         EdgeStorageIter::new(self)
     }
 
     #[inline(always)]
-    fn iter_mut(&mut self) -> impl Iterator<Item=&mut Edge> {
+    fn iter_mut(&mut self) -> impl Iterator<Item=&mut W::Edge> {
         EdgeStorageIterMut::new(self)
     }
 
+    fn edges_mut(&mut self) -> impl Iterator<Item=(W::VHandle, &mut W::Edge)> {
+        let mut iter = EdgeStorageIterMut::new(self);
+        std::iter::from_fn(move || {
+            let edge = iter.next()?;
+            Some((iter.source(), edge))
+        })
+    }
+
     #[inline(always)]
-    fn edges_iter(&self, handle: VHandle) -> impl Iterator<Item=&Edge> {
+    fn edges_iter(&self, handle: W::VHandle) -> impl Iterator<Item=&W::Edge> {
         let index = self.edges_index(handle);
         let end = index + self.edges_len(handle);
         self.edges.iter_range(index, end)
     }
 
     #[inline(always)]
-    fn edges_iter_mut(&mut self, handle: VHandle) -> impl Iterator<Item=&mut Edge> {
+    fn edges_iter_mut(&mut self, handle: W::VHandle) -> impl Iterator<Item=&mut W::Edge> {
         let index = self.edges_index(handle);
         let end = index + self.edges_len(handle);
         self.edges.iter_range_mut(index, end)
     }
 
-    unsafe fn edges_iter_mut_unchecked(&mut self, handle: VHandle) -> impl Iterator<Item=&mut Edge> {
+    unsafe fn edges_iter_mut_unchecked(&mut self, handle: W::VHandle) -> impl Iterator<Item=&mut W::Edge> {
         self.edges.iter_range_mut_unchecked(self.edges_index(handle), self.edges_len(handle))
     }
+
+    fn find_edge(&self, from: W::VHandle, to: W::VHandle) -> Option<usize> {
+        let edges = self.edges_as_slice(from);
+        let position = match self.sorted {
+            true => edges.partition_point(|edge| W::vh(*edge) < to),
+            false => edges.iter().position(|edge| W::vh(*edge) == to)?,
+        };
+        if position == edges.len() || W::vh(edges[position]) != to {
+            return None;
+        }
+        Some(self.edges_index(from) + position)
+    }
+
+    fn set_edge_weight(&mut self, from: W::VHandle, to: W::VHandle, weight: W::Weight) -> Result<(), Error> {
+        let index = self.find_edge(from, to).ok_or(Error::NoEdge { from: from.into(), to: to.into() })?;
+        let edge = self.edges[index];
+        self.edges[index] = W::set_wgt(edge, weight);
+
+        if let Some(in_edges) = self.in_edges.as_mut() {
+            if in_edges.contains_handle(to) {
+                let reversed = W::set_vh(edge, from);
+                if let Some(in_edge) = in_edges.edges_iter_mut(to).find(|in_edge| **in_edge == reversed) {
+                    *in_edge = W::set_wgt(reversed, weight);
+                }
+            }
+        }
+        Ok(())
+    }
 }
-impl Clone for EdgeStorage {
+impl<EdgeType: Clone, W: Width, Source> Clone for EdgeStorage<EdgeType, W, Source> {
     fn clone(&self) -> Self {
         EdgeStorage {
             reserve: self.reserve,
             edges: self.edges.clone(),
+            edge_data: self.edge_data.clone(),
             vertex_entries: self.vertex_entries.clone(),
+            free_blocks: self.free_blocks.clone(),
+            generations: self.generations.clone(),
+            growth_policy: self.growth_policy,
+            in_edges: self.in_edges.clone(),
+            sorted: self.sorted,
+            source: PhantomData,
         }
     }
 
     fn clone_from(&mut self, source: &Self) {
         self.reserve = source.reserve;
         self.edges.clone_from(&source.edges);
+        self.edge_data.clone_from(&source.edge_data);
         self.vertex_entries.clone_from(&source.vertex_entries);
+        self.free_blocks.clone_from(&source.free_blocks);
+        self.generations.clone_from(&source.generations);
+        self.growth_policy = source.growth_policy;
+        self.in_edges.clone_from(&source.in_edges);
+        self.sorted = source.sorted;
     }
 }
 
-impl Index<usize> for EdgeStorage {
-    type Output = Edge;
+impl<EdgeType, W: Width, Source> Index<usize> for EdgeStorage<EdgeType, W, Source> {
+    type Output = W::Edge;
     fn index(&self, index: usize) -> &Self::Output {
         &self.edges[index]
     }
 }
 
-impl IndexMut<usize> for EdgeStorage {
+impl<EdgeType, W: Width, Source> IndexMut<usize> for EdgeStorage<EdgeType, W, Source> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
         &mut self.edges[index]
     }
 }
 
-impl EdgeManipulate for EdgeStorage {}
+impl<EdgeType: Default + Clone, W: Width, Source> Generational<W> for EdgeStorage<EdgeType, W, Source> {
+    #[inline(always)]
+    fn generation(&self, handle: W::VHandle) -> Option<Generation> {
+        self.generations.get(W::index(handle), self.vertex_entries.len())
+    }
+
+    #[inline(always)]
+    fn slot_generation(&self, handle: W::VHandle) -> Option<Generation> {
+        self.generations.slot(W::index(handle))
+    }
+}
+
+impl<EdgeType: Default + Clone, W: Width, Source> EdgeManipulate<W> for EdgeStorage<EdgeType, W, Source> {
+    /// The payloads stay with their edges.
+    fn disconnect_last(&mut self, from: W::VHandle, edge: W::Edge) {
+        if let Some(position) = self.edges_as_slice(from).iter().rposition(|existing| *existing == edge) {
+            self.remove_edge_at(from, position);
+            self.remove_in_edge(from, edge);
+        }
+    }
+}
+
+impl<EdgeType: Default + Clone, W: Width, Source> PlaceholderEdges<W> for EdgeStorage<EdgeType, W, Source> {}
+
+impl<EdgeType: Default + Clone, W: Width, Source> WeightedEdgeManipulate<W> for EdgeStorage<EdgeType, W, Source> {}
+
+/// The weights come from the [WeightSource], payloads which are no weights use the packed weight.
+impl<EdgeType: Default + Clone, W: Width, Source: WeightSource<EdgeType, W>> WeightStore<W> for EdgeStorage<EdgeType, W, Source> {
+    type WeightType = Source::WeightType;
+    #[inline(always)]
+    fn weight(&self, index: usize) -> Source::WeightType {
+        Source::weight(self.edges[index], &self.edge_data[index])
+    }
+    #[inline(always)]
+    fn set_weight(&mut self, index: usize, weight: Source::WeightType) {
+        Source::set_weight(&mut self.edges[index], &mut self.edge_data[index], weight);
+    }
+
+    fn edges(&self) -> impl Iterator<Item=(W::VHandle, W::VHandle, Source::WeightType)> {
+        self.vertex_entries.iter().enumerate().flat_map(move |(from, entry)| {
+            let range = entry.offset as usize..(entry.offset + entry.len) as usize;
+            self.edges.as_slice()[range.clone()].iter().zip(&self.edge_data[range])
+                .map(move |(edge, data)| (W::handle(from), W::vh(*edge), Source::weight(*edge, data)))
+        })
+    }
+}
+
+/// Serialized form of [EdgeStorage]. Only the used part of every block is written, the layout is kept
+/// through the entries, so the storage round-trips exactly. The in-edge index is rebuilt on load.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "EdgeStorage")]
+struct EdgeStorageFormat<EdgeType, Edge> {
+    reserve: Ci,
+    capacity: usize,
+    vertex_entries: Vec<VertexEntry>,
+    free_blocks: Vec<FreeBlock>,
+    generations: Generations,
+    growth_policy: GrowthPolicy,
+    sorted: bool,
+    in_edges: bool,
+    edges: Vec<Edge>,
+    edge_data: Vec<EdgeType>,
+}
+
+#[cfg(feature = "serde")]
+impl<EdgeType: serde::Serialize + Default + Clone, W: Width, Source> serde::Serialize for EdgeStorage<EdgeType, W, Source>
+where
+    W::Edge: serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut edges = Vec::new();
+        let mut edge_data = Vec::new();
+        for handle in (0..self.vertex_entries.len()).map(W::handle) {
+            edges.extend_from_slice(self.edges_as_slice(handle));
+            edge_data.extend(self.edge_data_as_slice(handle));
+        }
+        EdgeStorageFormat {
+            reserve: self.reserve,
+            capacity: self.edges.capacity(),
+            vertex_entries: self.vertex_entries.clone(),
+            free_blocks: self.free_blocks.clone(),
+            generations: self.generations.clone(),
+            growth_policy: self.growth_policy,
+            sorted: self.sorted,
+            in_edges: self.in_edges.is_some(),
+            edges,
+            edge_data,
+        }.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, EdgeType: serde::Deserialize<'de> + Default + Clone, W: Width, Source> serde::Deserialize<'de> for EdgeStorage<EdgeType, W, Source>
+where
+    W::Edge: serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let format = EdgeStorageFormat::deserialize(deserializer)?;
+        EdgeStorage::from_format(format).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<EdgeType: Default + Clone, W: Width, Source> EdgeStorage<EdgeType, W, Source> {
+    /// Checks that every block lies inside the edges, blocks don't overlap and every edge points at a vertex or `NONE`.
+    fn from_format(format: EdgeStorageFormat<EdgeType, W::Edge>) -> Result<Self, &'static str> {
+        let vertex_count = format.vertex_entries.len();
+        if format.capacity > Ci::MAX as usize || vertex_count > W::index(W::NONE) {
+            return Err("edge storage exceeds the handle width");
+        }
+        if format.generations.len() < vertex_count {
+            return Err("missing generations");
+        }
 
-impl WeightedEdgeManipulate for EdgeStorage {}
\ No newline at end of file
+        let mut blocks: Vec<(usize, usize)> = Vec::with_capacity(vertex_count + format.free_blocks.len());
+        for entry in format.vertex_entries.iter() {
+            if entry.len > entry.capacity {
+                return Err("vertex entry length exceeds its capacity");
+            }
+            blocks.push((entry.offset as usize, entry.capacity as usize));
+        }
+        blocks.extend(format.free_blocks.iter().map(|block| (block.offset as usize, block.capacity as usize)));
+        blocks.retain(|(_, capacity)| *capacity > 0);
+        blocks.sort_unstable();
+        let mut end = 0;
+        for (offset, capacity) in blocks {
+            if offset < end {
+                return Err("edge blocks overlap");
+            }
+            end = offset + capacity;
+        }
+        if end > format.capacity {
+            return Err("edge block out of bounds");
+        }
+
+        let used: usize = format.vertex_entries.iter().map(|entry| entry.len as usize).sum();
+        if format.edges.len() != used || format.edge_data.len() != used {
+            return Err("edge count does not match the vertex entries");
+        }
+        if format.edges.iter().any(|edge| W::index(W::vh(*edge)) >= vertex_count && W::vh(*edge) != W::NONE) {
+            return Err("edge points at a missing vertex");
+        }
+
+        let mut edges = Array::new_with_default(format.capacity, W::Edge::default());
+        let mut edge_data = vec![EdgeType::default(); format.capacity];
+        let mut source = format.edges.iter().zip(format.edge_data);
+        for entry in format.vertex_entries.iter() {
+            for slot in entry.offset as usize..(entry.offset + entry.len) as usize {
+                let (edge, data) = source.next().unwrap();
+                edges[slot] = *edge;
+                edge_data[slot] = data;
+            }
+            let block = &edges.as_slice()[entry.offset as usize..(entry.offset + entry.len) as usize];
+            if format.sorted && block.windows(2).any(|pair| W::vh(pair[0]) > W::vh(pair[1])) {
+                return Err("sorted adjacency is not sorted");
+            }
+        }
+
+        let mut storage = EdgeStorage {
+            reserve: format.reserve,
+            edges,
+            edge_data,
+            vertex_entries: format.vertex_entries,
+            free_blocks: format.free_blocks,
+            generations: format.generations,
+            growth_policy: format.growth_policy,
+            in_edges: None,
+            sorted: format.sorted,
+            source: PhantomData,
+        };
+        if format.in_edges {
+            storage.enable_in_edges();
+        }
+        Ok(storage)
+    }
+}
\ No newline at end of file
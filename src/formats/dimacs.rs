@@ -0,0 +1,150 @@
+//! DIMACS shortest path (`.gr`) and maximum flow (`.max`) files.
+//!
+//! Vertex `i` of the file gets the handle `i - 1` and the value `i`.
+
+use std::io;
+use std::io::{BufRead, Write};
+use crate::formats::{check_edge_count, check_vertex_count, field, IdGraph, ParseError, ReadError, RESERVE_LIMIT};
+use crate::handles::{Width, W64};
+use crate::traits::EdgeStore;
+use crate::weighted_graph::WeightedGraph;
+
+/// Maximum flow instance, the capacities are the weights.
+pub struct MaxFlowProblem<W: Width = W64> {
+    pub graph: IdGraph<W>,
+    pub source: W::VHandle,
+    pub sink: W::VHandle,
+}
+
+/// Parsed body of a DIMACS file.
+struct Dimacs<W: Width> {
+    vertex_count: usize,
+    edges: Vec<(W::VHandle, W::VHandle, W::Weight)>,
+    source: Option<W::VHandle>,
+    sink: Option<W::VHandle>,
+    last_line: usize,
+}
+
+fn read<Reader: BufRead, W: Width>(reader: Reader, problem: &str) -> Result<Dimacs<W>, ReadError> {
+    let mut dimacs = Dimacs {
+        vertex_count: 0,
+        edges: Vec::new(),
+        source: None,
+        sink: None,
+        last_line: 0,
+    };
+    let mut edge_count = None;
+    for (index, text) in reader.lines().enumerate() {
+        let text = text?;
+        let line = index + 1;
+        dimacs.last_line = line;
+        let mut fields = text.split_whitespace();
+        let vertex = |fields: &mut std::str::SplitWhitespace, vertex_count: usize| -> Result<W::VHandle, ParseError> {
+            let id: usize = field(fields, line, "vertex")?;
+            if id == 0 || id > vertex_count {
+                return Err(ParseError::new(line, format!("vertex {} is out of range", id)));
+            }
+            Ok(W::handle(id - 1))
+        };
+        match fields.next() {
+            None | Some("c") => {}
+            Some("p") => {
+                if edge_count.is_some() {
+                    return Err(ParseError::new(line, "duplicate problem line").into());
+                }
+                let kind: String = field(&mut fields, line, "problem")?;
+                if kind != problem {
+                    return Err(ParseError::new(line, format!("expected a `{}` problem, found `{}`", problem, kind)).into());
+                }
+                dimacs.vertex_count = field(&mut fields, line, "vertex count")?;
+                check_vertex_count::<W>(dimacs.vertex_count, line)?;
+                let count: usize = field(&mut fields, line, "edge count")?;
+                check_edge_count(count, line)?;
+                dimacs.edges.reserve(count.min(RESERVE_LIMIT));
+                edge_count = Some(count);
+            }
+            Some(_) if edge_count.is_none() => return Err(ParseError::new(line, "expected the problem line").into()),
+            Some("a") => {
+                let from = vertex(&mut fields, dimacs.vertex_count)?;
+                let to = vertex(&mut fields, dimacs.vertex_count)?;
+                let weight = field(&mut fields, line, "weight")?;
+                dimacs.edges.push((from, to, weight));
+                check_edge_count(dimacs.edges.len(), line)?;
+            }
+            Some("n") if problem == "max" => {
+                let handle = vertex(&mut fields, dimacs.vertex_count)?;
+                match fields.next() {
+                    Some("s") => dimacs.source = Some(handle),
+                    Some("t") => dimacs.sink = Some(handle),
+                    _ => return Err(ParseError::new(line, "expected `s` or `t`").into()),
+                }
+            }
+            Some(other) => return Err(ParseError::new(line, format!("unknown line type `{}`", other)).into()),
+        }
+    }
+    match edge_count {
+        None => Err(ParseError::new(dimacs.last_line, "missing the problem line").into()),
+        Some(count) if count != dimacs.edges.len() => Err(ParseError::new(dimacs.last_line, format!("expected {} arcs, found {}", count, dimacs.edges.len())).into()),
+        Some(_) => Ok(dimacs),
+    }
+}
+
+fn build<W: Width>(dimacs: Dimacs<W>) -> IdGraph<W> {
+    WeightedGraph::from_edges_with_width(1..=dimacs.vertex_count as u64, dimacs.edges)
+        .expect("vertex and edge counts are checked per line")
+}
+
+/// Reads a shortest path problem, `p sp <vertices> <arcs>` followed by `a <from> <to> <weight>` lines.
+#[inline(always)]
+pub fn read_shortest_path<Reader: BufRead>(reader: Reader) -> Result<IdGraph, ReadError> {
+    read_shortest_path_with_width(reader)
+}
+
+/// [read_shortest_path] into a graph of the width `W`. Weights which don't fit into the width are rejected.
+pub fn read_shortest_path_with_width<Reader: BufRead, W: Width>(reader: Reader) -> Result<IdGraph<W>, ReadError> {
+    Ok(build(read(reader, "sp")?))
+}
+
+/// Reads a maximum flow problem, `p max <vertices> <arcs>`, the `n <vertex> s` and `n <vertex> t`
+/// descriptors and `a <from> <to> <capacity>` lines.
+#[inline(always)]
+pub fn read_max_flow<Reader: BufRead>(reader: Reader) -> Result<MaxFlowProblem, ReadError> {
+    read_max_flow_with_width(reader)
+}
+
+/// [read_max_flow] into a graph of the width `W`. Capacities which don't fit into the width are rejected.
+pub fn read_max_flow_with_width<Reader: BufRead, W: Width>(reader: Reader) -> Result<MaxFlowProblem<W>, ReadError> {
+    let dimacs = read(reader, "max")?;
+    let line = dimacs.last_line;
+    let source = dimacs.source.ok_or_else(|| ParseError::new(line, "missing the source descriptor"))?;
+    let sink = dimacs.sink.ok_or_else(|| ParseError::new(line, "missing the sink descriptor"))?;
+    Ok(MaxFlowProblem {
+        graph: build(dimacs),
+        source,
+        sink,
+    })
+}
+
+fn write<Edges: EdgeStore<W>, Writer: Write, W: Width>(edges: &Edges, vertices_len: usize, problem: &str, descriptors: &[(W::VHandle, &str)], writer: &mut Writer) -> io::Result<()> {
+    let arcs = (0..vertices_len).map(W::handle)
+        .map(|vertex| edges.edges_iter(vertex).filter(|edge| W::vh(**edge) != W::NONE).count())
+        .sum::<usize>();
+    writeln!(writer, "p {} {} {}", problem, vertices_len, arcs)?;
+    for (handle, kind) in descriptors {
+        writeln!(writer, "n {} {}", W::index(*handle) + 1, kind)?;
+    }
+    for from in (0..vertices_len).map(W::handle) {
+        for edge in edges.edges_iter(from).filter(|edge| W::vh(**edge) != W::NONE) {
+            writeln!(writer, "a {} {} {}", W::index(from) + 1, W::index(W::vh(*edge)) + 1, W::wgt(*edge))?;
+        }
+    }
+    Ok(())
+}
+
+pub fn write_shortest_path<Edges: EdgeStore<W>, Writer: Write, W: Width>(edges: &Edges, vertices_len: usize, writer: &mut Writer) -> io::Result<()> {
+    write(edges, vertices_len, "sp", &[], writer)
+}
+
+pub fn write_max_flow<Edges: EdgeStore<W>, Writer: Write, W: Width>(edges: &Edges, vertices_len: usize, source: W::VHandle, sink: W::VHandle, writer: &mut Writer) -> io::Result<()> {
+    write(edges, vertices_len, "max", &[(source, "s"), (sink, "t")], writer)
+}
\ No newline at end of file
@@ -0,0 +1,642 @@
+//! Graphviz DOT export and import.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::io::Write;
+use crate::edge_storage::EdgeStorage;
+use crate::formats::ParseError;
+use crate::handles::types::VHandle;
+use crate::handles::{vh, Width, NONE, W64};
+use crate::observer::NoObserver;
+use crate::observer::GraphObserver;
+use crate::traits::{EdgeWeight, StoreVertex, WeightStore};
+use crate::vertex_storage::VertexStorage;
+use crate::views::tree::{Tree, TREE_HEADER_ELEMENTS};
+use crate::weighted_graph::WeightedGraph;
+
+/// Attributes of a node or an edge. Values are quoted and escaped on write.
+#[derive(Default)]
+pub struct Attributes {
+    values: Vec<(String, String)>,
+}
+
+impl Attributes {
+    /// Sets the attribute, replacing a previous value.
+    pub fn set<Value: Display>(&mut self, key: &str, value: Value) {
+        let value = value.to_string();
+        match self.values.iter_mut().find(|(existing, _)| existing == key) {
+            Some(entry) => entry.1 = value,
+            None => self.values.push((key.to_string(), value)),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.iter().find(|(existing, _)| existing == key).map(|(_, value)| value.as_str())
+    }
+
+    fn write<Writer: Write>(&self, writer: &mut Writer) -> io::Result<()> {
+        if self.values.is_empty() {
+            return Ok(());
+        }
+        write!(writer, " [")?;
+        for (i, (key, value)) in self.values.iter().enumerate() {
+            if i > 0 {
+                write!(writer, ", ")?;
+            }
+            write!(writer, "{}=\"{}\"", key, escape(value))?;
+        }
+        write!(writer, "]")
+    }
+}
+
+pub(crate) fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+type VertexAttributes<'a, VertexType> = Box<dyn Fn(VHandle, &VertexType, &mut Attributes) + 'a>;
+type EdgeAttributes<'a> = Box<dyn Fn(VHandle, VHandle, usize, &mut Attributes) + 'a>;
+
+/// Writes a digraph in the DOT format. Nodes are named by their handle and labeled with the value.
+/// The attribute closures run last, so they can override the labels and the highlighting.
+pub struct Dot<'a, VertexType, VertexStorageType, EdgeStorageType>
+where
+    VertexStorageType: StoreVertex<VertexType=VertexType>,
+    EdgeStorageType: WeightStore,
+{
+    vertices: &'a VertexStorageType,
+    edges: &'a EdgeStorageType,
+    weights: bool,
+    header_edges: usize,
+    path: Vec<VHandle>,
+    flow: Option<Box<dyn Fn(usize) -> String + 'a>>,
+    vertex_attributes: Option<VertexAttributes<'a, VertexType>>,
+    edge_attributes: Option<EdgeAttributes<'a>>,
+}
+
+impl<'a, VertexType, VertexStorageType, EdgeStorageType> Dot<'a, VertexType, VertexStorageType, EdgeStorageType>
+where
+    VertexStorageType: StoreVertex<VertexType=VertexType>,
+    EdgeStorageType: WeightStore,
+    EdgeStorageType::WeightType: Display,
+    VertexType: Display,
+{
+    pub fn new(vertices: &'a VertexStorageType, edges: &'a EdgeStorageType) -> Self {
+        Dot {
+            vertices,
+            edges,
+            weights: false,
+            header_edges: 0,
+            path: Vec::new(),
+            flow: None,
+            vertex_attributes: None,
+            edge_attributes: None,
+        }
+    }
+
+    /// Renders only the parent -> child edges of the tree, without the root and parent links.
+    pub fn from_tree<Observer: GraphObserver<VertexType>>(tree: &'a Tree<VertexType, VertexStorageType, EdgeStorageType, Observer>) -> Self {
+        let mut dot = Self::new(&*tree.values, &*tree.nodes);
+        dot.header_edges = TREE_HEADER_ELEMENTS;
+        dot
+    }
+
+    /// Labels the edges with their weights.
+    pub fn weights(mut self, weights: bool) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Highlights the vertices and the edges along the path, e.g. the popped result of `dijkstra`.
+    pub fn highlight_path<Path: IntoIterator<Item=VHandle>>(mut self, path: Path) -> Self {
+        self.path = path.into_iter().collect();
+        self
+    }
+
+    /// Labels the edges as `flow/capacity`, where the flow is read from `flows` at the same edge index,
+    /// e.g. `DinicGraph::edge_storage`.
+    pub fn flow<Flows>(mut self, flows: &'a Flows) -> Self
+    where
+        Flows: WeightStore,
+        Flows::WeightType: Display,
+    {
+        self.flow = Some(Box::new(move |index| flows.weight(index).to_string()));
+        self
+    }
+
+    pub fn vertex_attributes<Func: Fn(VHandle, &VertexType, &mut Attributes) + 'a>(mut self, func: Func) -> Self {
+        self.vertex_attributes = Some(Box::new(func));
+        self
+    }
+
+    /// The closure gets the endpoints and the index of the edge in the edge storage.
+    pub fn edge_attributes<Func: Fn(VHandle, VHandle, usize, &mut Attributes) + 'a>(mut self, func: Func) -> Self {
+        self.edge_attributes = Some(Box::new(func));
+        self
+    }
+
+    #[inline(always)]
+    fn on_path(&self, from: VHandle, to: VHandle) -> bool {
+        self.path.windows(2).any(|step| step[0] == from && step[1] == to)
+    }
+
+    pub fn write<Writer: Write>(&self, writer: &mut Writer) -> io::Result<()> {
+        writeln!(writer, "digraph {{")?;
+        for (vertex, value) in self.vertices.iter().enumerate() {
+            let vertex = vertex as VHandle;
+            let mut attributes = Attributes::default();
+            attributes.set("label", value);
+            if self.path.contains(&vertex) {
+                attributes.set("color", "red");
+            }
+            if let Some(func) = &self.vertex_attributes {
+                func(vertex, value, &mut attributes);
+            }
+            write!(writer, "    {}", vertex)?;
+            attributes.write(writer)?;
+            writeln!(writer, ";")?;
+        }
+
+        for from in 0..self.vertices.len() as VHandle {
+            for index in self.edges.edge_indices(from).skip(self.header_edges) {
+                let to = vh(self.edges[index]);
+                if to == NONE {
+                    continue;
+                }
+                let mut attributes = Attributes::default();
+                match &self.flow {
+                    Some(flow) => attributes.set("label", format!("{}/{}", flow(index), self.edges.weight(index))),
+                    None if self.weights => attributes.set("label", self.edges.weight(index)),
+                    None => {}
+                }
+                if self.on_path(from, to) {
+                    attributes.set("color", "red");
+                    attributes.set("penwidth", 2);
+                }
+                if let Some(func) = &self.edge_attributes {
+                    func(from, to, index, &mut attributes);
+                }
+                write!(writer, "    {} -> {}", from, to)?;
+                attributes.write(writer)?;
+                writeln!(writer, ";")?;
+            }
+        }
+        writeln!(writer, "}}")
+    }
+}
+
+impl<'a, VertexType, VertexStorageType, EdgeStorageType> Display for Dot<'a, VertexType, VertexStorageType, EdgeStorageType>
+where
+    VertexStorageType: StoreVertex<VertexType=VertexType>,
+    EdgeStorageType: WeightStore,
+    EdgeStorageType::WeightType: Display,
+    VertexType: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut buffer = Vec::new();
+        self.write(&mut buffer).map_err(|_| fmt::Error)?;
+        f.write_str(&String::from_utf8_lossy(&buffer))
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Token {
+    Id(String),
+    /// Quoted or HTML string, never a keyword.
+    Quoted(String),
+    OpenBrace,
+    CloseBrace,
+    OpenBracket,
+    CloseBracket,
+    Equals,
+    Semicolon,
+    Comma,
+    Colon,
+    Plus,
+    DirectedEdge,
+    UndirectedEdge,
+}
+
+#[inline(always)]
+fn is_id_char(character: char) -> bool {
+    character.is_alphanumeric() || character == '_' || !character.is_ascii()
+}
+
+fn tokenize(input: &str) -> Result<Vec<(Token, usize)>, ParseError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens: Vec<(Token, usize)> = Vec::new();
+    let mut line = 1;
+    let mut line_start = true;
+    let mut i = 0;
+    while i < chars.len() {
+        let character = chars[i];
+        let next = chars.get(i + 1).copied();
+        let at = line;
+        if character == '\n' {
+            line += 1;
+            line_start = true;
+            i += 1;
+            continue;
+        }
+        if character.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let comment_line = (character == '#' && line_start) || (character == '/' && next == Some('/'));
+        line_start = false;
+        if comment_line {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            continue;
+        }
+
+        let token = match character {
+            '/' if next == Some('*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    if chars[i] == '\n' {
+                        line += 1;
+                    }
+                    i += 1;
+                }
+                if i == chars.len() {
+                    return Err(ParseError::new(at, "unterminated comment"));
+                }
+                i += 2;
+                continue;
+            }
+            '{' => Token::OpenBrace,
+            '}' => Token::CloseBrace,
+            '[' => Token::OpenBracket,
+            ']' => Token::CloseBracket,
+            '=' => Token::Equals,
+            ';' => Token::Semicolon,
+            ',' => Token::Comma,
+            ':' => Token::Colon,
+            '+' => Token::Plus,
+            '-' if next == Some('>') => {
+                i += 1;
+                Token::DirectedEdge
+            }
+            '-' if next == Some('-') => {
+                i += 1;
+                Token::UndirectedEdge
+            }
+            '"' => {
+                let mut value = String::new();
+                i += 1;
+                while chars.get(i) != Some(&'"') {
+                    match chars.get(i) {
+                        None => return Err(ParseError::new(at, "unterminated string")),
+                        Some('\\') if chars.get(i + 1) == Some(&'"') => {
+                            value.push('"');
+                            i += 1;
+                        }
+                        Some('\\') if chars.get(i + 1) == Some(&'\\') => {
+                            value.push('\\');
+                            i += 1;
+                        }
+                        Some('\\') if chars.get(i + 1) == Some(&'n') => {
+                            value.push('\n');
+                            i += 1;
+                        }
+                        Some('\\') if chars.get(i + 1) == Some(&'\n') => {
+                            line += 1;
+                            i += 1;
+                        }
+                        Some(other) => {
+                            if *other == '\n' {
+                                line += 1;
+                            }
+                            value.push(*other);
+                        }
+                    }
+                    i += 1;
+                }
+                Token::Quoted(value)
+            }
+            '<' => {
+                let mut value = String::new();
+                let mut depth = 1;
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err(ParseError::new(at, "unterminated HTML string")),
+                        Some('<') => depth += 1,
+                        Some('>') => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        Some('\n') => line += 1,
+                        _ => {}
+                    }
+                    value.push(chars[i]);
+                    i += 1;
+                }
+                Token::Quoted(value)
+            }
+            _ if is_id_char(character) || character == '-' || character == '.' => {
+                let start = i;
+                while i + 1 < chars.len() && (is_id_char(chars[i + 1]) || chars[i + 1] == '.') {
+                    i += 1;
+                }
+                Token::Id(chars[start..=i].iter().collect())
+            }
+            _ => return Err(ParseError::new(at, format!("unexpected character `{}`", character))),
+        };
+        i += 1;
+
+        // "a" + "b" concatenates the strings
+        if let Token::Quoted(value) = &token {
+            if let [.., (Token::Quoted(previous), _), (Token::Plus, _)] = tokens.as_mut_slice() {
+                previous.push_str(value);
+                tokens.pop();
+                continue;
+            }
+        }
+        tokens.push((token, at));
+    }
+    Ok(tokens)
+}
+
+/// Graph read from DOT. Vertex values are the node IDs, `names` maps them to the handles.
+/// Every edge of an undirected `graph` is stored in both directions, like in [UndirectedGraph](crate::undirected_graph::UndirectedGraph).
+pub struct DotGraph<W: Width = W64> {
+    pub graph: WeightedGraph<String, VertexStorage<String>, EdgeStorage<(), W>, NoObserver, W>,
+    pub names: HashMap<String, W::VHandle>,
+    pub directed: bool,
+}
+
+/// Reads DOT `graph` and `digraph` files. Node and graph attributes, ports and subgraph
+/// names are accepted and ignored, subgraphs used as edge endpoints connect all of their nodes.
+#[derive(Default)]
+pub struct DotParser<'a> {
+    weight_attribute: Option<&'a str>,
+}
+
+impl<'a> DotParser<'a> {
+    pub fn new() -> Self {
+        DotParser { weight_attribute: None }
+    }
+
+    /// Edge attribute parsed into the weight of the graph width, edges without it get the `edge [...]` default or 0.
+    pub fn weight_attribute(mut self, name: &'a str) -> Self {
+        self.weight_attribute = Some(name);
+        self
+    }
+
+    #[inline(always)]
+    pub fn parse(&self, input: &str) -> Result<DotGraph, ParseError> {
+        self.parse_with_width(input)
+    }
+
+    /// [parse](Self::parse) into a graph of the width `W`. Weights which don't fit into the width are rejected.
+    pub fn parse_with_width<W: Width>(&self, input: &str) -> Result<DotGraph<W>, ParseError> {
+        let mut parser = Parser::<W> {
+            tokens: tokenize(input)?,
+            position: 0,
+            last_line: input.lines().count().max(1),
+            directed: true,
+            weight_attribute: self.weight_attribute,
+            vertices: Vec::new(),
+            names: HashMap::new(),
+            edges: Vec::new(),
+        };
+        parser.graph()?;
+        let graph = WeightedGraph::from_edges_with_width(parser.vertices, parser.edges)
+            .map_err(|error| ParseError::new(parser.last_line, error.to_string()))?;
+        Ok(DotGraph {
+            graph,
+            names: parser.names,
+            directed: parser.directed,
+        })
+    }
+}
+
+struct Attribute {
+    key: String,
+    value: String,
+    line: usize,
+}
+
+struct Parser<'a, W: Width> {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+    last_line: usize,
+    directed: bool,
+    weight_attribute: Option<&'a str>,
+    vertices: Vec<String>,
+    names: HashMap<String, W::VHandle>,
+    edges: Vec<(W::VHandle, W::VHandle, W::Weight)>,
+}
+
+impl<'a, W: Width> Parser<'a, W> {
+    #[inline(always)]
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    #[inline(always)]
+    fn line(&self) -> usize {
+        self.tokens.get(self.position).map_or(self.last_line, |(_, line)| *line)
+    }
+
+    fn next(&mut self) -> Result<Token, ParseError> {
+        let (token, _) = self.tokens.get(self.position).cloned().ok_or_else(|| ParseError::new(self.last_line, "unexpected end of input"))?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: Token, description: &str) -> Result<(), ParseError> {
+        let line = self.line();
+        match self.next()? {
+            token if token == expected => Ok(()),
+            _ => Err(ParseError::new(line, format!("expected {}", description))),
+        }
+    }
+
+    #[inline(always)]
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Id(id)) if id.eq_ignore_ascii_case(keyword))
+    }
+
+    fn id(&mut self) -> Result<String, ParseError> {
+        let line = self.line();
+        match self.next()? {
+            Token::Id(id) | Token::Quoted(id) => Ok(id),
+            _ => Err(ParseError::new(line, "expected an ID")),
+        }
+    }
+
+    fn vertex(&mut self, name: String) -> W::VHandle {
+        if let Some(handle) = self.names.get(&name) {
+            return *handle;
+        }
+        let handle = W::handle(self.vertices.len());
+        self.vertices.push(name.clone());
+        self.names.insert(name, handle);
+        handle
+    }
+
+    fn graph(&mut self) -> Result<(), ParseError> {
+        if self.is_keyword("strict") {
+            self.position += 1;
+        }
+        if self.is_keyword("digraph") {
+            self.directed = true;
+        } else if self.is_keyword("graph") {
+            self.directed = false;
+        } else {
+            return Err(ParseError::new(self.line(), "expected `graph` or `digraph`"));
+        }
+        self.position += 1;
+        if self.peek() != Some(&Token::OpenBrace) {
+            self.id()?;
+        }
+        self.expect(Token::OpenBrace, "`{`")?;
+        self.statements(W::Weight::zero())?;
+        if self.peek().is_some() {
+            return Err(ParseError::new(self.line(), "unexpected input after the graph"));
+        }
+        Ok(())
+    }
+
+    /// Parses statements up to and including the closing brace, returns the mentioned vertices.
+    fn statements(&mut self, mut default_weight: W::Weight) -> Result<Vec<W::VHandle>, ParseError> {
+        let mut mentioned = Vec::new();
+        loop {
+            match self.peek() {
+                None => return Err(ParseError::new(self.last_line, "missing `}`")),
+                Some(Token::CloseBrace) => {
+                    self.position += 1;
+                    return Ok(mentioned);
+                }
+                Some(Token::Semicolon) => self.position += 1,
+                _ => self.statement(&mut default_weight, &mut mentioned)?,
+            }
+        }
+    }
+
+    fn statement(&mut self, default_weight: &mut W::Weight, mentioned: &mut Vec<W::VHandle>) -> Result<(), ParseError> {
+        if self.is_keyword("graph") || self.is_keyword("node") {
+            self.position += 1;
+            self.attributes()?;
+            return Ok(());
+        }
+        if self.is_keyword("edge") {
+            self.position += 1;
+            let attributes = self.attributes()?;
+            if let Some(weight) = self.weight(&attributes)? {
+                *default_weight = weight;
+            }
+            return Ok(());
+        }
+        // ID = ID sets a graph attribute
+        if matches!(self.tokens.get(self.position + 1), Some((Token::Equals, _))) {
+            self.id()?;
+            self.position += 1;
+            self.id()?;
+            return Ok(());
+        }
+
+        let mut groups = vec![self.endpoint(*default_weight)?];
+        while let Some(Token::DirectedEdge | Token::UndirectedEdge) = self.peek() {
+            let directed = self.peek() == Some(&Token::DirectedEdge);
+            if directed != self.directed {
+                let message = if directed { "`->` in an undirected graph" } else { "`--` in a directed graph" };
+                return Err(ParseError::new(self.line(), message));
+            }
+            self.position += 1;
+            groups.push(self.endpoint(*default_weight)?);
+        }
+        let attributes = self.attributes()?;
+        if groups.len() == 1 {
+            mentioned.extend(groups.pop().unwrap());
+            return Ok(());
+        }
+        let weight = self.weight(&attributes)?.unwrap_or(*default_weight);
+        for pair in groups.windows(2) {
+            for from in pair[0].iter() {
+                for to in pair[1].iter() {
+                    self.edges.push((*from, *to, weight));
+                    if !self.directed && from != to {
+                        self.edges.push((*to, *from, weight));
+                    }
+                }
+            }
+        }
+        for group in groups {
+            mentioned.extend(group);
+        }
+        Ok(())
+    }
+
+    /// A node with an optional port or a subgraph.
+    fn endpoint(&mut self, default_weight: W::Weight) -> Result<Vec<W::VHandle>, ParseError> {
+        if self.is_keyword("subgraph") || self.peek() == Some(&Token::OpenBrace) {
+            if self.is_keyword("subgraph") {
+                self.position += 1;
+                if self.peek() != Some(&Token::OpenBrace) {
+                    self.id()?;
+                }
+            }
+            self.expect(Token::OpenBrace, "`{`")?;
+            return self.statements(default_weight);
+        }
+        let name = self.id()?;
+        while self.peek() == Some(&Token::Colon) {
+            self.position += 1;
+            self.id()?;
+        }
+        Ok(vec![self.vertex(name)])
+    }
+
+    fn attributes(&mut self) -> Result<Vec<Attribute>, ParseError> {
+        let mut attributes = Vec::new();
+        while self.peek() == Some(&Token::OpenBracket) {
+            self.position += 1;
+            loop {
+                match self.peek() {
+                    Some(Token::CloseBracket) => {
+                        self.position += 1;
+                        break;
+                    }
+                    Some(Token::Semicolon | Token::Comma) => self.position += 1,
+                    _ => {
+                        let key = self.id()?;
+                        self.expect(Token::Equals, "`=`")?;
+                        let line = self.line();
+                        let value = self.id()?;
+                        attributes.push(Attribute { key, value, line });
+                    }
+                }
+            }
+        }
+        Ok(attributes)
+    }
+
+    fn weight(&self, attributes: &[Attribute]) -> Result<Option<W::Weight>, ParseError> {
+        let Some(name) = self.weight_attribute else {
+            return Ok(None);
+        };
+        match attributes.iter().rev().find(|attribute| attribute.key == name) {
+            Some(attribute) => attribute.value.trim().parse::<W::Weight>()
+                .map(Some)
+                .map_err(|_| ParseError::new(attribute.line, format!("`{}` is not a valid weight", attribute.value))),
+            None => Ok(None),
+        }
+    }
+}
\ No newline at end of file
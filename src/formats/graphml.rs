@@ -0,0 +1,777 @@
+//! GraphML export and import, compatible with Gephi, yEd and NetworkX.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::io::Write;
+use crate::edge_storage::EdgeStorage;
+use crate::formats::{check_edge_count, check_vertex_count, ParseError};
+use crate::handles::types::VHandle;
+use crate::handles::{vh, Width, NONE, W64};
+use crate::observer::NoObserver;
+use crate::traits::{EdgeWeight, StoreVertex, WeightStore};
+use crate::vertex_storage::VertexStorage;
+use crate::weighted_graph::WeightedGraph;
+
+/// `attr.type` of a `<key>`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AttributeType {
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    String,
+}
+
+impl AttributeType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AttributeType::Boolean => "boolean",
+            AttributeType::Int => "int",
+            AttributeType::Long => "long",
+            AttributeType::Float => "float",
+            AttributeType::Double => "double",
+            AttributeType::String => "string",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "boolean" => Some(AttributeType::Boolean),
+            "int" => Some(AttributeType::Int),
+            "long" => Some(AttributeType::Long),
+            "float" => Some(AttributeType::Float),
+            "double" => Some(AttributeType::Double),
+            "string" => Some(AttributeType::String),
+            _ => None,
+        }
+    }
+
+    fn parse(&self, text: &str) -> Option<Value> {
+        let text = text.trim();
+        Some(match self {
+            AttributeType::Boolean => match text.to_ascii_lowercase().as_str() {
+                "true" | "1" => Value::Boolean(true),
+                "false" | "0" => Value::Boolean(false),
+                _ => return None,
+            },
+            AttributeType::Int => Value::Int(text.parse().ok()?),
+            AttributeType::Long => Value::Long(text.parse().ok()?),
+            AttributeType::Float => Value::Float(text.parse().ok()?),
+            AttributeType::Double => Value::Double(text.parse().ok()?),
+            AttributeType::String => Value::String(text.to_string()),
+        })
+    }
+}
+
+/// Typed value of a `<data>` element.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Boolean(bool),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    String(String),
+}
+
+impl Value {
+    pub fn attribute_type(&self) -> AttributeType {
+        match self {
+            Value::Boolean(_) => AttributeType::Boolean,
+            Value::Int(_) => AttributeType::Int,
+            Value::Long(_) => AttributeType::Long,
+            Value::Float(_) => AttributeType::Float,
+            Value::Double(_) => AttributeType::Double,
+            Value::String(_) => AttributeType::String,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Boolean(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// `int` and `long` values.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Int(value) => Some(*value as i64),
+            Value::Long(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Any numeric value.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Int(value) => Some(*value as f64),
+            Value::Long(value) => Some(*value as f64),
+            Value::Float(value) => Some(*value as f64),
+            Value::Double(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Integral numbers and numeric strings which fit into the weight of the width `W`.
+    fn to_weight<W: Width>(&self) -> Option<W::Weight> {
+        match self {
+            Value::Int(_) | Value::Long(_) => W::Weight::try_from(self.as_i64()?).ok(),
+            Value::Float(_) | Value::Double(_) => {
+                let value = self.as_f64()?;
+                if value.fract() != 0.0 || value < i64::MIN as f64 || value >= i64::MAX as f64 {
+                    return None;
+                }
+                W::Weight::try_from(value as i64).ok()
+            }
+            Value::String(value) => value.trim().parse().ok(),
+            Value::Boolean(_) => None,
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Boolean(value) => write!(f, "{}", value),
+            Value::Int(value) => write!(f, "{}", value),
+            Value::Long(value) => write!(f, "{}", value),
+            Value::Float(value) => write!(f, "{}", value),
+            Value::Double(value) => write!(f, "{}", value),
+            Value::String(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// Weight types which can be exported, selects the `attr.type` of the weight key.
+pub trait KeyType: Display {
+    const KEY_TYPE: AttributeType;
+}
+
+macro_rules! key_type_impl {
+    ($key_type:expr; $($type:ty),*) => {
+        $(impl KeyType for $type {
+            const KEY_TYPE: AttributeType = $key_type;
+        })*
+    };
+}
+
+key_type_impl!(AttributeType::Long; i8, i16, i32, i64, u8, u16, u32, u64, usize);
+key_type_impl!(AttributeType::Double; f32, f64);
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+type VertexKey<'a, VertexType> = (String, AttributeType, Box<dyn Fn(VHandle, &VertexType) -> Option<Value> + 'a>);
+
+/// Writes GraphML. Nodes are named `n<handle>`, vertex values are written through the declared keys.
+pub struct GraphMl<'a, VertexType, VertexStorageType, EdgeStorageType>
+where
+    VertexStorageType: StoreVertex<VertexType=VertexType>,
+    EdgeStorageType: WeightStore,
+{
+    vertices: &'a VertexStorageType,
+    edges: &'a EdgeStorageType,
+    weights: bool,
+    directed: bool,
+    vertex_keys: Vec<VertexKey<'a, VertexType>>,
+}
+
+impl<'a, VertexType, VertexStorageType, EdgeStorageType> GraphMl<'a, VertexType, VertexStorageType, EdgeStorageType>
+where
+    VertexStorageType: StoreVertex<VertexType=VertexType>,
+    EdgeStorageType: WeightStore,
+    EdgeStorageType::WeightType: KeyType,
+{
+    pub fn new(vertices: &'a VertexStorageType, edges: &'a EdgeStorageType) -> Self {
+        GraphMl {
+            vertices,
+            edges,
+            weights: false,
+            directed: true,
+            vertex_keys: Vec::new(),
+        }
+    }
+
+    /// Writes the weights as the `weight` edge key.
+    pub fn weights(mut self, weights: bool) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Declares an undirected graph. The edges are expected in both directions, like in
+    /// [UndirectedGraph](crate::undirected_graph::UndirectedGraph), and only `a -> b` with `a <= b` is written.
+    pub fn directed(mut self, directed: bool) -> Self {
+        self.directed = directed;
+        self
+    }
+
+    /// Declares a node key. The closure converts the vertex value, `None` skips the `<data>` element.
+    pub fn vertex_key<Func>(mut self, name: &str, key_type: AttributeType, func: Func) -> Self
+    where
+        Func: Fn(VHandle, &VertexType) -> Option<Value> + 'a,
+    {
+        self.vertex_keys.push((name.to_string(), key_type, Box::new(func)));
+        self
+    }
+
+    pub fn write<Writer: Write>(&self, writer: &mut Writer) -> io::Result<()> {
+        writeln!(writer, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(writer, "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">")?;
+        for (i, (name, key_type, _)) in self.vertex_keys.iter().enumerate() {
+            writeln!(writer, "  <key id=\"d{}\" for=\"node\" attr.name=\"{}\" attr.type=\"{}\"/>", i, escape(name), key_type.name())?;
+        }
+        let weight_key = self.vertex_keys.len();
+        if self.weights {
+            writeln!(writer, "  <key id=\"d{}\" for=\"edge\" attr.name=\"weight\" attr.type=\"{}\"/>",
+                     weight_key, EdgeStorageType::WeightType::KEY_TYPE.name())?;
+        }
+        writeln!(writer, "  <graph edgedefault=\"{}\">", if self.directed { "directed" } else { "undirected" })?;
+
+        for (vertex, value) in self.vertices.iter().enumerate() {
+            let data: Vec<_> = self.vertex_keys.iter().enumerate()
+                .filter_map(|(i, (_, _, func))| func(vertex as VHandle, value).map(|value| (i, value)))
+                .collect();
+            if data.is_empty() {
+                writeln!(writer, "    <node id=\"n{}\"/>", vertex)?;
+                continue;
+            }
+            writeln!(writer, "    <node id=\"n{}\">", vertex)?;
+            for (key, value) in data {
+                writeln!(writer, "      <data key=\"d{}\">{}</data>", key, escape(&value.to_string()))?;
+            }
+            writeln!(writer, "    </node>")?;
+        }
+
+        for from in 0..self.vertices.len() as VHandle {
+            for index in self.edges.edge_indices(from) {
+                let to = vh(self.edges[index]);
+                if to == NONE || (!self.directed && to < from) {
+                    continue;
+                }
+                if !self.weights {
+                    writeln!(writer, "    <edge source=\"n{}\" target=\"n{}\"/>", from, to)?;
+                    continue;
+                }
+                writeln!(writer, "    <edge source=\"n{}\" target=\"n{}\">", from, to)?;
+                writeln!(writer, "      <data key=\"d{}\">{}</data>", weight_key, self.edges.weight(index))?;
+                writeln!(writer, "    </edge>")?;
+            }
+        }
+        writeln!(writer, "  </graph>")?;
+        writeln!(writer, "</graphml>")
+    }
+}
+
+impl<'a, VertexType, VertexStorageType, EdgeStorageType> Display for GraphMl<'a, VertexType, VertexStorageType, EdgeStorageType>
+where
+    VertexStorageType: StoreVertex<VertexType=VertexType>,
+    EdgeStorageType: WeightStore,
+    EdgeStorageType::WeightType: KeyType,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut buffer = Vec::new();
+        self.write(&mut buffer).map_err(|_| fmt::Error)?;
+        f.write_str(&String::from_utf8_lossy(&buffer))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Event {
+    Start {
+        name: String,
+        attributes: Vec<(String, String)>,
+        empty: bool,
+    },
+    End(String),
+    Text(String),
+}
+
+/// Just enough XML for GraphML: elements, attributes, text, CDATA and the predefined and numeric entities.
+/// Declarations, comments and processing instructions are skipped.
+struct XmlReader {
+    chars: Vec<char>,
+    position: usize,
+    line: usize,
+    open: Vec<String>,
+}
+
+impl XmlReader {
+    fn new(input: &str) -> Self {
+        XmlReader {
+            chars: input.chars().collect(),
+            position: 0,
+            line: 1,
+            open: Vec::new(),
+        }
+    }
+
+    #[inline(always)]
+    fn starts_with(&self, prefix: &str) -> bool {
+        prefix.chars().enumerate().all(|(i, character)| self.chars.get(self.position + i) == Some(&character))
+    }
+
+    fn advance(&mut self, count: usize) {
+        for _ in 0..count {
+            if self.chars.get(self.position) == Some(&'\n') {
+                self.line += 1;
+            }
+            self.position += 1;
+        }
+    }
+
+    /// Consumes everything up to and including `end`, returns the skipped text.
+    fn take_until(&mut self, end: &str, what: &str) -> Result<String, ParseError> {
+        let line = self.line;
+        let start = self.position;
+        while !self.starts_with(end) {
+            if self.position >= self.chars.len() {
+                return Err(ParseError::new(line, format!("unterminated {}", what)));
+            }
+            self.advance(1);
+        }
+        let text = self.chars[start..self.position].iter().collect();
+        self.advance(end.chars().count());
+        Ok(text)
+    }
+
+    fn unescape(&self, text: &str, line: usize) -> Result<String, ParseError> {
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find('&') {
+            result.push_str(&rest[..start]);
+            let end = rest[start..].find(';').ok_or_else(|| ParseError::new(line, "unterminated entity"))? + start;
+            let entity = &rest[start + 1..end];
+            let character = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ if entity.starts_with("#x") => u32::from_str_radix(&entity[2..], 16).ok().and_then(char::from_u32),
+                _ if entity.starts_with('#') => entity[1..].parse().ok().and_then(char::from_u32),
+                _ => None,
+            };
+            result.push(character.ok_or_else(|| ParseError::new(line, format!("unknown entity `&{};`", entity)))?);
+            rest = &rest[end + 1..];
+        }
+        result.push_str(rest);
+        Ok(result)
+    }
+
+    fn name(&mut self) -> Result<String, ParseError> {
+        let start = self.position;
+        while let Some(character) = self.chars.get(self.position) {
+            if character.is_whitespace() || matches!(character, '/' | '>' | '=') {
+                break;
+            }
+            self.advance(1);
+        }
+        if start == self.position {
+            return Err(ParseError::new(self.line, "expected a name"));
+        }
+        Ok(self.chars[start..self.position].iter().collect())
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.get(self.position).is_some_and(|character| character.is_whitespace()) {
+            self.advance(1);
+        }
+    }
+
+    fn next(&mut self) -> Result<Option<(Event, usize)>, ParseError> {
+        loop {
+            let line = self.line;
+            if self.position >= self.chars.len() {
+                return match self.open.pop() {
+                    Some(name) => Err(ParseError::new(line, format!("missing `</{}>`", name))),
+                    None => Ok(None),
+                };
+            }
+            if self.starts_with("<?") {
+                self.take_until("?>", "processing instruction")?;
+            } else if self.starts_with("<!--") {
+                self.take_until("-->", "comment")?;
+            } else if self.starts_with("<![CDATA[") {
+                self.advance(9);
+                let text = self.take_until("]]>", "CDATA section")?;
+                return Ok(Some((Event::Text(text), line)));
+            } else if self.starts_with("<!") {
+                self.take_until(">", "declaration")?;
+            } else if self.starts_with("</") {
+                self.advance(2);
+                let name = self.name()?;
+                self.skip_whitespace();
+                if !self.starts_with(">") {
+                    return Err(ParseError::new(self.line, "expected `>`"));
+                }
+                self.advance(1);
+                match self.open.pop() {
+                    Some(open) if open == name => return Ok(Some((Event::End(name), line))),
+                    Some(open) => return Err(ParseError::new(line, format!("expected `</{}>`, found `</{}>`", open, name))),
+                    None => return Err(ParseError::new(line, format!("unexpected `</{}>`", name))),
+                }
+            } else if self.starts_with("<") {
+                self.advance(1);
+                let name = self.name()?;
+                let mut attributes = Vec::new();
+                loop {
+                    self.skip_whitespace();
+                    if self.starts_with("/>") {
+                        self.advance(2);
+                        return Ok(Some((Event::Start { name, attributes, empty: true }, line)));
+                    }
+                    if self.starts_with(">") {
+                        self.advance(1);
+                        self.open.push(name.clone());
+                        return Ok(Some((Event::Start { name, attributes, empty: false }, line)));
+                    }
+                    let key = self.name()?;
+                    self.skip_whitespace();
+                    if !self.starts_with("=") {
+                        return Err(ParseError::new(self.line, format!("expected `=` after `{}`", key)));
+                    }
+                    self.advance(1);
+                    self.skip_whitespace();
+                    let value_line = self.line;
+                    let quote = match self.chars.get(self.position) {
+                        Some(quote @ ('"' | '\'')) => quote.to_string(),
+                        _ => return Err(ParseError::new(self.line, "expected a quoted attribute value")),
+                    };
+                    self.advance(1);
+                    let value = self.take_until(&quote, "attribute value")?;
+                    attributes.push((key, self.unescape(&value, value_line)?));
+                }
+            } else {
+                let start = self.position;
+                while self.position < self.chars.len() && !self.starts_with("<") {
+                    self.advance(1);
+                }
+                let text: String = self.chars[start..self.position].iter().collect();
+                if self.open.is_empty() {
+                    if text.trim().is_empty() {
+                        continue;
+                    }
+                    return Err(ParseError::new(line, "text outside of the root element"));
+                }
+                return Ok(Some((Event::Text(self.unescape(&text, line)?), line)));
+            }
+        }
+    }
+}
+
+/// Data of a node or an edge, addressed by `attr.name`, or the key id for keys without a name.
+/// Contains the `<default>` of every key which was not set.
+#[derive(Debug, Default, Clone)]
+pub struct Data {
+    values: Vec<(String, Value)>,
+}
+
+impl Data {
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.values.iter().find(|(key, _)| key == name).map(|(_, value)| value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=(&str, &Value)> {
+        self.values.iter().map(|(key, value)| (key.as_str(), value))
+    }
+}
+
+/// Graph read from GraphML. `names` maps the node ids to the handles.
+/// Every undirected edge is stored in both directions, like in [UndirectedGraph](crate::undirected_graph::UndirectedGraph).
+pub struct GraphMlGraph<VertexType, W: Width = W64> {
+    pub graph: WeightedGraph<VertexType, VertexStorage<VertexType>, EdgeStorage<(), W>, NoObserver, W>,
+    pub names: HashMap<String, W::VHandle>,
+    /// The `edgedefault` of the graph, single edges can override it.
+    pub directed: bool,
+}
+
+type VertexConversion<'a, VertexType> = Box<dyn Fn(&str, &Data) -> VertexType + 'a>;
+
+/// Reads the first `<graph>` of a GraphML document. Hyperedges and nested graphs are rejected,
+/// ports and elements of other namespaces are ignored.
+pub struct GraphMlParser<'a, VertexType> {
+    vertex: VertexConversion<'a, VertexType>,
+    weight_attribute: Option<&'a str>,
+}
+
+struct Key {
+    name: String,
+    domain: String,
+    key_type: AttributeType,
+    default: Option<Value>,
+}
+
+struct PendingEdge<W: Width> {
+    source: String,
+    target: String,
+    directed: Option<bool>,
+    weight: W::Weight,
+    line: usize,
+}
+
+/// Parsing state, `skip` is the depth of an ignored `<graph>` whose events are dropped.
+struct State<VertexType, W: Width> {
+    keys: HashMap<String, Key>,
+    key: Option<(String, Key)>,
+    text: Option<String>,
+    data: Vec<(String, String, usize)>,
+    node: Option<(String, usize)>,
+    edge: Option<PendingEdge<W>>,
+    graph_open: bool,
+    graph_done: bool,
+    skip: Option<usize>,
+    directed: bool,
+    vertices: Vec<VertexType>,
+    names: HashMap<String, W::VHandle>,
+    edges: Vec<PendingEdge<W>>,
+}
+
+impl<'a, VertexType> GraphMlParser<'a, VertexType> {
+    /// The closure converts the node id and its data into the vertex value.
+    pub fn new<Func: Fn(&str, &Data) -> VertexType + 'a>(vertex: Func) -> Self {
+        GraphMlParser {
+            vertex: Box::new(vertex),
+            weight_attribute: None,
+        }
+    }
+
+    /// Edge key, by `attr.name`, parsed into the weight of the graph width. Edges without it get the key default or 0.
+    pub fn weight_attribute(mut self, name: &'a str) -> Self {
+        self.weight_attribute = Some(name);
+        self
+    }
+
+    #[inline(always)]
+    pub fn parse(&self, input: &str) -> Result<GraphMlGraph<VertexType>, ParseError> {
+        self.parse_with_width(input)
+    }
+
+    /// [parse](Self::parse) into a graph of the width `W`. Weights which don't fit into the width are rejected.
+    pub fn parse_with_width<W: Width>(&self, input: &str) -> Result<GraphMlGraph<VertexType, W>, ParseError> {
+        let mut reader = XmlReader::new(input);
+        let mut state = State::<VertexType, W> {
+            keys: HashMap::new(),
+            key: None,
+            text: None,
+            data: Vec::new(),
+            node: None,
+            edge: None,
+            graph_open: false,
+            graph_done: false,
+            skip: None,
+            directed: true,
+            vertices: Vec::new(),
+            names: HashMap::new(),
+            edges: Vec::new(),
+        };
+        let mut root_seen = false;
+
+        while let Some((event, line)) = reader.next()? {
+            let depth = reader.open.len();
+            match event {
+                Event::Text(content) => {
+                    if let (None, Some(text)) = (state.skip, &mut state.text) {
+                        text.push_str(&content);
+                    }
+                }
+                Event::Start { name, attributes, empty } => {
+                    if !root_seen && name != "graphml" {
+                        return Err(ParseError::new(line, "expected `<graphml>`"));
+                    }
+                    root_seen = true;
+                    if state.skip.is_some() {
+                        continue;
+                    }
+                    if name == "graph" && state.graph_done {
+                        if !empty {
+                            state.skip = Some(depth);
+                        }
+                        continue;
+                    }
+                    self.start(&mut state, &name, &attributes, line)?;
+                    if empty {
+                        self.end(&mut state, &name, line)?;
+                    }
+                }
+                Event::End(name) => {
+                    match state.skip {
+                        Some(skip) if depth + 1 == skip => state.skip = None,
+                        Some(_) => {}
+                        None => self.end(&mut state, &name, line)?,
+                    }
+                }
+            }
+        }
+        if !state.graph_done {
+            return Err(ParseError::new(reader.line, "missing `<graph>`"));
+        }
+
+        let mut edges = Vec::with_capacity(state.edges.len());
+        for edge in state.edges.iter() {
+            let handle = |id: &str| state.names.get(id).copied()
+                .ok_or_else(|| ParseError::new(edge.line, format!("edge to the undeclared node `{}`", id)));
+            let (source, target) = (handle(&edge.source)?, handle(&edge.target)?);
+            edges.push((source, target, edge.weight));
+            if !edge.directed.unwrap_or(state.directed) && source != target {
+                edges.push((target, source, edge.weight));
+            }
+            check_edge_count(edges.len(), edge.line)?;
+        }
+        let graph = WeightedGraph::from_edges_with_width(state.vertices, edges)
+            .expect("vertex and edge counts are checked per node and edge");
+        Ok(GraphMlGraph {
+            graph,
+            names: state.names,
+            directed: state.directed,
+        })
+    }
+
+    fn start<W: Width>(&self, state: &mut State<VertexType, W>, name: &str, attributes: &[(String, String)], line: usize) -> Result<(), ParseError> {
+        let attribute = |key: &str| attributes.iter().find(|(name, _)| name == key).map(|(_, value)| value.clone());
+        let required = |key: &str| attribute(key).ok_or_else(|| ParseError::new(line, format!("`<{}>` is missing `{}`", name, key)));
+        match name {
+            "key" if !state.graph_open => {
+                let id = required("id")?;
+                let type_name = attribute("attr.type").unwrap_or_else(|| "string".to_string());
+                let key_type = AttributeType::from_name(&type_name)
+                    .ok_or_else(|| ParseError::new(line, format!("unknown attribute type `{}`", type_name)))?;
+                let key = Key {
+                    name: attribute("attr.name").unwrap_or_else(|| id.clone()),
+                    domain: attribute("for").unwrap_or_else(|| "all".to_string()),
+                    key_type,
+                    default: None,
+                };
+                state.key = Some((id, key));
+            }
+            "default" if state.key.is_some() => state.text = Some(String::new()),
+            "graph" => {
+                if state.graph_open {
+                    return Err(ParseError::new(line, "nested graphs are not supported"));
+                }
+                state.graph_open = true;
+                state.directed = match attribute("edgedefault").as_deref() {
+                    None | Some("directed") => true,
+                    Some("undirected") => false,
+                    Some(other) => return Err(ParseError::new(line, format!("unknown edgedefault `{}`", other))),
+                };
+            }
+            "node" if state.graph_open => {
+                state.node = Some((required("id")?, line));
+                state.data.clear();
+            }
+            "edge" if state.graph_open => {
+                let directed = match attribute("directed").as_deref() {
+                    None => None,
+                    Some("true") => Some(true),
+                    Some("false") => Some(false),
+                    Some(other) => return Err(ParseError::new(line, format!("`{}` is not a valid boolean", other))),
+                };
+                state.edge = Some(PendingEdge {
+                    source: required("source")?,
+                    target: required("target")?,
+                    directed,
+                    weight: W::Weight::zero(),
+                    line,
+                });
+                state.data.clear();
+            }
+            "hyperedge" => return Err(ParseError::new(line, "hyperedges are not supported")),
+            "data" if state.node.is_some() || state.edge.is_some() => {
+                state.data.push((required("key")?, String::new(), line));
+                state.text = Some(String::new());
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn end<W: Width>(&self, state: &mut State<VertexType, W>, name: &str, line: usize) -> Result<(), ParseError> {
+        match name {
+            "key" => {
+                if let Some((id, key)) = state.key.take() {
+                    state.keys.insert(id, key);
+                }
+            }
+            "default" => {
+                if let (Some((_, key)), Some(text)) = (&mut state.key, state.text.take()) {
+                    key.default = Some(key.key_type.parse(&text)
+                        .ok_or_else(|| ParseError::new(line, format!("`{}` is not a valid {}", text.trim(), key.key_type.name())))?);
+                }
+            }
+            "data" => {
+                if let (Some(data), Some(text)) = (state.data.last_mut(), state.text.take()) {
+                    data.1 = text;
+                }
+            }
+            "node" => {
+                if let Some((id, node_line)) = state.node.take() {
+                    let data = self.data(state, "node")?;
+                    if state.names.contains_key(&id) {
+                        return Err(ParseError::new(node_line, format!("duplicate node id `{}`", id)));
+                    }
+                    check_vertex_count::<W>(state.vertices.len(), node_line)?;
+                    state.vertices.push((self.vertex)(&id, &data));
+                    state.names.insert(id, W::handle(state.vertices.len() - 1));
+                }
+            }
+            "edge" => {
+                if let Some(mut edge) = state.edge.take() {
+                    let data = self.data(state, "edge")?;
+                    if let Some(value) = self.weight_attribute.and_then(|name| data.get(name)) {
+                        edge.weight = value.to_weight::<W>()
+                            .ok_or_else(|| ParseError::new(edge.line, format!("`{}` is not a valid weight", value)))?;
+                    }
+                    state.edges.push(edge);
+                }
+            }
+            "graph" if state.graph_open => {
+                state.graph_open = false;
+                state.graph_done = true;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn data<W: Width>(&self, state: &mut State<VertexType, W>, domain: &str) -> Result<Data, ParseError> {
+        let mut data = Data::default();
+        for (id, text, line) in state.data.drain(..) {
+            let key = state.keys.get(&id).ok_or_else(|| ParseError::new(line, format!("undeclared key `{}`", id)))?;
+            let value = key.key_type.parse(&text)
+                .ok_or_else(|| ParseError::new(line, format!("`{}` is not a valid {}", text.trim(), key.key_type.name())))?;
+            data.values.retain(|(name, _)| *name != key.name);
+            data.values.push((key.name.clone(), value));
+        }
+        for key in state.keys.values() {
+            if (key.domain == domain || key.domain == "all") && data.get(&key.name).is_none() {
+                if let Some(default) = &key.default {
+                    data.values.push((key.name.clone(), default.clone()));
+                }
+            }
+        }
+        Ok(data)
+    }
+}
\ No newline at end of file
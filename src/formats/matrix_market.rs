@@ -0,0 +1,145 @@
+//! Matrix Market coordinate files (`.mtx`) as adjacency matrices. Entry `(i, j)` is the edge from
+//! vertex `i - 1` to `j - 1`, vertex `i` gets the value `i`.
+
+use std::io;
+use std::io::{BufRead, Write};
+use crate::formats::{check_edge_count, check_vertex_count, field, IdGraph, ParseError, ReadError, RESERVE_LIMIT};
+use crate::handles::Width;
+use crate::traits::{EdgeStore, EdgeWeight};
+use crate::weighted_graph::WeightedGraph;
+
+#[derive(Copy, Clone, PartialEq)]
+enum Symmetry {
+    General,
+    Symmetric,
+    SkewSymmetric,
+}
+
+/// Reads `integer`, `real` and `pattern` matrices with `general`, `symmetric` or `skew-symmetric` symmetry.
+/// Real values must be integral, pattern entries get the weight 0. The lower triangle of symmetric
+/// matrices is stored in both directions.
+#[inline(always)]
+pub fn read<Reader: BufRead>(reader: Reader) -> Result<IdGraph, ReadError> {
+    read_with_width(reader)
+}
+
+/// [read] into a graph of the width `W`. Values which don't fit into the weight of the width are rejected.
+pub fn read_with_width<Reader: BufRead, W: Width>(reader: Reader) -> Result<IdGraph<W>, ReadError> {
+    let mut lines = reader.lines().enumerate();
+    let header = match lines.next() {
+        Some((_, text)) => text?,
+        None => return Err(ParseError::new(1, "missing the header").into()),
+    };
+    let header = header.to_ascii_lowercase();
+    let mut fields = header.split_whitespace();
+    if fields.next() != Some("%%matrixmarket") || fields.next() != Some("matrix") {
+        return Err(ParseError::new(1, "expected `%%MatrixMarket matrix`").into());
+    }
+    if fields.next() != Some("coordinate") {
+        return Err(ParseError::new(1, "only coordinate matrices are supported").into());
+    }
+    let pattern = match fields.next() {
+        Some("integer") | Some("real") => false,
+        Some("pattern") => true,
+        Some(other) => return Err(ParseError::new(1, format!("unsupported field `{}`", other)).into()),
+        None => return Err(ParseError::new(1, "missing the field").into()),
+    };
+    let symmetry = match fields.next() {
+        Some("general") => Symmetry::General,
+        Some("symmetric") => Symmetry::Symmetric,
+        Some("skew-symmetric") => Symmetry::SkewSymmetric,
+        Some(other) => return Err(ParseError::new(1, format!("unsupported symmetry `{}`", other)).into()),
+        None => return Err(ParseError::new(1, "missing the symmetry").into()),
+    };
+
+    let mut size: Option<(usize, usize)> = None;
+    let mut edges = Vec::new();
+    let mut read_entries = 0;
+    let mut last_line = 1;
+    for (index, text) in lines {
+        let text = text?;
+        let line = index + 1;
+        last_line = line;
+        let content = text.trim();
+        if content.is_empty() || content.starts_with('%') {
+            continue;
+        }
+        let mut fields = content.split_whitespace();
+        let Some((vertex_count, entries)) = size else {
+            let rows: usize = field(&mut fields, line, "row count")?;
+            let columns: usize = field(&mut fields, line, "column count")?;
+            let entries: usize = field(&mut fields, line, "entry count")?;
+            let vertex_count = rows.max(columns);
+            check_vertex_count::<W>(vertex_count, line)?;
+            check_edge_count(entries, line)?;
+            // Symmetric entries off the diagonal are stored in both directions
+            let count = if symmetry == Symmetry::General { entries } else { entries.saturating_mul(2) };
+            edges.reserve(count.min(RESERVE_LIMIT));
+            size = Some((vertex_count, entries));
+            continue;
+        };
+        let vertex = |fields: &mut std::str::SplitWhitespace| -> Result<W::VHandle, ParseError> {
+            let index: usize = field(fields, line, "index")?;
+            if index == 0 || index > vertex_count {
+                return Err(ParseError::new(line, format!("index {} is out of range", index)));
+            }
+            Ok(W::handle(index - 1))
+        };
+        let from = vertex(&mut fields)?;
+        let to = vertex(&mut fields)?;
+        let weight: W::Weight = if pattern {
+            W::Weight::zero()
+        } else {
+            let text = fields.next().ok_or_else(|| ParseError::new(line, "missing value"))?;
+            parse_weight::<W>(text).ok_or_else(|| ParseError::new(line, format!("`{}` is not a valid weight", text)))?
+        };
+        read_entries += 1;
+        if read_entries > entries {
+            return Err(ParseError::new(line, format!("more than {} entries", entries)).into());
+        }
+        edges.push((from, to, weight));
+        if from != to {
+            match symmetry {
+                Symmetry::General => {}
+                Symmetry::Symmetric => edges.push((to, from, weight)),
+                Symmetry::SkewSymmetric => edges.push((to, from, W::Weight::try_from(-weight.into()).ok().ok_or_else(|| ParseError::new(line, "negated value does not fit into the weight"))?)),
+            }
+        }
+        check_edge_count(edges.len(), line)?;
+    }
+
+    let Some((vertex_count, entries)) = size else {
+        return Err(ParseError::new(last_line, "missing the size line").into());
+    };
+    if read_entries != entries {
+        return Err(ParseError::new(last_line, format!("expected {} entries, found {}", entries, read_entries)).into());
+    }
+    Ok(WeightedGraph::from_edges_with_width(1..=vertex_count as u64, edges)
+        .expect("vertex and edge counts are checked per line"))
+}
+
+fn parse_weight<W: Width>(text: &str) -> Option<W::Weight> {
+    if let Ok(weight) = text.parse() {
+        return Some(weight);
+    }
+    let value: f64 = text.parse().ok()?;
+    if value.fract() != 0.0 || value < i64::MIN as f64 || value >= i64::MAX as f64 {
+        return None;
+    }
+    W::Weight::try_from(value as i64).ok()
+}
+
+/// Writes a square `integer general` matrix with an entry for every edge.
+pub fn write<Edges: EdgeStore<W>, Writer: Write, W: Width>(edges: &Edges, vertices_len: usize, writer: &mut Writer) -> io::Result<()> {
+    let entries = (0..vertices_len).map(W::handle)
+        .map(|vertex| edges.edges_iter(vertex).filter(|edge| W::vh(**edge) != W::NONE).count())
+        .sum::<usize>();
+    writeln!(writer, "%%MatrixMarket matrix coordinate integer general")?;
+    writeln!(writer, "{} {} {}", vertices_len, vertices_len, entries)?;
+    for from in (0..vertices_len).map(W::handle) {
+        for edge in edges.edges_iter(from).filter(|edge| W::vh(**edge) != W::NONE) {
+            writeln!(writer, "{} {} {}", W::index(from) + 1, W::index(W::vh(*edge)) + 1, W::wgt(*edge))?;
+        }
+    }
+    Ok(())
+}
\ No newline at end of file
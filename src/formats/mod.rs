@@ -0,0 +1,99 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::str::FromStr;
+use crate::edge_storage::EdgeStorage;
+use crate::handles::{Width, W64};
+use crate::handles::types::Ci;
+use crate::observer::NoObserver;
+use crate::vertex_storage::VertexStorage;
+use crate::weighted_graph::WeightedGraph;
+
+pub mod dot;
+pub mod graphml;
+pub mod dimacs;
+pub mod snap;
+pub mod matrix_market;
+
+/// Graph of the numeric formats, the vertex values are the vertex ids of the file.
+/// The readers build the default width, their `_with_width` variants any width `W`.
+pub type IdGraph<W = W64> = WeightedGraph<u64, VertexStorage<u64>, EdgeStorage<(), W>, NoObserver, W>;
+
+/// Error of the text format readers, `line` starts at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub(crate) fn new<Message: Into<String>>(line: usize, message: Message) -> Self {
+        ParseError { line, message: message.into() }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Error of the readers of the line based formats. The readers parse the input line by line, but collect
+/// the edges before building the graph so every block gets exactly the capacity of its edges.
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(error) => write!(f, "{}", error),
+            ReadError::Parse(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for ReadError {}
+
+impl From<io::Error> for ReadError {
+    fn from(error: io::Error) -> Self {
+        ReadError::Io(error)
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(error: ParseError) -> Self {
+        ReadError::Parse(error)
+    }
+}
+
+/// Parses the next whitespace separated field of a line.
+pub(crate) fn field<'a, T: FromStr, Fields: Iterator<Item=&'a str>>(fields: &mut Fields, line: usize, what: &str) -> Result<T, ParseError> {
+    match fields.next() {
+        Some(text) => text.parse().map_err(|_| ParseError::new(line, format!("`{}` is not a valid {}", text, what))),
+        None => Err(ParseError::new(line, format!("missing {}", what))),
+    }
+}
+
+/// Upper bound of the edges reserved up front for the count of a header, larger inputs grow while they are read.
+pub(crate) const RESERVE_LIMIT: usize = 1 << 20;
+
+/// Fails at `line` when a vertex is added to `count` vertices that already use up the handle space of `W`.
+pub(crate) fn check_vertex_count<W: Width>(count: usize, line: usize) -> Result<(), ParseError> {
+    match count >= W::index(W::NONE) {
+        true => Err(ParseError::new(line, "vertex count exceeds the handle width")),
+        false => Ok(()),
+    }
+}
+
+/// Fails at `line` once the edges no longer fit into the edge indices of the storage.
+pub(crate) fn check_edge_count(count: usize, line: usize) -> Result<(), ParseError> {
+    match count > Ci::MAX as usize {
+        true => Err(ParseError::new(line, "edge count exceeds the edge index width")),
+        false => Ok(()),
+    }
+}
\ No newline at end of file
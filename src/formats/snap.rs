@@ -0,0 +1,84 @@
+//! SNAP edge lists, one `<from> <to>` or `<from> <to> <weight>` pair per line, `#` starts a comment.
+
+use std::collections::HashMap;
+use std::io;
+use std::io::{BufRead, Write};
+use crate::formats::{check_edge_count, check_vertex_count, field, IdGraph, ParseError, ReadError};
+use crate::handles::{Width, W64};
+use crate::traits::{EdgeStore, EdgeWeight};
+use crate::weighted_graph::WeightedGraph;
+
+/// Graph read from an edge list. The vertices are numbered in the order of appearance, `names` maps the ids of the file to the handles.
+pub struct SnapGraph<W: Width = W64> {
+    pub graph: IdGraph<W>,
+    pub names: HashMap<u64, W::VHandle>,
+}
+
+/// Reads the edge list, the weights default to 0. Undirected lists are stored in both directions,
+/// like in [UndirectedGraph](crate::undirected_graph::UndirectedGraph).
+#[inline(always)]
+pub fn read<Reader: BufRead>(reader: Reader, directed: bool) -> Result<SnapGraph, ReadError> {
+    read_with_width(reader, directed)
+}
+
+/// [read] into a graph of the width `W`. Weights which don't fit into the width are rejected.
+pub fn read_with_width<Reader: BufRead, W: Width>(reader: Reader, directed: bool) -> Result<SnapGraph<W>, ReadError> {
+    let mut ids = Vec::new();
+    let mut names = HashMap::new();
+    let mut edges = Vec::new();
+    for (index, text) in reader.lines().enumerate() {
+        let text = text?;
+        let line = index + 1;
+        let content = text.trim();
+        if content.is_empty() || content.starts_with('#') || content.starts_with('%') {
+            continue;
+        }
+        let mut fields = content.split_whitespace();
+        let mut vertex = |fields: &mut std::str::SplitWhitespace| -> Result<W::VHandle, ParseError> {
+            let id: u64 = field(fields, line, "vertex id")?;
+            if let Some(handle) = names.get(&id) {
+                return Ok(*handle);
+            }
+            check_vertex_count::<W>(ids.len(), line)?;
+            ids.push(id);
+            names.insert(id, W::handle(ids.len() - 1));
+            Ok(W::handle(ids.len() - 1))
+        };
+        let from = vertex(&mut fields)?;
+        let to = vertex(&mut fields)?;
+        let weight: W::Weight = match fields.next() {
+            Some(text) => text.parse().map_err(|_| ParseError::new(line, format!("`{}` is not a valid weight", text)))?,
+            None => W::Weight::zero(),
+        };
+        edges.push((from, to, weight));
+        if !directed && from != to {
+            edges.push((to, from, weight));
+        }
+        check_edge_count(edges.len(), line)?;
+    }
+    let graph = WeightedGraph::from_edges_with_width(ids, edges)
+        .expect("vertex and edge counts are checked per line");
+    Ok(SnapGraph { graph, names })
+}
+
+/// Writes the edges with the handles as ids. Undirected graphs stored in both directions
+/// are written once per pair with `directed` set to false.
+pub fn write<Edges: EdgeStore<W>, Writer: Write, W: Width>(edges: &Edges, vertices_len: usize, directed: bool, weights: bool, writer: &mut Writer) -> io::Result<()> {
+    let written = |from: W::VHandle, to: W::VHandle| to != W::NONE && (directed || from <= to);
+    let edge_count = (0..vertices_len).map(W::handle)
+        .map(|from| edges.edges_iter(from).filter(|edge| written(from, W::vh(**edge))).count())
+        .sum::<usize>();
+    writeln!(writer, "# {} graph", if directed { "Directed" } else { "Undirected" })?;
+    writeln!(writer, "# Nodes: {} Edges: {}", vertices_len, edge_count)?;
+    writeln!(writer, "# FromNodeId\tToNodeId{}", if weights { "\tWeight" } else { "" })?;
+    for from in (0..vertices_len).map(W::handle) {
+        for edge in edges.edges_iter(from).filter(|edge| written(from, W::vh(**edge))) {
+            if weights {
+                writeln!(writer, "{}\t{}\t{}", from, W::vh(*edge), W::wgt(*edge))?;
+            } else {
+                writeln!(writer, "{}\t{}", from, W::vh(*edge))?;
+            }
+        }
+    }
+    Ok(())
+}
\ No newline at end of file
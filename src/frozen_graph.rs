@@ -0,0 +1,48 @@
+use std::marker::PhantomData;
+use crate::csr_edge_storage::CsrEdgeStorage;
+use crate::graph::Graph;
+use crate::edge_storage::{EdgeStorage, Packed};
+use crate::handles::{Width, W64};
+use crate::observer::NoObserver;
+use crate::traits::StoreVertex;
+
+/// Read-only graph produced by [Graph::freeze]. The edges are stored as compressed sparse rows,
+/// see [CsrEdgeStorage]. `W` is the edge width of the graph it was frozen from.
+pub struct FrozenGraph<VertexType, VertexStorageType, EdgeType = (), Source = Packed, W: Width = W64>
+where
+    VertexStorageType: StoreVertex<VertexType=VertexType>,
+{
+    pub vertices: VertexStorageType,
+    pub edge_storage: CsrEdgeStorage<EdgeType, W, Source>,
+}
+
+impl<VertexType, VertexStorageType, EdgeType, Source, W> FrozenGraph<VertexType, VertexStorageType, EdgeType, Source, W>
+where
+    VertexStorageType: StoreVertex<VertexType=VertexType>,
+    EdgeType: Default + Clone,
+    W: Width,
+{
+    /// Converts back into the mutable graph, see [CsrEdgeStorage::thaw].
+    pub fn thaw(self) -> Graph<VertexType, VertexStorageType, EdgeStorage<EdgeType, W, Source>, NoObserver, W> {
+        Graph{
+            vertices: self.vertices,
+            edge_storage: self.edge_storage.thaw(),
+            observer: NoObserver,
+            width: PhantomData,
+        }
+    }
+}
+
+impl<VertexType, VertexStorageType, EdgeType, Source, W> Clone for FrozenGraph<VertexType, VertexStorageType, EdgeType, Source, W>
+where
+    VertexStorageType: StoreVertex<VertexType=VertexType> + Clone,
+    EdgeType: Clone,
+    W: Width,
+{
+    fn clone(&self) -> Self {
+        FrozenGraph{
+            vertices: self.vertices.clone(),
+            edge_storage: self.edge_storage.clone(),
+        }
+    }
+}
\ No newline at end of file
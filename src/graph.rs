@@ -1,44 +1,132 @@
-use crate::edge_storage::{EdgeStorage};
-use crate::handles::types::{VHandle, Ci};
-use crate::traits::{EdgeManipulate, StoreVertex};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+use crate::adjacency_matrix::AdjacencyMatrix;
+use crate::edge_storage::{EdgeStorage, PayloadWeighted};
+use crate::formats::dot::Dot;
+use crate::formats::graphml::{GraphMl, KeyType};
+use crate::frozen_graph::FrozenGraph;
+use crate::handles::{GHandle, Width, W64};
+use crate::handles::types::{Edge, VHandle, Ci, Weight};
+use crate::observer::{GraphObserver, NoObserver};
+use crate::traits::{EdgeManipulate, EdgeWeight, Generational, PlaceholderEdges, StoreVertex, WeightStore, WeightedEdgeManipulate};
+use crate::transaction::{Operation, Transaction};
 use crate::vertex_storage::VertexStorage;
+use crate::views::filtered::FilteredView;
+use crate::views::reversed::ReversedView;
 use crate::views::tree::Tree;
 
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Error {
+    /// The handle does not refer to an existing vertex.
+    InvalidHandle(VHandle),
+    /// The generational handle refers to a vertex which was removed.
+    StaleHandle,
+    /// The edge block of the vertex is full and the storage is not allowed to grow it.
+    CapacityExceeded {
+        handle: VHandle,
+        capacity: usize,
+        required: usize,
+    },
+    /// No more vertices or edges can be addressed with the configured handle size.
+    HandleSpaceExhausted,
+    /// The weight does not fit into the packed edge.
+    WeightOutOfRange,
+    /// There is no edge between the two vertices.
+    NoEdge {
+        from: VHandle,
+        to: VHandle,
+    },
+    /// The storage is frozen, see [Graph::freeze].
+    Frozen,
+    /// Never returned anymore, the handle errors are reported as [Error::InvalidHandle] and [Error::StaleHandle].
+    #[deprecated(note = "use `Error::InvalidHandle` or `Error::StaleHandle`")]
     NoHandle,
 }
-pub struct Graph<VertexType, VertexStorageType, EdgeStorageType>
+
+#[allow(deprecated)]
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidHandle(handle) => write!(f, "invalid vertex handle {}", handle),
+            Error::StaleHandle => write!(f, "vertex handle refers to a removed vertex"),
+            Error::CapacityExceeded { handle, capacity, required } => {
+                write!(f, "vertex {} needs {} edges but has capacity for {}", handle, required, capacity)
+            }
+            Error::HandleSpaceExhausted => write!(f, "handle space exhausted"),
+            Error::WeightOutOfRange => write!(f, "weight does not fit into the packed edge"),
+            Error::NoEdge { from, to } => write!(f, "no edge from {} to {}", from, to),
+            Error::Frozen => write!(f, "storage is frozen"),
+            Error::NoHandle => write!(f, "no such handle"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Describes how handles changed after [Graph::remove_vertex].
+/// The last vertex is moved into the slot of the removed one, so at most one surviving handle changes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct VertexRemap<W: Width = W64> {
+    pub removed: W::VHandle,
+    /// Previous handle of the vertex which now lives at `removed`. `NONE` if no vertex was moved.
+    pub moved: W::VHandle,
+}
+
+impl<W: Width> VertexRemap<W> {
+    /// Translates a handle obtained before the removal. Returns `NONE` for the removed vertex.
+    #[inline(always)]
+    pub fn apply(&self, handle: W::VHandle) -> W::VHandle {
+        if handle == self.removed {
+            return W::NONE;
+        }
+        if handle == self.moved {
+            return self.removed;
+        }
+        handle
+    }
+}
+/// `Observer` is notified about the mutations made through the graph, see [GraphObserver].
+/// `W` is the edge width of the storage, the handles and weights of the graph are those of the width, see [Width].
+pub struct Graph<VertexType, VertexStorageType, EdgeStorageType, Observer = NoObserver, W: Width = W64>
 where
     VertexStorageType: StoreVertex<VertexType=VertexType>,
-    EdgeStorageType: EdgeManipulate,
+    EdgeStorageType: EdgeManipulate<W>,
+    Observer: GraphObserver<VertexType, W>,
 {
     pub vertices: VertexStorageType,
     pub edge_storage: EdgeStorageType,
+    pub observer: Observer,
+    pub(crate) width: PhantomData<W>,
 }
 
-impl<VertexType, VertexStorageType, EdgeStorageType> Clone for Graph<VertexType, VertexStorageType, EdgeStorageType>
+impl<VertexType, VertexStorageType, EdgeStorageType, Observer, W> Clone for Graph<VertexType, VertexStorageType, EdgeStorageType, Observer, W>
 where
-    EdgeStorageType: EdgeManipulate,
+    EdgeStorageType: EdgeManipulate<W>,
     VertexType: Clone,
-    VertexStorageType: StoreVertex<VertexType=VertexType> + Clone {
+    VertexStorageType: StoreVertex<VertexType=VertexType> + Clone,
+    Observer: GraphObserver<VertexType, W> + Clone,
+    W: Width {
     #[inline(always)]
     fn clone(&self) -> Self {
         Graph{
             vertices: self.vertices.clone(),
             edge_storage: self.edge_storage.clone(),
+            observer: self.observer.clone(),
+            width: PhantomData,
         }
     }
     #[inline(always)]
     fn clone_from(&mut self, source: &Self) {
         self.vertices.clone_from(&source.vertices);
         self.edge_storage.clone_from(&source.edge_storage);
+        self.observer.clone_from(&source.observer);
     }
 }
 
-impl<VertexType> Default for Graph<VertexType, VertexStorage<VertexType>, EdgeStorage> {
+impl<VertexType, W: Width> Default for Graph<VertexType, VertexStorage<VertexType>, EdgeStorage<(), W>, NoObserver, W> {
     fn default() -> Self {
-        Self::new()
+        Self::with_width(0)
     }
 }
 
@@ -48,49 +136,402 @@ impl<VertexType> Graph<VertexType, VertexStorage<VertexType>, EdgeStorage>
         Graph{
             edge_storage: EdgeStorage::new_large(),
             vertices: VertexStorage::new(),
+            observer: NoObserver,
+            width: PhantomData,
         }
     }
     pub fn with_reserve(reserve: Ci) -> Self {
         Graph{
             edge_storage: EdgeStorage::with_reserve(reserve),
             vertices: VertexStorage::new(),
+            observer: NoObserver,
+            width: PhantomData,
         }
     }
     pub fn new() -> Self {
         Graph{
             edge_storage: EdgeStorage::new(),
             vertices: VertexStorage::new(),
+            observer: NoObserver,
+            width: PhantomData,
         }
     }
 
+    /// Builds the graph in bulk. Vertex `i` gets the `i`-th value, the edges are `(from, to, weight)` triples.
+    /// Every edge block has exactly the out-degree of its vertex, see [EdgeStorage::from_edges].
+    #[inline(always)]
+    pub fn from_edges<Values, Edges>(values: Values, edges: Edges) -> Result<Self, Error>
+    where
+        Values: IntoIterator<Item=VertexType>,
+        Edges: IntoIterator<Item=(VHandle, VHandle, Weight)>,
+    {
+        Self::from_edges_with_width(values, edges)
+    }
+}
 
+impl<VertexType, W: Width> Graph<VertexType, VertexStorage<VertexType>, EdgeStorage<(), W>, NoObserver, W>
+{
+    /// [Graph::with_reserve] of the width `W`, which is picked by the type, e.g. `let graph: Graph<&str, _, _, _, W16> = Graph::with_width(4)`.
+    /// The constructors of the default width need no annotation.
+    pub fn with_width(reserve: Ci) -> Self {
+        Graph{
+            edge_storage: EdgeStorage::with_edge_data(reserve),
+            vertices: VertexStorage::new(),
+            observer: NoObserver,
+            width: PhantomData,
+        }
+    }
+
+    /// [Graph::from_edges] of the width `W`, see [EdgeStorage::from_edges_with_width].
+    pub fn from_edges_with_width<Values, Edges>(values: Values, edges: Edges) -> Result<Self, Error>
+    where
+        Values: IntoIterator<Item=VertexType>,
+        Edges: IntoIterator<Item=(W::VHandle, W::VHandle, W::Weight)>,
+    {
+        let mut vertices = VertexStorage::new();
+        for value in values {
+            vertices.push(value);
+        }
+        Ok(Graph{
+            edge_storage: EdgeStorage::from_edges_with_width(vertices.len(), edges)?,
+            vertices,
+            observer: NoObserver,
+            width: PhantomData,
+        })
+    }
+}
+
+impl<VertexType, EdgeType: Default + Clone> Graph<VertexType, VertexStorage<VertexType>, EdgeStorage<EdgeType>>
+{
+    /// Creates a graph storing an `EdgeType` value for every edge, see [EdgeStorage::with_edge_data].
+    pub fn with_edge_data(reserve: Ci) -> Self {
+        Graph{
+            edge_storage: EdgeStorage::with_edge_data(reserve),
+            vertices: VertexStorage::new(),
+            observer: NoObserver,
+            width: PhantomData,
+        }
+    }
 }
 
-impl<VertexType, VertexStorageType, EdgeStorageType> Graph<VertexType, VertexStorageType, EdgeStorageType>
+impl<VertexType, WeightType: EdgeWeight + Default> Graph<VertexType, VertexStorage<VertexType>, PayloadWeighted<WeightType>>
+{
+    /// Creates a graph whose edge payloads are the weights read by the algorithms, see [Payload](crate::edge_storage::Payload).
+    pub fn with_payload_weights(reserve: Ci) -> Self {
+        Graph{
+            edge_storage: EdgeStorage::with_edge_data(reserve),
+            vertices: VertexStorage::new(),
+            observer: NoObserver,
+            width: PhantomData,
+        }
+    }
+}
+
+impl<VertexType> Graph<VertexType, VertexStorage<VertexType>, AdjacencyMatrix>
+{
+    /// Creates a graph on a dense [AdjacencyMatrix] with rows for `vertices` vertices.
+    pub fn with_adjacency_matrix(vertices: usize) -> Self {
+        Graph{
+            edge_storage: AdjacencyMatrix::with_capacity(vertices),
+            vertices: VertexStorage::new(),
+            observer: NoObserver,
+            width: PhantomData,
+        }
+    }
+}
+
+impl<VertexType, VertexStorageType, EdgeType, Source, Observer, W> Graph<VertexType, VertexStorageType, EdgeStorage<EdgeType, W, Source>, Observer, W>
 where
-    EdgeStorageType: EdgeManipulate,
-    VertexStorageType: StoreVertex<VertexType=VertexType>{
+    VertexStorageType: StoreVertex<VertexType=VertexType>,
+    EdgeType: Default + Clone,
+    Observer: GraphObserver<VertexType, W>,
+    W: Width,
+{
+    /// Converts the graph into compressed sparse rows for read-heavy use. The reserve, free blocks
+    /// and the in-edge index are dropped, as is the observer. See [FrozenGraph::thaw] to get a mutable graph back.
+    pub fn freeze(self) -> FrozenGraph<VertexType, VertexStorageType, EdgeType, Source, W> {
+        FrozenGraph{
+            vertices: self.vertices,
+            edge_storage: self.edge_storage.freeze(),
+        }
+    }
+
+    /// Returns a copy of the graph with every edge reversed, see [EdgeStorage::transposed].
+    /// The copy is not observed.
+    pub fn transpose(&self) -> Graph<VertexType, VertexStorageType, EdgeStorage<EdgeType, W, Source>, NoObserver, W>
+    where
+        VertexStorageType: Clone,
+    {
+        Graph{
+            vertices: self.vertices.clone(),
+            edge_storage: self.edge_storage.transposed(),
+            observer: NoObserver,
+            width: PhantomData,
+        }
+    }
+}
+
+impl<VertexType, VertexStorageType, EdgeStorageType, Observer, W> Graph<VertexType, VertexStorageType, EdgeStorageType, Observer, W>
+where
+    EdgeStorageType: EdgeManipulate<W>,
+    VertexStorageType: StoreVertex<VertexType=VertexType>,
+    Observer: GraphObserver<VertexType, W>,
+    W: Width {
+    /// Replaces the observer, the graph itself is kept.
+    pub fn with_observer<NewObserver: GraphObserver<VertexType, W>>(self, observer: NewObserver) -> Graph<VertexType, VertexStorageType, EdgeStorageType, NewObserver, W> {
+        Graph{
+            vertices: self.vertices,
+            edge_storage: self.edge_storage,
+            observer,
+            width: PhantomData,
+        }
+    }
+
+    /// The tree reports to the observer of the graph.
     #[inline(always)]
-    pub fn tree_view(&mut self) -> Tree<VertexType, VertexStorageType, EdgeStorageType> {
-        return Tree::new(&mut self.edge_storage, &mut self.vertices);
+    pub fn tree_view(&mut self) -> Tree<'_, VertexType, VertexStorageType, EdgeStorageType, &mut Observer, W>
+    where
+        EdgeStorageType: PlaceholderEdges<W>,
+    {
+        return Tree::with_observer(&mut self.edge_storage, &mut self.vertices, &mut self.observer);
     }
 
-    pub fn create_and_connect(&mut self, from: VHandle, val: VertexType, edge_count: Ci) -> VHandle {
+    pub fn create_and_connect(&mut self, from: W::VHandle, val: VertexType, edge_count: Ci) -> W::VHandle {
         let new_vertex = self.create(val, edge_count);
-        self.edge_storage.connect(from, new_vertex);
+        self.connect(from, new_vertex);
         new_vertex
     }
     #[inline(always)]
-    pub fn create_and_connect_0(&mut self, from: VHandle, val: VertexType) -> VHandle {
+    pub fn create_and_connect_0(&mut self, from: W::VHandle, val: VertexType) -> W::VHandle {
         self.create_and_connect(from, val, 0)
     }
 
-    pub fn create(&mut self, val: VertexType, edge_count: Ci) -> VHandle {
+    pub fn create(&mut self, val: VertexType, edge_count: Ci) -> W::VHandle {
         self.vertices.push(val);
-        self.edge_storage.create_vertex_entry(edge_count)
+        let handle = self.edge_storage.create_vertex_entry(edge_count);
+        self.observer.vertex_created(handle, &self.vertices[handle.into()]);
+        handle
     }
     #[inline(always)]
-    pub fn create_leaf(&mut self, val: VertexType) -> VHandle {
+    pub fn create_leaf(&mut self, val: VertexType) -> W::VHandle {
         self.create(val, 0)
     }
+
+    /// Like [Graph::create], but reports an error instead of panicking. The graph is unchanged on error.
+    pub fn try_create(&mut self, val: VertexType, edge_count: Ci) -> Result<W::VHandle, Error> {
+        let handle = self.edge_storage.try_create_vertex_entry(edge_count)?;
+        self.vertices.push(val);
+        self.observer.vertex_created(handle, &self.vertices[handle.into()]);
+        Ok(handle)
+    }
+
+    #[inline(always)]
+    pub fn connect(&mut self, from: W::VHandle, to: W::VHandle) {
+        self.edge_storage.connect(from, to);
+        self.observer.connected(from, to, W::Weight::zero());
+    }
+
+    /// The observer is only notified if there was an edge to remove.
+    #[inline(always)]
+    pub fn disconnect(&mut self, from: W::VHandle, to: W::VHandle) {
+        if self.edge_storage.disconnect(from, to) {
+            self.observer.disconnected(from, to);
+        }
+    }
+
+    #[inline(always)]
+    pub fn try_connect(&mut self, from: W::VHandle, to: W::VHandle) -> Result<(), Error> {
+        self.edge_storage.try_connect(from, to)?;
+        self.observer.connected(from, to, W::Weight::zero());
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn try_disconnect(&mut self, from: W::VHandle, to: W::VHandle) -> Result<(), Error> {
+        self.edge_storage.try_disconnect(from, to)?;
+        self.observer.disconnected(from, to);
+        Ok(())
+    }
+
+    /// Removes the vertex, its edges and every edge pointing at it. The edge block of the vertex is released
+    /// for reuse. The last vertex takes over the removed handle, use the returned remap to fix up stored handles.
+    #[inline(always)]
+    pub fn remove_vertex(&mut self, handle: W::VHandle) -> VertexRemap<W> {
+        self.take_vertex(handle).1
+    }
+
+    /// [Graph::remove_vertex] which returns the value of the removed vertex.
+    pub(crate) fn take_vertex(&mut self, handle: W::VHandle) -> (VertexType, VertexRemap<W>) {
+        let value = self.vertices.swap_remove(handle.into());
+        let moved = self.edge_storage.remove_vertex_entry(handle);
+        let remap = VertexRemap {
+            removed: handle,
+            moved,
+        };
+        self.observer.vertex_removed(remap);
+        (value, remap)
+    }
+}
+
+/// Views and exports, which read the edges at the default width.
+impl<VertexType, VertexStorageType, EdgeStorageType, Observer> Graph<VertexType, VertexStorageType, EdgeStorageType, Observer>
+where
+    EdgeStorageType: EdgeManipulate,
+    VertexStorageType: StoreVertex<VertexType=VertexType>,
+    Observer: GraphObserver<VertexType>{
+    /// Subgraph of the vertices and edges passing the filters, see [FilteredView].
+    #[inline(always)]
+    pub fn filtered_view<VertexFilter, EdgeFilter>(&mut self, vertex_filter: VertexFilter, edge_filter: EdgeFilter) -> FilteredView<'_, EdgeStorageType, VertexFilter, EdgeFilter>
+    where
+        VertexFilter: Fn(VHandle) -> bool,
+        EdgeFilter: Fn(VHandle, usize, Edge) -> bool,
+    {
+        FilteredView::new(&mut self.edge_storage, self.vertices.len(), vertex_filter, edge_filter)
+    }
+
+    /// Transpose of the graph built on first access, see [ReversedView].
+    #[inline(always)]
+    pub fn reversed_view(&mut self) -> ReversedView<'_, EdgeStorageType> {
+        ReversedView::new(&mut self.edge_storage, self.vertices.len())
+    }
+
+    /// DOT export without weights, see [Dot].
+    #[inline(always)]
+    pub fn dot(&self) -> Dot<'_, VertexType, VertexStorageType, EdgeStorageType>
+    where
+        EdgeStorageType: WeightStore,
+        EdgeStorageType::WeightType: Display,
+        VertexType: Display,
+    {
+        Dot::new(&self.vertices, &self.edge_storage)
+    }
+
+    /// GraphML export without weights, see [GraphMl].
+    #[inline(always)]
+    pub fn graphml(&self) -> GraphMl<'_, VertexType, VertexStorageType, EdgeStorageType>
+    where
+        EdgeStorageType: WeightStore,
+        EdgeStorageType::WeightType: KeyType,
+    {
+        GraphMl::new(&self.vertices, &self.edge_storage)
+    }
+}
+
+impl<VertexType, VertexStorageType, EdgeStorageType, Observer, W> Graph<VertexType, VertexStorageType, EdgeStorageType, Observer, W>
+where
+    EdgeStorageType: WeightedEdgeManipulate<W>,
+    VertexStorageType: StoreVertex<VertexType=VertexType>,
+    Observer: GraphObserver<VertexType, W>,
+    W: Width {
+    /// Starts a batch of changes which is rolled back on drop unless committed, see [Transaction].
+    #[inline(always)]
+    pub fn transaction(&mut self) -> Transaction<'_, VertexType, VertexStorageType, EdgeStorageType, Observer, W> {
+        Transaction::new(self)
+    }
+
+    /// Reverts a log returned by [Transaction::commit], newest operation first.
+    /// The graph has to be in the state the log ended in, so logs are undone in the reverse order of their commits.
+    pub fn undo(&mut self, log: Vec<Operation<VertexType, W>>) {
+        for operation in log.into_iter().rev() {
+            operation.revert(self);
+        }
+    }
+}
+
+/// The generations are read from the edge storage, which keeps them in step with the vertices.
+impl<VertexType, VertexStorageType, EdgeStorageType, Observer, W> Graph<VertexType, VertexStorageType, EdgeStorageType, Observer, W>
+where
+    EdgeStorageType: EdgeManipulate<W> + Generational<W>,
+    VertexStorageType: StoreVertex<VertexType=VertexType>,
+    Observer: GraphObserver<VertexType, W>,
+    W: Width {
+    /// Pairs the handle with the current generation of the vertex.
+    #[inline(always)]
+    pub fn generational(&self, handle: W::VHandle) -> Result<GHandle<W::VHandle>, Error> {
+        self.edge_storage.generational(handle)
+    }
+
+    #[inline(always)]
+    pub fn get(&self, handle: GHandle<W::VHandle>) -> Result<&VertexType, Error> {
+        let handle = self.edge_storage.validate(handle)?;
+        Ok(&self.vertices[handle.into()])
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self, handle: GHandle<W::VHandle>) -> Result<&mut VertexType, Error> {
+        let handle = self.edge_storage.validate(handle)?;
+        Ok(&mut self.vertices[handle.into()])
+    }
+
+    #[inline(always)]
+    pub fn edges_checked(&self, handle: GHandle<W::VHandle>) -> Result<&[W::Edge], Error> {
+        let handle = self.edge_storage.validate(handle)?;
+        Ok(self.edge_storage.edges_as_slice(handle))
+    }
+
+    pub fn connect_checked(&mut self, from: GHandle<W::VHandle>, to: GHandle<W::VHandle>) -> Result<(), Error> {
+        let from = self.edge_storage.validate(from)?;
+        let to = self.edge_storage.validate(to)?;
+        self.connect(from, to);
+        Ok(())
+    }
+
+    pub fn disconnect_checked(&mut self, from: GHandle<W::VHandle>, to: GHandle<W::VHandle>) -> Result<(), Error> {
+        let from = self.edge_storage.validate(from)?;
+        let to = self.edge_storage.validate(to)?;
+        self.disconnect(from, to);
+        Ok(())
+    }
+
+    pub fn remove_vertex_checked(&mut self, handle: GHandle<W::VHandle>) -> Result<VertexRemap<W>, Error> {
+        let handle = self.edge_storage.validate(handle)?;
+        Ok(self.remove_vertex(handle))
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "Graph")]
+struct GraphFormat<VertexStorageType, EdgeStorageType> {
+    vertices: VertexStorageType,
+    edge_storage: EdgeStorageType,
+}
+
+#[cfg(feature = "serde")]
+impl<VertexType, EdgeType, Source, W> serde::Serialize for Graph<VertexType, VertexStorage<VertexType>, EdgeStorage<EdgeType, W, Source>, NoObserver, W>
+where
+    VertexType: serde::Serialize,
+    EdgeType: serde::Serialize + Default + Clone,
+    W: Width,
+    W::Edge: serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        GraphFormat {
+            vertices: &self.vertices,
+            edge_storage: &self.edge_storage,
+        }.serialize(serializer)
+    }
+}
+
+/// Fails if the number of vertex values does not match the number of edge entries.
+#[cfg(feature = "serde")]
+impl<'de, VertexType, EdgeType, Source, W> serde::Deserialize<'de> for Graph<VertexType, VertexStorage<VertexType>, EdgeStorage<EdgeType, W, Source>, NoObserver, W>
+where
+    VertexType: serde::Deserialize<'de>,
+    EdgeType: serde::Deserialize<'de> + Default + Clone,
+    W: Width,
+    W::Edge: serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let format = GraphFormat::<VertexStorage<VertexType>, EdgeStorage<EdgeType, W, Source>>::deserialize(deserializer)?;
+        if format.vertices.len() != format.edge_storage.vertices_len() {
+            return Err(serde::de::Error::custom("vertex count does not match the edge storage"));
+        }
+        Ok(Graph{
+            vertices: format.vertices,
+            edge_storage: format.edge_storage,
+            observer: NoObserver,
+            width: PhantomData,
+        })
+    }
 }
\ No newline at end of file
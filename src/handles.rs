@@ -1,69 +1,205 @@
-use crate::handles::types::{MASK, SHIFT, VHandle, Weight, Edge};
-#[cfg(msize_type = "u16")]
-pub mod types{
-    pub type PackedEdge = u16;
-    pub type Weight = i8;
-    pub type VHandle = u8;
-    pub type Ci = u8;
-    pub(in crate::handles) const SHIFT: usize = 8;
-    pub(in crate::handles) const MASK: u8 = 0xFF;
-    pub const UNSET: u8 = MASK;
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::str::FromStr;
+use crate::handles::types::VHandle;
+use crate::traits::EdgeWeight;
+
+/// Edge width of a storage, the packing functions of one of the width modules as a type parameter.
+/// Storages of different widths can be used side by side, see [W16], [W32] and [W64].
+pub trait Width: Copy + Debug + 'static {
+    type Edge: Copy + Eq + Hash + Debug + Default;
+    /// Parsed and range checked through `i64` by the format readers.
+    type Weight: EdgeWeight + Eq + Hash + Debug + Display + Default + FromStr + TryFrom<i64> + Into<i64>;
+    /// Converts losslessly into the default [VHandle], which is what [Error](crate::graph::Error) reports.
+    type VHandle: Copy + Eq + Ord + Hash + Debug + Display + Default + Into<VHandle>;
+    const NONE: Self::VHandle;
+    /// Bits of the packed edge, recorded in snapshots.
+    const BITS: u32;
+    fn vh(edge: Self::Edge) -> Self::VHandle;
+    fn wgt(edge: Self::Edge) -> Self::Weight;
+    fn vh_pack(handle: Self::VHandle) -> Self::Edge;
+    fn pack(handle: Self::VHandle, weight: Self::Weight) -> Self::Edge;
+    fn set_wgt(edge: Self::Edge, weight: Self::Weight) -> Self::Edge;
+    fn set_vh(edge: Self::Edge, handle: Self::VHandle) -> Self::Edge;
+    /// Casts to usize for convenient indexing.
+    fn index(handle: Self::VHandle) -> usize;
+    /// Casts the index to a handle, truncating indices which don't fit.
+    fn handle(index: usize) -> Self::VHandle;
+    /// Little-endian bytes of the packed edge.
+    fn to_le_bytes(edge: Self::Edge) -> impl AsRef<[u8]>;
 }
 
+/// Generates the types and packing functions of one edge width. The handle takes the low half of the edge,
+/// the weight the high half.
+macro_rules! edge_width {
+    ($name:ident, $marker:ident, $edge:ty, $weight:ty, $handle:ty, $doc:literal) => {
+        #[doc = $doc]
+        pub mod $name {
+            pub mod types {
+                pub type Edge = $edge;
+                pub type Weight = $weight;
+                pub type VHandle = $handle;
+                /// Compact integer
+                pub type Ci = $handle;
+                pub(in crate::handles) const SHIFT: usize = <$handle>::BITS as usize;
+                pub(in crate::handles) const MASK: $handle = <$handle>::MAX;
+                pub const UNSET: $handle = MASK;
+            }
+            use types::{MASK, SHIFT, VHandle, Weight, Edge};
 
-#[cfg(msize_type = "u32")]
-pub mod types{
-    pub type PackedEdge = u32;
-    pub type Weight = i16;
-    pub type VHandle = u16;
-    pub type Ci = u16;
-    pub(in crate::handles) const SHIFT: usize = 16;
-    pub(in crate::handles) const MASK: u16 = 0xFFFF;
-    pub const UNSET: u16 = MASK;
+            pub const NONE: VHandle = VHandle::MAX;
 
-}
+            ///Casts to usize for convenient indexing
+            #[inline(always)]
+            pub fn vhu(handle: Edge) -> usize {
+                (handle as VHandle) as usize
+            }
+            #[inline(always)]
+            pub fn vh(handle: Edge) -> VHandle {
+                handle as VHandle
+            }
 
+            #[inline(always)]
+            pub fn wgt(handle: Edge) -> Weight {
+                (handle >> SHIFT) as Weight
+            }
+            #[inline(always)]
+            pub fn vh_pack(handle: VHandle) -> Edge {
+                handle as Edge
+            }
+            #[inline(always)]
+            pub fn pack(node_id: VHandle, weight: Weight) -> Edge {
+                (node_id as Edge) | ((weight as Edge) << SHIFT)
+            }
+            #[inline(always)]
+            pub fn set_wgt(handle: Edge, weight: Weight) -> Edge {
+                (handle & !((MASK as Edge) << SHIFT)) | ((weight as Edge) << SHIFT)
+            }
+            #[inline(always)]
+            pub fn set_vh(handle: Edge, vert_id: VHandle) -> Edge {
+                (handle & !(MASK as Edge)) | (vert_id as Edge)
+            }
+        }
 
+        #[doc = $doc]
+        #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+        pub struct $marker;
 
-#[cfg(msize_type = "u64")]
-pub mod types {
-    pub type Edge = u64;
-    pub type Weight = i32;
-    pub type VHandle = u32;
-    pub type Ci = u32; /// Compact integer
-    pub(in crate::handles) const SHIFT: usize = 32;
-    pub(in crate::handles) const MASK: u32 = 0xFFFFFFFF;
+        impl Width for $marker {
+            type Edge = $edge;
+            type Weight = $weight;
+            type VHandle = $handle;
+            const NONE: $handle = $name::NONE;
+            const BITS: u32 = <$edge>::BITS;
+            #[inline(always)]
+            fn vh(edge: $edge) -> $handle {
+                $name::vh(edge)
+            }
+            #[inline(always)]
+            fn wgt(edge: $edge) -> $weight {
+                $name::wgt(edge)
+            }
+            #[inline(always)]
+            fn vh_pack(handle: $handle) -> $edge {
+                $name::vh_pack(handle)
+            }
+            #[inline(always)]
+            fn pack(handle: $handle, weight: $weight) -> $edge {
+                $name::pack(handle, weight)
+            }
+            #[inline(always)]
+            fn set_wgt(edge: $edge, weight: $weight) -> $edge {
+                $name::set_wgt(edge, weight)
+            }
+            #[inline(always)]
+            fn set_vh(edge: $edge, handle: $handle) -> $edge {
+                $name::set_vh(edge, handle)
+            }
+            #[inline(always)]
+            fn index(handle: $handle) -> usize {
+                handle as usize
+            }
+            #[inline(always)]
+            fn handle(index: usize) -> $handle {
+                index as $handle
+            }
+            #[inline(always)]
+            fn to_le_bytes(edge: $edge) -> impl AsRef<[u8]> {
+                edge.to_le_bytes()
+            }
+        }
+    };
 }
 
-pub const NONE: VHandle = VHandle::MAX;
+edge_width!(w16, W16, u16, i8, u8, "16 bit edges. Up to 255 vertices per storage, `i8` weights.");
+edge_width!(w32, W32, u32, i16, u16, "32 bit edges. Up to 65535 vertices per storage, `i16` weights.");
+edge_width!(w64, W64, u64, i32, u32, "64 bit edges, the default width.");
 
-///Casts to usize for convenient indexing
-#[inline(always)]
-pub fn vhu(handle: Edge) -> usize {
-    (handle as VHandle) as usize
-}
-#[inline(always)]
-pub fn vh(handle: Edge) -> VHandle {
-    handle as VHandle
-}
+// The crate level types are the default width, storages take the width as a type parameter.
+pub use w64::*;
 
-#[inline(always)]
-pub fn wgt(handle: Edge) -> Weight {
-    (handle >> SHIFT) as Weight
-}
-#[inline(always)]
-pub fn vh_pack(handle: VHandle) -> Edge {
-    handle as Edge
-}
-#[inline(always)]
-pub fn pack(node_id: VHandle, weight: Weight) -> Edge {
-    (node_id as Edge) | ((weight as Edge) << SHIFT)
+pub type Generation = u32;
+
+/// Vertex handle paired with the generation of its slot.
+/// Unlike a bare [VHandle] it is rejected once the vertex is removed or the slot is reused.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct GHandle<Handle = VHandle> {
+    pub handle: Handle,
+    pub generation: Generation,
 }
-#[inline(always)]
-pub fn set_wgt(handle: Edge, weight: Weight) -> Edge {
-    (handle & !((MASK as Edge) << SHIFT)) | ((weight as Edge) << SHIFT)
+
+/// Generation counters of vertex slots. Counters outlive the slots, so a reused slot never
+/// matches a handle issued for its previous occupant.
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+pub(crate) struct Generations {
+    data: Vec<Generation>,
 }
-#[inline(always)]
-pub fn set_vh(handle: Edge, vert_id: VHandle) -> Edge {
-    (handle & !(MASK as Edge)) | (vert_id as Edge)
+
+impl Generations {
+    #[inline(always)]
+    pub(crate) fn new() -> Self {
+        Generations {
+            data: Vec::new(),
+        }
+    }
+
+    /// Called after a slot was appended at `index`.
+    #[inline(always)]
+    pub(crate) fn push(&mut self, index: usize) {
+        if index == self.data.len() {
+            self.data.push(0);
+        }
+    }
+
+    /// Called after the slot at `index` was swap removed. `last` is the slot which was vacated.
+    #[inline(always)]
+    pub(crate) fn swap_remove(&mut self, index: usize, last: usize) {
+        self.data[index] = self.data[index].wrapping_add(1);
+        if index != last {
+            self.data[last] = self.data[last].wrapping_add(1);
+        }
+    }
+
+    /// Number of slots which ever existed.
+    #[cfg(feature = "serde")]
+    #[inline(always)]
+    pub(crate) fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns the generation of the slot, occupied or not.
+    #[inline(always)]
+    pub(crate) fn slot(&self, index: usize) -> Option<Generation> {
+        self.data.get(index).copied()
+    }
+
+    /// Returns the generation of the slot if it is one of the first `len` occupied slots.
+    #[inline(always)]
+    pub(crate) fn get(&self, index: usize, len: usize) -> Option<Generation> {
+        if index >= len {
+            return None;
+        }
+        self.data.get(index).copied()
+    }
 }
\ No newline at end of file
@@ -3,11 +3,19 @@ pub mod graph;
 pub mod traits;
 pub mod views;
 pub mod edge_storage;
+pub mod csr_edge_storage;
+pub mod adjacency_matrix;
+pub mod snapshot;
 pub mod handles;
 pub mod weighted_graph;
+pub mod undirected_graph;
+pub mod frozen_graph;
 pub mod algorithms;
 pub mod vertex_storage;
 pub mod utils;
+pub mod formats;
+pub mod observer;
+pub mod transaction;
 
 #[cfg(test)]
 pub mod tests;
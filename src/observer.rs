@@ -0,0 +1,62 @@
+use crate::graph::VertexRemap;
+use crate::handles::{Width, W64};
+
+/// Receives the mutations made through [crate::graph::Graph], [crate::weighted_graph::WeightedGraph] and
+/// [crate::views::tree::Tree], for keeping secondary indexes in sync. Every method does nothing by default.
+/// Changes made directly on the vertex or edge storage bypass the observer.
+/// The handles and weights are those of the edge width `W` of the graph.
+pub trait GraphObserver<VertexType, W: Width = W64> {
+    #[inline(always)]
+    fn vertex_created(&mut self, _handle: W::VHandle, _value: &VertexType) {}
+
+    /// Called after the removal, the remap describes the handles which changed.
+    #[inline(always)]
+    fn vertex_removed(&mut self, _remap: VertexRemap<W>) {}
+
+    #[inline(always)]
+    fn connected(&mut self, _from: W::VHandle, _to: W::VHandle, _weight: W::Weight) {}
+
+    #[inline(always)]
+    fn disconnected(&mut self, _from: W::VHandle, _to: W::VHandle) {}
+
+    #[inline(always)]
+    fn weight_changed(&mut self, _from: W::VHandle, _to: W::VHandle, _weight: W::Weight) {}
+
+    /// Called by [crate::views::tree::Tree::add_child] instead of [GraphObserver::connected].
+    #[inline(always)]
+    fn child_added(&mut self, _parent: W::VHandle, _child: W::VHandle) {}
+}
+
+/// Default observer of the graphs, compiles down to nothing.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct NoObserver;
+
+impl<VertexType, W: Width> GraphObserver<VertexType, W> for NoObserver {}
+
+/// Lets views borrow the observer of their graph.
+impl<VertexType, W: Width, Observer: GraphObserver<VertexType, W>> GraphObserver<VertexType, W> for &mut Observer {
+    #[inline(always)]
+    fn vertex_created(&mut self, handle: W::VHandle, value: &VertexType) {
+        (**self).vertex_created(handle, value);
+    }
+    #[inline(always)]
+    fn vertex_removed(&mut self, remap: VertexRemap<W>) {
+        (**self).vertex_removed(remap);
+    }
+    #[inline(always)]
+    fn connected(&mut self, from: W::VHandle, to: W::VHandle, weight: W::Weight) {
+        (**self).connected(from, to, weight);
+    }
+    #[inline(always)]
+    fn disconnected(&mut self, from: W::VHandle, to: W::VHandle) {
+        (**self).disconnected(from, to);
+    }
+    #[inline(always)]
+    fn weight_changed(&mut self, from: W::VHandle, to: W::VHandle, weight: W::Weight) {
+        (**self).weight_changed(from, to, weight);
+    }
+    #[inline(always)]
+    fn child_added(&mut self, parent: W::VHandle, child: W::VHandle) {
+        (**self).child_added(parent, child);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,346 @@
+//! Versioned little-endian snapshot of the edges, which can be memory mapped without copying.
+//!
+//! Layout:
+//! * header of [HEADER_LEN] bytes: magic, version, edge width in bits, vertex count, edge count
+//!   and the FNV-1a checksum of everything after the header
+//! * vertex entry table, `len`, `capacity` and `offset` of every vertex as [Ci]
+//! * zero padding up to the alignment of the edges
+//! * the edges, packed as in memory
+//!
+//! Blocks are written without reserve, so every vertex has exactly the capacity of its edges.
+//! A snapshot is mapped with the edge width it was written with, see [Width].
+
+use std::io;
+use std::io::Write;
+use std::mem::size_of;
+use crate::edge_storage::{EdgeStorage, VertexEntry};
+use crate::handles::types::Ci;
+use crate::handles::Width;
+use crate::traits::EdgeStore;
+
+pub const MAGIC: [u8; 8] = *b"ETAGRAPH";
+pub const VERSION: u32 = 1;
+pub const HEADER_LEN: usize = 48;
+
+pub(in crate) const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+#[inline(always)]
+pub(in crate) fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+#[inline(always)]
+fn edges_offset<W: Width>(vertex_count: usize) -> usize {
+    (HEADER_LEN + vertex_count * size_of::<VertexEntry>()).next_multiple_of(size_of::<W::Edge>())
+}
+
+impl<W: Width> EdgeStorage<(), W> {
+    /// Writes the snapshot of the storage, see the [module](crate::snapshot) docs for the layout.
+    pub fn write_snapshot<Writer: Write>(&self, writer: &mut Writer) -> io::Result<()> {
+        let vertex_count = self.vertices_len();
+        let edge_count: usize = (0..vertex_count).map(|vertex| self.edges_len(W::handle(vertex))).sum();
+
+        // The body is produced twice, once for the checksum in the header and once for the writer
+        let write_body = |sink: &mut dyn FnMut(&[u8]) -> io::Result<()>| -> io::Result<()> {
+            let mut offset: Ci = 0;
+            for vertex in (0..vertex_count).map(W::handle) {
+                let len = self.edges_len(vertex) as Ci;
+                for value in [len, len, offset] {
+                    sink(&value.to_le_bytes())?;
+                }
+                offset += len;
+            }
+            let padding = edges_offset::<W>(vertex_count) - HEADER_LEN - vertex_count * size_of::<VertexEntry>();
+            sink(&vec![0u8; padding])?;
+            for vertex in (0..vertex_count).map(W::handle) {
+                for edge in self.edges_iter(vertex) {
+                    sink(W::to_le_bytes(*edge).as_ref())?;
+                }
+            }
+            Ok(())
+        };
+
+        let mut checksum = FNV_OFFSET;
+        write_body(&mut |bytes| {
+            checksum = fnv1a(checksum, bytes);
+            Ok(())
+        })?;
+
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&W::BITS.to_le_bytes())?;
+        writer.write_all(&(vertex_count as u64).to_le_bytes())?;
+        writer.write_all(&(edge_count as u64).to_le_bytes())?;
+        writer.write_all(&checksum.to_le_bytes())?;
+        writer.write_all(&[0u8; HEADER_LEN - 40])?;
+        write_body(&mut |bytes| writer.write_all(bytes))
+    }
+}
+
+#[cfg(feature = "mmap")]
+pub use mapped::MappedEdgeStorage;
+
+#[cfg(feature = "mmap")]
+mod mapped {
+    use std::fs::File;
+    use std::io;
+    use std::marker::PhantomData;
+    use std::ops::{Index, IndexMut};
+    use std::path::Path;
+    use std::slice;
+    use eta_algorithms::data_structs::array::Array;
+    use eta_algorithms::data_structs::fat_ptr::{FatPtr, FatPtrMut};
+    use memmap2::{Mmap, MmapOptions};
+    use crate::csr_edge_storage::CsrEdgeStorage;
+    use crate::edge_storage::{EdgeStorage, GrowthPolicy, VertexEntry};
+    use crate::graph::Error;
+    use crate::handles::types::Ci;
+    use crate::handles::{Generations, Width, W64};
+    use crate::traits::{EdgeStore, WeightStore};
+    use super::{edges_offset, fnv1a, FNV_OFFSET, HEADER_LEN, MAGIC, VERSION};
+
+    /// Edge storage backed by a read-only memory mapped snapshot. Nothing is copied on load.
+    /// Vertices can't be added or removed and the edges can't be modified in place, the mutable accessors panic.
+    /// Mutate a copy made with [MappedEdgeStorage::to_edge_storage] instead.
+    /// `W` is the edge width the snapshot was written with.
+    pub struct MappedEdgeStorage<W: Width = W64> {
+        map: Mmap,
+        vertex_count: usize,
+        edge_count: usize,
+        edges_offset: usize,
+        width: PhantomData<W>,
+    }
+
+    fn invalid(message: &str) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, message)
+    }
+
+    impl MappedEdgeStorage {
+        /// Maps a snapshot written with the default width, see [MappedEdgeStorage::open_with_width].
+        #[inline(always)]
+        pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+            Self::open_with_width(path)
+        }
+    }
+
+    impl<W: Width> MappedEdgeStorage<W> {
+        /// Maps the snapshot. Checks the header, the edge width against `W`, the checksum,
+        /// that every block lies inside the edges without overlapping another one and that every edge
+        /// points at a vertex or `NONE`.
+        pub fn open_with_width<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+            if cfg!(target_endian = "big") {
+                return Err(io::Error::new(io::ErrorKind::Unsupported, "snapshots can only be mapped on little-endian targets"));
+            }
+            let file = File::open(path)?;
+            // Safety: changes of the file while mapped are the callers responsibility
+            let map = unsafe { MmapOptions::new().map(&file)? };
+            if map.len() < HEADER_LEN || map[0..8] != MAGIC {
+                return Err(invalid("not an edge storage snapshot"));
+            }
+            let read_u32 = |at: usize| u32::from_le_bytes(map[at..at + 4].try_into().unwrap());
+            let read_u64 = |at: usize| u64::from_le_bytes(map[at..at + 8].try_into().unwrap());
+            if read_u32(8) != VERSION {
+                return Err(invalid("unsupported snapshot version"));
+            }
+            if read_u32(12) != W::BITS {
+                return Err(invalid("snapshot edge width does not match the storage width"));
+            }
+            let (vertex_count, edge_count) = (read_u64(16), read_u64(24));
+            if vertex_count > W::index(W::NONE) as u64 || edge_count > Ci::MAX as u64 {
+                return Err(invalid("snapshot exceeds the handle width"));
+            }
+            let (vertex_count, edge_count) = (vertex_count as usize, edge_count as usize);
+            let edges_offset = edges_offset::<W>(vertex_count);
+            if map.len() != edges_offset + edge_count * size_of::<W::Edge>() {
+                return Err(invalid("snapshot length does not match the header"));
+            }
+            if fnv1a(FNV_OFFSET, &map[HEADER_LEN..]) != read_u64(32) {
+                return Err(invalid("snapshot checksum mismatch"));
+            }
+
+            let storage = MappedEdgeStorage {
+                map,
+                vertex_count,
+                edge_count,
+                edges_offset,
+                width: PhantomData,
+            };
+            if storage.entries().iter().any(|entry| entry.len > entry.capacity || entry.offset as usize + entry.capacity as usize > edge_count) {
+                return Err(invalid("snapshot edge block out of bounds"));
+            }
+            let mut blocks: Vec<&VertexEntry> = storage.entries().iter().collect();
+            blocks.sort_unstable_by_key(|entry| entry.offset);
+            if blocks.windows(2).any(|pair| pair[0].offset + pair[0].capacity > pair[1].offset) {
+                return Err(invalid("snapshot edge blocks overlap"));
+            }
+            if storage.edges().iter().any(|edge| W::index(W::vh(*edge)) >= vertex_count && W::vh(*edge) != W::NONE) {
+                return Err(invalid("snapshot edge points at a missing vertex"));
+            }
+            Ok(storage)
+        }
+
+        #[inline(always)]
+        fn entries(&self) -> &[VertexEntry] {
+            // Safety: the table starts at an offset aligned for Ci and its length was checked on open
+            unsafe { slice::from_raw_parts(self.map.as_ptr().add(HEADER_LEN) as *const VertexEntry, self.vertex_count) }
+        }
+
+        #[inline(always)]
+        fn edges(&self) -> &[W::Edge] {
+            unsafe { slice::from_raw_parts(self.map.as_ptr().add(self.edges_offset) as *const W::Edge, self.edge_count) }
+        }
+
+        /// Every mutable accessor goes through here, the mapping is read-only.
+        fn edges_mut(&mut self) -> &mut [W::Edge] {
+            panic!("Mapped edge storage is read-only, modify a copy made with to_edge_storage");
+        }
+
+        #[inline(always)]
+        fn range(&self, handle: W::VHandle) -> std::ops::Range<usize> {
+            let entry = self.entries()[W::index(handle)];
+            entry.offset as usize..(entry.offset + entry.len) as usize
+        }
+
+        /// Copies the edges into a mutable [EdgeStorage]. Every vertex gets exactly the capacity of its edges.
+        pub fn to_edge_storage(&self) -> EdgeStorage<(), W> {
+            let mut offsets = Vec::with_capacity(self.vertex_count + 1);
+            let mut edges = Array::new(self.entries().iter().map(|entry| entry.len as usize).sum());
+            let mut generations = Generations::new();
+            let mut offset = 0;
+            offsets.push(0);
+            for vertex in 0..self.vertex_count {
+                let block = self.edges_as_slice(W::handle(vertex));
+                edges.as_mut_slice()[offset..offset + block.len()].copy_from_slice(block);
+                offset += block.len();
+                offsets.push(offset as Ci);
+                generations.push(vertex);
+            }
+            CsrEdgeStorage {
+                offsets,
+                edges,
+                edge_data: vec![(); offset],
+                growth_policy: GrowthPolicy::Double,
+                sorted: false,
+                generations,
+                source: PhantomData,
+            }.thaw()
+        }
+    }
+
+    impl<W: Width> EdgeStore<W> for MappedEdgeStorage<W> {
+        fn create_vertex_entry(&mut self, _size: Ci) -> W::VHandle {
+            panic!("Mapped edge storage can't be modified");
+        }
+
+        #[inline(always)]
+        fn try_create_vertex_entry(&mut self, _size: Ci) -> Result<W::VHandle, Error> {
+            Err(Error::Frozen)
+        }
+
+        fn remove_vertex_entry(&mut self, _handle: W::VHandle) -> W::VHandle {
+            panic!("Mapped edge storage can't be modified");
+        }
+
+        #[inline(always)]
+        fn vertices_len(&self) -> usize {
+            self.vertex_count
+        }
+
+        #[inline(always)]
+        fn edges_as_slice(&self, handle: W::VHandle) -> &[W::Edge] {
+            &self.edges()[self.range(handle)]
+        }
+
+        #[inline(always)]
+        fn edges_as_mut_slice(&mut self, handle: W::VHandle) -> &mut [W::Edge] {
+            let range = self.range(handle);
+            &mut self.edges_mut()[range]
+        }
+
+        #[inline(always)]
+        fn edges_as_ptr(&self, handle: W::VHandle) -> FatPtr<W::Edge> {
+            let edges = self.edges_as_slice(handle).as_ptr_range();
+            FatPtr::new(edges.start, edges.end)
+        }
+
+        #[inline(always)]
+        fn edges_as_mut_ptr(&mut self, handle: W::VHandle) -> FatPtrMut<W::Edge> {
+            let edges = self.edges_as_mut_slice(handle).as_mut_ptr_range();
+            FatPtrMut::new(edges.start, edges.end)
+        }
+
+        #[inline(always)]
+        fn edges_is_empty(&self, handle: W::VHandle) -> bool {
+            self.edges_len(handle) == 0
+        }
+
+        #[inline(always)]
+        fn edges_len(&self, handle: W::VHandle) -> usize {
+            self.entries()[W::index(handle)].len as usize
+        }
+
+        #[inline(always)]
+        fn edges_capacity(&self, handle: W::VHandle) -> usize {
+            self.entries()[W::index(handle)].capacity as usize
+        }
+
+        #[inline(always)]
+        fn edges_index(&self, handle: W::VHandle) -> usize {
+            self.entries()[W::index(handle)].offset as usize
+        }
+
+        #[inline(always)]
+        fn iter(&self) -> impl Iterator<Item=&W::Edge> {
+            self.edges().iter()
+        }
+
+        #[inline(always)]
+        fn iter_mut(&mut self) -> impl Iterator<Item=&mut W::Edge> {
+            self.edges_mut().iter_mut()
+        }
+
+        #[inline(always)]
+        fn edges_iter(&self, handle: W::VHandle) -> impl Iterator<Item=&W::Edge> {
+            self.edges_as_slice(handle).iter()
+        }
+
+        #[inline(always)]
+        fn edges_iter_mut(&mut self, handle: W::VHandle) -> impl Iterator<Item=&mut W::Edge> {
+            self.edges_as_mut_slice(handle).iter_mut()
+        }
+
+        unsafe fn edges_iter_mut_unchecked(&mut self, handle: W::VHandle) -> impl Iterator<Item=&mut W::Edge> {
+            self.edges_iter_mut(handle)
+        }
+    }
+
+    impl<W: Width> Index<usize> for MappedEdgeStorage<W> {
+        type Output = W::Edge;
+        fn index(&self, index: usize) -> &Self::Output {
+            &self.edges()[index]
+        }
+    }
+
+    impl<W: Width> IndexMut<usize> for MappedEdgeStorage<W> {
+        fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+            &mut self.edges_mut()[index]
+        }
+    }
+
+    impl<W: Width> WeightStore<W> for MappedEdgeStorage<W> {
+        type WeightType = W::Weight;
+        #[inline(always)]
+        fn weight(&self, index: usize) -> W::Weight {
+            W::wgt(self[index])
+        }
+        #[inline(always)]
+        fn set_weight(&mut self, index: usize, weight: W::Weight) {
+            self[index] = W::set_wgt(self[index], weight);
+        }
+    }
+}
\ No newline at end of file
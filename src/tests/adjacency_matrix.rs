@@ -0,0 +1,135 @@
+use crate::adjacency_matrix::AdjacencyMatrix;
+use crate::algorithms::dfs_bfs::bfs;
+use crate::algorithms::dfs_bfs::ControlFlow::Resume;
+use crate::algorithms::dinic::DinicGraph;
+use crate::algorithms::path_finding::dijkstra;
+use crate::graph::{Error, Graph};
+use crate::handles::{vh, vh_pack, wgt, NONE};
+use crate::handles::types::VHandle;
+use crate::traits::{EdgeConnect, EdgeStore, StoreVertex, WeightStore, WeightedEdgeConnect};
+use crate::weighted_graph::WeightedGraph;
+
+fn assert_consistent(matrix: &AdjacencyMatrix) {
+    for from in 0..matrix.vertices_len() as VHandle {
+        for (position, edge) in matrix.edges_iter(from).enumerate() {
+            assert_eq!(matrix.find_edge(from, vh(*edge)), Some(matrix.edges_index(from) + position));
+        }
+        let present = (0..matrix.vertices_len() as VHandle).filter(|to| matrix.has_edge(from, *to)).count();
+        assert_eq!(present, matrix.edges_len(from));
+    }
+}
+
+#[test]
+pub fn adjacency_matrix_connect_test(){
+    let mut graph = WeightedGraph::with_adjacency_matrix(2);
+    let a = graph.graph.create("a", 0);
+    let b = graph.create_and_connect_weighted(a, "b", 3, 0);
+    // Grows past the initial rows
+    let vertices: Vec<_> = (0..10).map(|i| graph.create_and_connect_weighted(b, "x", i, 0)).collect();
+    let edges = &mut graph.graph.edge_storage;
+    assert_eq!(edges.vertices_len(), 12);
+    assert_eq!(edges.edge_weight(a, b), Some(3));
+    assert_eq!(edges.edges_len(b), 10);
+    assert_eq!(edges.edge_weight(b, vertices[7]), Some(7));
+    assert!(!edges.has_edge(vertices[7], b));
+
+    edges.connect_weighted(a, b, 9);
+    assert_eq!(edges.edges_len(a), 1);
+    assert_eq!(edges.edge_weight(a, b), Some(9));
+
+    assert!(edges.disconnect(b, vertices[2]));
+    assert!(!edges.has_edge(b, vertices[2]));
+    assert!(!edges.disconnect(b, vertices[2]));
+    assert_eq!(edges.edges_len(b), 9);
+    assert_eq!(edges.try_disconnect(b, vertices[2]), Err(Error::NoEdge { from: b, to: vertices[2] }));
+    assert_eq!(edges.try_connect(b, 40), Err(Error::InvalidHandle(40)));
+    assert_eq!(edges.try_connect(b, NONE), Err(Error::InvalidHandle(NONE)));
+    assert!(!edges.has_edge(b, 40));
+    edges.disconnect(b, 40);
+    assert_consistent(edges);
+
+    let mut weights: Vec<_> = edges.edges_iter(b).map(|edge| wgt(*edge)).collect();
+    weights.sort();
+    assert_eq!(weights, vec![0, 1, 3, 4, 5, 6, 7, 8, 9]);
+    assert_eq!(edges.iter().count(), 10);
+    assert_eq!(edges.iter_mut().count(), 10);
+}
+
+#[test]
+pub fn adjacency_matrix_remove_vertex_test(){
+    let mut graph = Graph::with_adjacency_matrix(0);
+    let a = graph.create("a", 0);
+    let b = graph.create("b", 0);
+    let c = graph.create("c", 0);
+    let d = graph.create("d", 0);
+    for (from, to) in [(a, b), (a, d), (b, c), (c, d), (d, a), (d, d), (d, b)] {
+        graph.edge_storage.connect(from, to);
+    }
+    let stale = graph.generational(b).unwrap();
+
+    let remap = graph.remove_vertex(b);
+    assert_eq!(remap.apply(d), b);
+    assert_eq!(graph.vertices.len(), 3);
+    assert_eq!(graph.vertices[b], "d");
+    let edges = &graph.edge_storage;
+    assert!(edges.has_edge(a, b));
+    assert!(edges.has_edge(b, a));
+    assert!(edges.has_edge(b, b));
+    assert!(edges.has_edge(c, b));
+    assert_eq!(edges.edges_len(a), 1);
+    assert_eq!(edges.edges_len(b), 2);
+    assert_eq!(edges.edges_len(c), 1);
+    assert_consistent(edges);
+    assert_eq!(graph.get(stale), Err(Error::StaleHandle));
+
+    assert_eq!(graph.edge_storage.remove_vertex_entry(c), NONE);
+    assert!(!graph.edge_storage.has_edge(a, c));
+    assert_consistent(&graph.edge_storage);
+}
+
+#[test]
+pub fn adjacency_matrix_algorithms_test(){
+    let mut graph = WeightedGraph::with_adjacency_matrix(4);
+    let s = graph.graph.create("s", 0);
+    let a = graph.create_and_connect_weighted(s, "a", 4, 0);
+    let b = graph.create_and_connect_weighted(s, "b", 1, 0);
+    let t = graph.create_and_connect_weighted(a, "t", 3, 0);
+    graph.graph.edge_storage.connect_weighted(b, t, 2);
+
+    let mut path = dijkstra(&mut graph.graph.edge_storage, s, t, 4).unwrap();
+    let mut order = Vec::new();
+    while let Some(vertex) = path.pop() {
+        order.push(vertex);
+    }
+    assert_eq!(order, vec![s, b, t]);
+
+    let mut visited = Vec::new();
+    bfs(&mut graph.graph.edge_storage, vh_pack(s), 4, |edge, _| {
+        visited.push(vh(*edge));
+        Resume
+    });
+    assert_eq!(visited, vec![s, a, b, t]);
+
+    let dinic = DinicGraph::from(&graph.graph.vertices, &graph.graph.edge_storage, s, t);
+    let flow = |from, to| dinic.edge_storage.weight(dinic.edge_storage.find_edge(from, to).unwrap());
+    assert_eq!(flow(s, a), 3);
+    assert_eq!(flow(s, b), 1);
+}
+
+#[test]
+#[should_panic]
+pub fn adjacency_matrix_connect_outside_stride_test(){
+    let mut matrix = AdjacencyMatrix::with_capacity(4);
+    let a = matrix.create_vertex_entry(0);
+    matrix.create_vertex_entry(0);
+    // Would land in the slots of the second row
+    matrix.connect(a, 5);
+}
+
+#[test]
+#[should_panic]
+pub fn adjacency_matrix_connect_placeholder_test(){
+    let mut matrix = AdjacencyMatrix::new();
+    let a = matrix.create_vertex_entry(0);
+    matrix.connect(a, NONE);
+}
\ No newline at end of file
@@ -1,4 +1,5 @@
-use crate::algorithms::path_finding::{dijkstra};
+use crate::algorithms::path_finding::{a_star, dijkstra};
+use crate::graph::Graph;
 use crate::traits::{StoreVertex, WeightedEdgeConnect};
 use crate::weighted_graph::WeightedGraph;
 
@@ -244,4 +245,70 @@ pub fn dijkstra_test_with_disconnected_node() {
     // Test 3: Path from F to any other node (should not exist)
     let result = dijkstra(&mut weighted_graph.graph.edge_storage, f, a, weighted_graph.graph.vertices.len());
     assert!(result.is_none(), "Path from F to A should not exist");
+}
+
+#[test]
+pub fn dijkstra_test_float_weights() {
+    let mut graph = Graph::<&str, _, _>::with_payload_weights(0);
+    let s = graph.create("s", 2);
+    let a = graph.create("a", 1);
+    let b = graph.create("b", 1);
+    let t = graph.create("t", 0);
+    graph.edge_storage.connect_with_data(s, a, 0, 0.5f64);
+    graph.edge_storage.connect_with_data(s, b, 0, 0.25);
+    graph.edge_storage.connect_with_data(a, t, 0, 0.5);
+    graph.edge_storage.connect_with_data(b, t, 0, 0.875);
+
+    let mut path = dijkstra(&mut graph.edge_storage, s, t, graph.vertices.len()).unwrap();
+    assert_eq!(path.pop(), Some(s));
+    assert_eq!(path.pop(), Some(a));
+    assert_eq!(path.pop(), Some(t));
+
+    // The heuristic penalises a, so b is explored first but the optimal path is kept
+    let mut path = a_star(&mut graph.edge_storage, s, t, graph.vertices.len(), |_, edge| {
+        if crate::handles::vh(edge) == a { 0.1 } else { 0.0 }
+    }).unwrap();
+    assert_eq!(path.pop(), Some(s));
+    assert_eq!(path.pop(), Some(a));
+}
+
+#[test]
+pub fn dijkstra_test_widths() {
+    use crate::edge_storage::EdgeStorage;
+    use crate::handles::{W16, W32};
+    use crate::traits::EdgeStore;
+    let mut small = EdgeStorage::<(), W16>::default();
+    let mut medium = EdgeStorage::<(), W32>::default();
+    for _ in 0..4 {
+        small.create_vertex_entry(2);
+        medium.create_vertex_entry(2);
+    }
+    for (from, to, weight) in [(0, 1, 1), (0, 2, 5), (1, 3, 9), (2, 3, 1)] {
+        small.connect_weighted(from, to, weight);
+        medium.connect_weighted(from as u16, to as u16, weight as i16 * 1000);
+    }
+
+    let mut path = dijkstra(&mut small, 0, 3, 4).unwrap();
+    assert_eq!((path.pop(), path.pop(), path.pop()), (Some(0u8), Some(2), Some(3)));
+    let mut path = dijkstra(&mut medium, 0, 3, 4).unwrap();
+    assert_eq!((path.pop(), path.pop(), path.pop()), (Some(0u16), Some(2), Some(3)));
+}
+
+#[test]
+pub fn dijkstra_test_packed_weights_with_payload() {
+    let mut graph = Graph::<&str, _, _>::with_edge_data(0);
+    let s = graph.create("s", 2);
+    let a = graph.create("a", 1);
+    let b = graph.create("b", 1);
+    let t = graph.create("t", 0);
+    graph.edge_storage.connect_with_data(s, a, 4, "highway".to_string());
+    graph.edge_storage.connect_with_data(s, b, 1, "road".to_string());
+    graph.edge_storage.connect_with_data(a, t, 1, "highway".to_string());
+    graph.edge_storage.connect_with_data(b, t, 2, "road".to_string());
+
+    // Payloads which are no weights fall back to the packed weights
+    let mut path = dijkstra(&mut graph.edge_storage, s, t, graph.vertices.len()).unwrap();
+    assert_eq!(path.pop(), Some(s));
+    assert_eq!(path.pop(), Some(b));
+    assert_eq!(path.pop(), Some(t));
 }
\ No newline at end of file
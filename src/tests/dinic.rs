@@ -5,8 +5,9 @@ use crate::algorithms::dfs_bfs::bfs;
 use crate::algorithms::dfs_bfs::ControlFlow::Resume;
 use crate::algorithms::dinic::{mark_levels, DinicGraph};
 use crate::handles::{vh, vh_pack, wgt};
-use crate::handles::types::{VHandle, Weight};
-use crate::traits::{EdgeStore, StoreVertex, WeightedEdgeConnect};
+use crate::handles::types::VHandle;
+use crate::traits::{EdgeStore, StoreVertex, WeightStore, WeightedEdgeConnect};
+use crate::graph::Graph;
 use crate::weighted_graph::WeightedGraph;
 
 #[test]
@@ -26,7 +27,7 @@ pub fn level_test(){
     weighted_graph.graph.edge_storage.connect_weighted(a_b_b, a_a_x, 10);
     weighted_graph.graph.edge_storage.connect_weighted(a_b_c, a_a_x, 10);
 
-    let mut flow_data = Array::new_with_default(weighted_graph.graph.vertices.len(), Weight::MAX);
+    let mut flow_data = Array::new_with_default(weighted_graph.graph.vertices.len(), usize::MAX);
     let mut queue = Queue::<VHandle>::new_pow2_sized(weighted_graph.graph.vertices.len());
     let mut edges_copy = weighted_graph.graph.edge_storage.clone();
     mark_levels(a, a_a_x, &mut edges_copy, &mut queue, &mut flow_data).expect("Sink not found");
@@ -178,4 +179,51 @@ fn dinic_test_advanced(){
         }
     }
     assert_eq!(snap.len(), 0);
+}
+
+#[test]
+pub fn dinic_test_u64_capacities(){
+    let mut graph = Graph::<&str, _, _>::with_payload_weights(0);
+    let s = graph.create("s", 2);
+    let a = graph.create("a", 1);
+    let b = graph.create("b", 1);
+    let t = graph.create("t", 0);
+    let large = u32::MAX as u64 * 4;
+    graph.edge_storage.connect_with_data(s, a, 0, large);
+    graph.edge_storage.connect_with_data(s, b, 0, 7u64);
+    graph.edge_storage.connect_with_data(a, t, 0, large / 2);
+    graph.edge_storage.connect_with_data(b, t, 0, 3);
+
+    let dinic_graph = DinicGraph::from(&graph.vertices, &graph.edge_storage, s, t);
+    let flow = |from: VHandle, to: VHandle| dinic_graph.edge_storage.weight(dinic_graph.edge_storage.find_edge(from, to).unwrap());
+    assert_eq!(flow(s, a), large / 2);
+    assert_eq!(flow(a, t), large / 2);
+    assert_eq!(flow(s, b), 3);
+    assert_eq!(flow(b, t), 3);
+}
+
+#[test]
+pub fn dinic_test_widths(){
+    use crate::edge_storage::EdgeStorage;
+    use crate::handles::{W16, W32};
+    use crate::vertex_storage::VertexStorage;
+    let mut vertices = VertexStorage::new();
+    let mut small = EdgeStorage::<(), W16>::default();
+    let mut medium = EdgeStorage::<(), W32>::default();
+    for name in ["s", "a", "b", "t"] {
+        vertices.push(name);
+        small.create_vertex_entry(2);
+        medium.create_vertex_entry(2);
+    }
+    for (from, to, capacity) in [(0, 1, 3), (0, 2, 2), (1, 3, 2), (2, 3, 3)] {
+        small.connect_weighted(from, to, capacity);
+        medium.connect_weighted(from as u16, to as u16, capacity as i16 * 1000);
+    }
+
+    let small_flow = DinicGraph::from(&vertices, &small, 0, 3);
+    let flow = |from: u8, to: u8| small_flow.edge_storage.weight(small_flow.edge_storage.find_edge(from, to).unwrap());
+    assert_eq!((flow(0, 1), flow(0, 2), flow(1, 3), flow(2, 3)), (2, 2, 2, 2));
+    let medium_flow = DinicGraph::from(&vertices, &medium, 0, 3);
+    let flow = |from: u16, to: u16| medium_flow.edge_storage.weight(medium_flow.edge_storage.find_edge(from, to).unwrap());
+    assert_eq!((flow(0, 1), flow(0, 2), flow(1, 3), flow(2, 3)), (2000, 2000, 2000, 2000));
 }
\ No newline at end of file
@@ -0,0 +1,176 @@
+use crate::algorithms::dinic::DinicGraph;
+use crate::algorithms::path_finding::dijkstra;
+use crate::formats::dot::{escape, Dot, DotParser};
+use crate::formats::ParseError;
+use crate::graph::Graph;
+use crate::handles::{vh, wgt, W16, W32};
+use crate::traits::{EdgeStore, StoreVertex, WeightedEdgeConnect};
+use crate::weighted_graph::WeightedGraph;
+
+#[test]
+pub fn dot_export_test(){
+    let mut graph = WeightedGraph::new();
+    let s = graph.graph.create("s", 2);
+    let a = graph.create_and_connect_weighted(s, "a", 4, 1);
+    let b = graph.create_and_connect_weighted(s, "b \"quoted\"", 1, 1);
+    let t = graph.create_and_connect_weighted(a, "t", 3, 0);
+    graph.graph.edge_storage.connect_weighted(b, t, 2);
+
+    let expected = "digraph {
+    0 [label=\"s\"];
+    1 [label=\"a\"];
+    2 [label=\"b \\\"quoted\\\"\"];
+    3 [label=\"t\", shape=\"doublecircle\"];
+    0 -> 1 [label=\"4\"];
+    0 -> 2 [label=\"1\"];
+    1 -> 3 [label=\"3\"];
+    2 -> 3 [label=\"2\", style=\"dashed\"];
+}
+";
+    let dot = graph.dot()
+        .vertex_attributes(|vertex, _, attributes| if vertex == t { attributes.set("shape", "doublecircle") })
+        .edge_attributes(|from, _, _, attributes| if from == b { attributes.set("style", "dashed") });
+    let mut output = Vec::new();
+    dot.write(&mut output).unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), expected);
+    assert_eq!(dot.to_string(), expected);
+    assert!(!graph.graph.dot().to_string().contains("label=\"4\""));
+}
+
+#[test]
+pub fn dot_highlight_test(){
+    let mut graph = WeightedGraph::new();
+    let s = graph.graph.create("s", 2);
+    let a = graph.create_and_connect_weighted(s, "a", 4, 1);
+    let b = graph.create_and_connect_weighted(s, "b", 1, 1);
+    let t = graph.create_and_connect_weighted(a, "t", 3, 0);
+    graph.graph.edge_storage.connect_weighted(b, t, 2);
+
+    let mut stack = dijkstra(&mut graph.graph.edge_storage, s, t, graph.graph.vertices.len()).unwrap();
+    let mut path = Vec::new();
+    while let Some(vertex) = stack.pop() {
+        path.push(vertex);
+    }
+    let output = graph.dot().highlight_path(path).to_string();
+    assert!(output.contains("0 -> 2 [label=\"1\", color=\"red\", penwidth=\"2\"];"));
+    assert!(output.contains("0 -> 1 [label=\"4\"];"));
+    assert!(output.contains("3 [label=\"t\", color=\"red\"];"));
+    assert!(output.contains("1 [label=\"a\"];"));
+
+    let dinic = DinicGraph::from(&graph.graph.vertices, &graph.graph.edge_storage, s, t);
+    let output = Dot::new(&graph.graph.vertices, &graph.graph.edge_storage).flow(&dinic.edge_storage).to_string();
+    assert!(output.contains("0 -> 1 [label=\"3/4\"];"));
+    assert!(output.contains("0 -> 2 [label=\"1/1\"];"));
+}
+
+#[test]
+pub fn dot_tree_test(){
+    let mut graph = Graph::new();
+    let mut tree = graph.tree_view();
+    let root = tree.create_node("root");
+    let child = tree.create_child(root, "child");
+    tree.create_child(child, "leaf");
+
+    let expected = "digraph {
+    0 [label=\"root\"];
+    1 [label=\"child\"];
+    2 [label=\"leaf\"];
+    0 -> 1;
+    1 -> 2;
+}
+";
+    assert_eq!(Dot::from_tree(&tree).to_string(), expected);
+}
+
+#[test]
+pub fn dot_import_test(){
+    let input = "// fixture
+strict digraph \"routes\" {
+    rankdir = LR; node [shape=box]
+    s -> a [weight=4, color=red];
+    s -> b [weight=1]
+    /* multi
+       line */
+    edge [weight=2]
+    a:east -> t:w [weight=3];
+    b -> \"t\";
+    \"long \" + \"name\" -> { a b }
+    c;
+}";
+    let parsed = DotParser::new().weight_attribute("weight").parse(input).unwrap();
+    assert!(parsed.directed);
+    let graph = &parsed.graph;
+    assert_eq!(graph.graph.vertices.len(), 6);
+    let handle = |name: &str| parsed.names[name];
+    assert_eq!(graph.graph.vertices[handle("long name")], "long name");
+    assert_eq!(graph.edge_weight(handle("s"), handle("a")), Some(4));
+    assert_eq!(graph.edge_weight(handle("s"), handle("b")), Some(1));
+    assert_eq!(graph.edge_weight(handle("a"), handle("t")), Some(3));
+    assert_eq!(graph.edge_weight(handle("b"), handle("t")), Some(2));
+    assert_eq!(graph.edge_weight(handle("long name"), handle("b")), Some(2));
+    assert_eq!(graph.graph.edge_storage.edges_len(handle("long name")), 2);
+    assert_eq!(graph.graph.edge_storage.edges_len(handle("c")), 0);
+
+    let undirected = DotParser::new().parse("graph { a -- b -- c; a -- a }").unwrap();
+    assert!(!undirected.directed);
+    let handle = |name: &str| undirected.names[name];
+    assert!(undirected.graph.has_edge(handle("b"), handle("a")));
+    assert!(undirected.graph.has_edge(handle("b"), handle("c")));
+    assert_eq!(undirected.graph.graph.edge_storage.edges_len(handle("a")), 2);
+    assert_eq!(undirected.graph.edge_weight(handle("a"), handle("b")), Some(0));
+}
+
+#[test]
+pub fn dot_import_roundtrip_test(){
+    let mut graph = WeightedGraph::new();
+    let s = graph.graph.create("s", 2);
+    let a = graph.create_and_connect_weighted(s, "a", 4, 1);
+    graph.create_and_connect_weighted(s, "b", 1, 0);
+    graph.graph.edge_storage.connect_weighted(a, a, 7);
+
+    let parsed = DotParser::new().weight_attribute("label").parse(&graph.dot().to_string()).unwrap();
+    assert_eq!(parsed.graph.graph.vertices.len(), 3);
+    for vertex in 0..3 {
+        let handle = parsed.names[&vertex.to_string()];
+        let edges: Vec<_> = parsed.graph.graph.edge_storage.edges_iter(handle)
+            .map(|edge| (parsed.graph.graph.vertices[vh(*edge)].clone(), wgt(*edge)))
+            .collect();
+        let expected: Vec<_> = graph.graph.edge_storage.edges_iter(vertex)
+            .map(|edge| (vh(*edge).to_string(), wgt(*edge)))
+            .collect();
+        assert_eq!(edges, expected);
+    }
+}
+
+#[test]
+pub fn dot_escape_roundtrip_test(){
+    for name in ["a\\b", "c\\", "\\\"d\"", "e\nf", "\\n"] {
+        let parsed = DotParser::new().parse(&format!("digraph {{ \"{}\" }}", escape(name))).unwrap();
+        assert!(parsed.names.contains_key(name), "{}", name);
+    }
+}
+
+#[test]
+pub fn dot_import_error_test(){
+    let error = |input: &str| DotParser::new().weight_attribute("weight").parse(input).err().unwrap();
+    assert_eq!(error("digraph {\n a -> b\n c -- d\n}"), ParseError::new(3, "`--` in a directed graph"));
+    assert_eq!(error("digraph {\n a -> b [weight=x]\n}"), ParseError::new(2, "`x` is not a valid weight"));
+    assert_eq!(error("digraph {\n a -> b [weight 1]\n}").line, 2);
+    assert_eq!(error("digraph {\n a -> \"b\n\n").line, 2);
+    assert_eq!(error("digraph {\n a -> b\n").message, "missing `}`");
+    assert_eq!(error("tree { }"), ParseError::new(1, "expected `graph` or `digraph`"));
+    assert_eq!(error("graph { }\n\n}").line, 3);
+    assert_eq!(error("graph {\n a -- @\n}").to_string(), "line 2: unexpected character `@`");
+}
+
+#[test]
+pub fn dot_parse_widths_test(){
+    let input = "digraph { a -> b [weight=-90]; b -> c [weight=120] }";
+    let parser = DotParser::new().weight_attribute("weight");
+    let small = parser.parse_with_width::<W16>(input).unwrap();
+    let medium = parser.parse_with_width::<W32>(input).unwrap();
+    assert_eq!(small.graph.edge_weight(small.names["a"], small.names["b"]), Some(-90));
+    assert_eq!(medium.graph.edge_weight(medium.names["b"], medium.names["c"]), Some(120));
+    assert_eq!(parser.parse_with_width::<W16>("digraph { a -> b [weight=300] }").err(), Some(ParseError::new(1, "`300` is not a valid weight")));
+    assert_eq!(parser.parse_with_width::<W32>("digraph { a -> b [weight=300] }").unwrap().graph.edge_weight(0, 1), Some(300));
+}
\ No newline at end of file
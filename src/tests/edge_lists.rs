@@ -0,0 +1,187 @@
+use std::io::Cursor;
+use crate::algorithms::dfs_bfs::bfs;
+use crate::algorithms::dfs_bfs::ControlFlow::Resume;
+use crate::algorithms::dinic::DinicGraph;
+use crate::algorithms::path_finding::dijkstra;
+use crate::formats::{dimacs, matrix_market, snap, ParseError, ReadError};
+use crate::handles::{vh, vh_pack, W16, W32};
+use crate::traits::{EdgeStore, StoreVertex, WeightStore};
+
+fn parse_error(result: Result<impl Sized, ReadError>) -> ParseError {
+    match result {
+        Err(ReadError::Parse(error)) => error,
+        Err(ReadError::Io(error)) => panic!("unexpected io error {}", error),
+        Ok(_) => panic!("expected a parse error"),
+    }
+}
+
+#[test]
+pub fn dimacs_shortest_path_test(){
+    let input = "c 9th DIMACS challenge style
+p sp 4 5
+a 1 2 4
+a 1 3 1
+a 2 4 3
+a 3 4 2
+c trailing comment
+a 3 2 1
+";
+    let mut graph = dimacs::read_shortest_path(Cursor::new(input)).unwrap();
+    assert_eq!(graph.graph.vertices.len(), 4);
+    assert_eq!(graph.graph.vertices[0], 1);
+    assert_eq!(graph.edge_weight(2, 1), Some(1));
+
+    let mut path = dijkstra(&mut graph.graph.edge_storage, 0, 3, 4).unwrap();
+    let mut order = Vec::new();
+    while let Some(vertex) = path.pop() {
+        order.push(vertex);
+    }
+    assert_eq!(order, vec![0, 2, 3]);
+
+    let mut output = Vec::new();
+    dimacs::write_shortest_path(&graph.graph.edge_storage, 4, &mut output).unwrap();
+    let written = String::from_utf8(output).unwrap();
+    assert!(written.starts_with("p sp 4 5\na 1 2 4\na 1 3 1\n"));
+    let reread = dimacs::read_shortest_path(Cursor::new(written)).unwrap();
+    for vertex in 0..4 {
+        assert_eq!(reread.graph.edge_storage.edges_as_slice(vertex), graph.graph.edge_storage.edges_as_slice(vertex));
+    }
+
+    assert_eq!(parse_error(dimacs::read_shortest_path(Cursor::new("p sp 2 1\na 1 3 1\n"))), ParseError::new(2, "vertex 3 is out of range"));
+    assert_eq!(parse_error(dimacs::read_shortest_path(Cursor::new("p sp 2 2\na 1 2 1\n"))), ParseError::new(2, "expected 2 arcs, found 1"));
+    assert_eq!(parse_error(dimacs::read_shortest_path(Cursor::new("a 1 2 1\n"))), ParseError::new(1, "expected the problem line"));
+    assert_eq!(parse_error(dimacs::read_shortest_path(Cursor::new("p max 2 0\n"))), ParseError::new(1, "expected a `sp` problem, found `max`"));
+    assert_eq!(parse_error(dimacs::read_shortest_path(Cursor::new("p sp 2 1\na 1 2 x\n"))), ParseError::new(2, "`x` is not a valid weight"));
+    assert_eq!(parse_error(dimacs::read_shortest_path(Cursor::new("p sp 3 18446744073709551615\n"))), ParseError::new(1, "edge count exceeds the edge index width"));
+    assert_eq!(parse_error(dimacs::read_shortest_path(Cursor::new("p sp 3 4294967295\na 1 2 1\n"))), ParseError::new(2, "expected 4294967295 arcs, found 1"));
+}
+
+#[test]
+pub fn dimacs_max_flow_test(){
+    let input = "p max 4 4
+n 1 s
+n 4 t
+a 1 2 3
+a 1 3 2
+a 2 4 2
+a 3 4 3
+";
+    let problem = dimacs::read_max_flow(Cursor::new(input)).unwrap();
+    assert_eq!((problem.source, problem.sink), (0, 3));
+    let graph = &problem.graph.graph;
+    let dinic = DinicGraph::from(&graph.vertices, &graph.edge_storage, problem.source, problem.sink);
+    let total: i64 = (dinic.edge_storage.edges_index(0)..dinic.edge_storage.edges_index(0) + dinic.edge_storage.edges_len(0))
+        .map(|index| dinic.edge_storage.weight(index) as i64)
+        .sum();
+    assert_eq!(total, 4);
+
+    let mut output = Vec::new();
+    dimacs::write_max_flow(&graph.edge_storage, graph.vertices.len(), problem.source, problem.sink, &mut output).unwrap();
+    assert!(String::from_utf8(output).unwrap().starts_with("p max 4 4\nn 1 s\nn 4 t\na 1 2 3\n"));
+
+    assert_eq!(parse_error(dimacs::read_max_flow(Cursor::new("p max 2 0\nn 1 s\n"))), ParseError::new(2, "missing the sink descriptor"));
+}
+
+#[test]
+pub fn snap_test(){
+    let input = "# Undirected graph: example
+# Nodes: 4 Edges: 3
+# FromNodeId\tToNodeId
+10\t20
+20\t30
+
+30\t10
+40\t40
+";
+    let mut parsed = snap::read(Cursor::new(input), false).unwrap();
+    let graph = &mut parsed.graph.graph;
+    assert_eq!(graph.vertices.len(), 4);
+    assert_eq!(graph.vertices[parsed.names[&30]], 30);
+    assert_eq!(graph.edge_storage.edges_len(parsed.names[&20]), 2);
+    assert_eq!(graph.edge_storage.edges_len(parsed.names[&40]), 1);
+
+    let mut visited = Vec::new();
+    bfs(&mut graph.edge_storage, vh_pack(parsed.names[&30]), 4, |edge, _| {
+        visited.push(vh(*edge));
+        Resume
+    });
+    assert_eq!(visited.len(), 3);
+
+    let mut output = Vec::new();
+    snap::write(&graph.edge_storage, 4, false, true, &mut output).unwrap();
+    let written = String::from_utf8(output).unwrap();
+    assert!(written.contains("# Nodes: 4 Edges: 4\n"));
+    assert!(written.contains("\n0\t1\t0\n"));
+    let reread = snap::read(Cursor::new(written), false).unwrap();
+    assert_eq!(reread.graph.graph.edge_storage.iter().count(), 7);
+
+    let directed = snap::read(Cursor::new("1 2 5\n2 1 -3\n"), true).unwrap();
+    assert_eq!(directed.graph.edge_weight(0, 1), Some(5));
+    assert_eq!(directed.graph.edge_weight(1, 0), Some(-3));
+    assert_eq!(parse_error(snap::read(Cursor::new("1 2\n3\n"), true)), ParseError::new(2, "missing vertex id"));
+}
+
+#[test]
+pub fn matrix_market_test(){
+    let input = "%%MatrixMarket matrix coordinate real symmetric
+% comment
+3 3 3
+1 1 5.0
+2 1 2
+3 2 7e0
+";
+    let graph = matrix_market::read(Cursor::new(input)).unwrap();
+    assert_eq!(graph.graph.vertices.len(), 3);
+    assert_eq!(graph.edge_weight(0, 0), Some(5));
+    assert_eq!(graph.edge_weight(1, 0), Some(2));
+    assert_eq!(graph.edge_weight(0, 1), Some(2));
+    assert_eq!(graph.edge_weight(1, 2), Some(7));
+    assert_eq!(graph.graph.edge_storage.edges_len(0), 2);
+
+    let mut output = Vec::new();
+    matrix_market::write(&graph.graph.edge_storage, 3, &mut output).unwrap();
+    let written = String::from_utf8(output).unwrap();
+    assert!(written.starts_with("%%MatrixMarket matrix coordinate integer general\n3 3 5\n"));
+    let reread = matrix_market::read(Cursor::new(written)).unwrap();
+    for vertex in 0..3 {
+        assert_eq!(reread.graph.edge_storage.edges_as_slice(vertex), graph.graph.edge_storage.edges_as_slice(vertex));
+    }
+
+    let skew = matrix_market::read(Cursor::new("%%MatrixMarket matrix coordinate integer skew-symmetric\n2 2 1\n2 1 4\n")).unwrap();
+    assert_eq!(skew.edge_weight(0, 1), Some(-4));
+    let pattern = matrix_market::read(Cursor::new("%%MatrixMarket matrix coordinate pattern general\n2 3 1\n1 3\n")).unwrap();
+    assert_eq!(pattern.graph.vertices.len(), 3);
+    assert_eq!(pattern.edge_weight(0, 2), Some(0));
+
+    let error = |input: &str| parse_error(matrix_market::read(Cursor::new(input.to_string())));
+    assert_eq!(error("%%MatrixMarket matrix array real general\n"), ParseError::new(1, "only coordinate matrices are supported"));
+    assert_eq!(error("%%MatrixMarket matrix coordinate complex general\n"), ParseError::new(1, "unsupported field `complex`"));
+    assert_eq!(error("%%MatrixMarket matrix coordinate real general\n2 2 1\n1 2 0.5\n"), ParseError::new(3, "`0.5` is not a valid weight"));
+    assert_eq!(error("%%MatrixMarket matrix coordinate real general\n2 2 2\n1 2 1\n"), ParseError::new(3, "expected 2 entries, found 1"));
+    assert_eq!(error("%%MatrixMarket matrix coordinate real general\n2 2 1\n1 3 1\n"), ParseError::new(3, "index 3 is out of range"));
+    assert_eq!(error("%%MatrixMarket matrix coordinate real symmetric\n2 2 18446744073709551615\n"), ParseError::new(2, "edge count exceeds the edge index width"));
+    assert_eq!(error("%%MatrixMarket matrix coordinate real symmetric\n2 2 4294967295\n2 1 1\n"), ParseError::new(3, "expected 4294967295 entries, found 1"));
+}
+
+#[test]
+pub fn edge_lists_widths_test(){
+    let input = "p sp 3 2\na 1 2 -100\na 2 3 100\n";
+    let mut small = dimacs::read_shortest_path_with_width::<_, W16>(Cursor::new(input)).unwrap();
+    let medium = dimacs::read_shortest_path_with_width::<_, W32>(Cursor::new(input)).unwrap();
+    assert_eq!(small.edge_weight(0, 1), Some(-100));
+    assert_eq!(medium.edge_weight(1, 2), Some(100));
+    let mut path = dijkstra(&mut small.graph.edge_storage, 1, 2, 3).unwrap();
+    assert_eq!(path.pop(), Some(1));
+
+    let wide = "p sp 2 1\na 1 2 200\n";
+    assert_eq!(parse_error(dimacs::read_shortest_path_with_width::<_, W16>(Cursor::new(wide))), ParseError::new(2, "`200` is not a valid weight"));
+    assert_eq!(dimacs::read_shortest_path_with_width::<_, W32>(Cursor::new(wide)).unwrap().edge_weight(0, 1), Some(200));
+
+    let small = snap::read_with_width::<_, W16>(Cursor::new("7 9 -1\n"), false).unwrap();
+    let medium = matrix_market::read_with_width::<_, W32>(Cursor::new("%%MatrixMarket matrix coordinate integer skew-symmetric\n2 2 1\n2 1 -32767\n")).unwrap();
+    assert_eq!((small.graph.edge_weight(0, 1), small.graph.edge_weight(1, 0)), (Some(-1), Some(-1)));
+    assert_eq!(small.names[&9], 1);
+    assert_eq!((medium.edge_weight(1, 0), medium.edge_weight(0, 1)), (Some(-32767), Some(i16::MAX)));
+    let negated = "%%MatrixMarket matrix coordinate integer skew-symmetric\n2 2 1\n2 1 -32768\n";
+    assert_eq!(parse_error(matrix_market::read_with_width::<_, W32>(Cursor::new(negated))), ParseError::new(3, "negated value does not fit into the weight"));
+}
\ No newline at end of file
@@ -1,8 +1,10 @@
 use std::collections::HashMap;
-use crate::edge_storage::EdgeStorage;
+use std::mem::size_of;
+use crate::edge_storage::{EdgeStorage, GrowthPolicy, PayloadWeighted};
+use crate::graph::Error;
 use crate::handles::{vh, pack};
-use crate::handles::types::{VHandle};
-use crate::traits::{EdgeStore, EdgeConnect, WeightedEdgeConnect};
+use crate::handles::types::{Ci, Edge, VHandle, Weight};
+use crate::traits::{EdgeStore, EdgeConnect, WeightStore, WeightedEdgeConnect};
 
 #[test]
 fn edge_storage_create_test(){
@@ -132,9 +134,10 @@ fn edge_storage_disconnect_test() {
     assert_eq!(edge_storage.edges_len(b), 3);
     assert_eq!(edge_storage.edges_len(c), 3);
 
-    edge_storage.disconnect(a, 1);
-    edge_storage.disconnect(b, 2);
-    edge_storage.disconnect(c, 3);
+    assert!(edge_storage.disconnect(a, 1));
+    assert!(edge_storage.disconnect(b, 2));
+    assert!(edge_storage.disconnect(c, 3));
+    assert!(!edge_storage.disconnect(c, 3));
 
     assert_eq!(edge_storage.edges_len(a), 2);
     assert_eq!(edge_storage.edges_len(b), 2);
@@ -300,4 +303,326 @@ fn invalid_handle_test_vertex_as_ptr(){
     let mut edge_storage = EdgeStorage::new();
     edge_storage.create_vertex_entry(3);
     let _ = edge_storage.edges_as_ptr(5);
+}
+#[test]
+fn edge_storage_remove_reuses_block_test(){
+    let mut edge_storage = EdgeStorage::new();
+    let a = edge_storage.create_vertex_entry(4);
+    let b = edge_storage.create_vertex_entry(2);
+    edge_storage.connect(a, b);
+    assert_eq!(edge_storage.edges.capacity(), 6);
+
+    assert_eq!(edge_storage.remove_vertex_entry(a), b);
+    assert_eq!(edge_storage.edges_len(0), 0);
+
+    let c = edge_storage.create_vertex_entry(3);
+    assert_eq!(edge_storage.edges.capacity(), 6);
+    assert_eq!(edge_storage.edges_index(c), 0);
+    assert_eq!(edge_storage.edges_capacity(c), 3);
+
+    let d = edge_storage.create_vertex_entry(1);
+    assert_eq!(edge_storage.edges.capacity(), 6);
+    assert_eq!(edge_storage.edges_index(d), 3);
+}
+
+#[test]
+fn edge_storage_growth_reuses_hole_test(){
+    let mut edge_storage = EdgeStorage::new();
+    let a = edge_storage.create_vertex_entry(1);
+    let b = edge_storage.create_vertex_entry(1);
+    edge_storage.connect(a, b);
+    edge_storage.connect(a, a);
+    assert_eq!(edge_storage.edges_capacity(a), 2);
+    assert_eq!(edge_storage.edges_index(a), 2);
+
+    // The block released by `a` holds a single edge, so `b` must move past it when growing
+    edge_storage.connect(b, a);
+    edge_storage.connect(b, b);
+    assert_eq!(edge_storage.edges_index(b), 4);
+    assert_eq!(edge_storage.edges.capacity(), 6);
+
+    let c = edge_storage.create_vertex_entry(2);
+    assert_eq!(edge_storage.edges_capacity(c), 2);
+    assert_eq!(edge_storage.edges.capacity(), 6);
+    assert_eq!(edge_storage.edges_as_slice(a), &[pack(b, 0), pack(a, 0)]);
+    assert_eq!(edge_storage.edges_as_slice(b), &[pack(a, 0), pack(b, 0)]);
+}
+
+#[test]
+#[should_panic]
+fn edge_storage_strict_overflow_test(){
+    let mut edge_storage = EdgeStorage::new();
+    edge_storage.set_growth_policy(GrowthPolicy::Strict);
+    let a = edge_storage.create_vertex_entry(1);
+    edge_storage.connect_edges(a, &[1, 2]);
+}
+
+#[test]
+fn edge_storage_shrink_to_fit_test(){
+    let mut edge_storage = EdgeStorage::with_reserve(10);
+    let a = edge_storage.create_vertex_entry(0);
+    let b = edge_storage.create_vertex_entry(0);
+    let c = edge_storage.create_vertex_entry(0);
+    edge_storage.connect_edges(a, &[1, 2, 3]);
+    edge_storage.connect_edges(c, &[7, 8]);
+    assert_eq!(edge_storage.edges.capacity(), 30);
+
+    let reclaimed = edge_storage.shrink_to_fit();
+    assert!(reclaimed >= 25 * size_of::<Edge>());
+    assert_eq!(edge_storage.edges.capacity(), 5);
+    assert_eq!(edge_storage.edges_capacity(a), 3);
+    assert_eq!(edge_storage.edges_capacity(b), 0);
+    assert_eq!(edge_storage.edges_index(c), 3);
+    assert_eq!(edge_storage.edges_as_slice(a), &[1, 2, 3]);
+    assert_eq!(edge_storage.edges_as_slice(c), &[7, 8]);
+
+    edge_storage.connect(b, a);
+    assert_eq!(edge_storage.edges_as_slice(b), &[pack(a, 0)]);
+    assert_eq!(edge_storage.edges_as_slice(c), &[7, 8]);
+}
+
+#[test]
+fn edge_storage_compact_test(){
+    let mut edge_storage = EdgeStorage::new();
+    let a = edge_storage.create_vertex_entry(4);
+    let b = edge_storage.create_vertex_entry(2);
+    edge_storage.connect_edges(b, &[5, 6]);
+    edge_storage.remove_vertex_entry(a);
+    assert_eq!(edge_storage.edges.capacity(), 6);
+
+    let reclaimed = edge_storage.compact();
+    assert!(reclaimed >= 4 * size_of::<Edge>());
+    assert_eq!(edge_storage.edges.capacity(), 2);
+    assert_eq!(edge_storage.edges_index(0), 0);
+    assert_eq!(edge_storage.edges_capacity(0), 2);
+    assert_eq!(edge_storage.edges_as_slice(0), &[5, 6]);
+    assert_eq!(edge_storage.compact(), 0);
+}
+
+#[test]
+fn edge_storage_try_create_test(){
+    let mut edge_storage = EdgeStorage::with_reserve(Ci::MAX);
+    assert_eq!(edge_storage.try_create_vertex_entry(1), Err(Error::HandleSpaceExhausted));
+    assert_eq!(edge_storage.edges.capacity(), 0);
+    assert_eq!(edge_storage.try_connect(0, 0), Err(Error::InvalidHandle(0)));
+}
+
+#[test]
+fn edge_storage_transposed_test(){
+    let mut edge_storage = EdgeStorage::new();
+    let a = edge_storage.create_vertex_entry(2);
+    let b = edge_storage.create_vertex_entry(1);
+    let c = edge_storage.create_vertex_entry(0);
+    edge_storage.connect_weighted(a, b, 3);
+    edge_storage.connect_weighted(a, c, 4);
+    edge_storage.connect_weighted(b, c, 5);
+
+    let transposed = edge_storage.transposed();
+    assert_eq!(transposed.edges_len(a), 0);
+    assert_eq!(transposed.edges_as_slice(b), &[pack(a, 3)]);
+    assert_eq!(transposed.edges_as_slice(c), &[pack(a, 4), pack(b, 5)]);
+    assert_eq!(transposed.edges_capacity(c), 2);
+}
+
+#[test]
+fn edge_storage_in_edges_test(){
+    let mut edge_storage = EdgeStorage::new();
+    let a = edge_storage.create_vertex_entry(1);
+    let b = edge_storage.create_vertex_entry(1);
+    edge_storage.connect_weighted(a, b, 7);
+    edge_storage.enable_in_edges();
+    assert_eq!(edge_storage.in_edges_iter(b).copied().collect::<Vec<_>>(), vec![pack(a, 7)]);
+
+    let c = edge_storage.create_vertex_entry(2);
+    edge_storage.connect_weighted(c, b, 2);
+    edge_storage.connect(c, a);
+    edge_storage.connect_weighted(b, c, 1);
+    assert_eq!(edge_storage.in_edges_iter(b).copied().collect::<Vec<_>>(), vec![pack(a, 7), pack(c, 2)]);
+    assert_eq!(edge_storage.in_edges_iter(a).copied().collect::<Vec<_>>(), vec![pack(c, 0)]);
+
+    edge_storage.disconnect(a, b);
+    assert_eq!(edge_storage.in_edges_iter(b).copied().collect::<Vec<_>>(), vec![pack(c, 2)]);
+
+    // `c` takes over the handle of `a`
+    edge_storage.remove_vertex_entry(a);
+    let c = a;
+    assert_eq!(edge_storage.in_edges_iter(b).copied().collect::<Vec<_>>(), vec![pack(c, 2)]);
+    assert_eq!(edge_storage.in_edges_iter(c).copied().collect::<Vec<_>>(), vec![pack(b, 1)]);
+    assert_eq!(edge_storage.in_edges_len(b), 1);
+}
+
+#[test]
+fn edge_storage_edge_lookup_test(){
+    let mut edge_storage = EdgeStorage::new();
+    let a = edge_storage.create_vertex_entry(2);
+    let b = edge_storage.create_vertex_entry(2);
+    let c = edge_storage.create_vertex_entry(0);
+    edge_storage.connect_weighted(a, c, 4);
+    edge_storage.connect_weighted(a, b, 3);
+    edge_storage.enable_in_edges();
+
+    assert!(edge_storage.has_edge(a, b));
+    assert!(!edge_storage.has_edge(b, a));
+    assert_eq!(edge_storage.find_edge(a, b), Some(edge_storage.edges_index(a) + 1));
+    assert_eq!(edge_storage.edge_weight(a, c), Some(4));
+    assert_eq!(edge_storage.edge_weight(c, a), None);
+
+    assert_eq!(edge_storage.set_edge_weight(a, c, 9), Ok(()));
+    assert_eq!(edge_storage.edge_weight(a, c), Some(9));
+    assert_eq!(edge_storage.in_edges_iter(c).copied().collect::<Vec<_>>(), vec![pack(a, 9)]);
+    assert_eq!(edge_storage.set_edge_weight(b, c, 1), Err(Error::NoEdge{from: b, to: c}));
+}
+
+#[test]
+fn edge_storage_sorted_adjacency_test(){
+    let mut edge_storage = EdgeStorage::new();
+    for _ in 0..10 {
+        edge_storage.create_vertex_entry(0);
+    }
+    edge_storage.connect_edges(0, &[pack(7, 1), pack(2, 1)]);
+    edge_storage.set_sorted_adjacency(true);
+    assert_eq!(edge_storage.edges_as_slice(0), &[pack(2, 1), pack(7, 1)]);
+
+    edge_storage.connect_edges(0, &[pack(9, 0), pack(1, 0), pack(5, 0)]);
+    edge_storage.connect_weighted(0, 5, 2);
+    assert_eq!(edge_storage.edges_as_slice(0), &[pack(1, 0), pack(2, 1), pack(5, 0), pack(5, 2), pack(7, 1), pack(9, 0)]);
+    assert_eq!(edge_storage.find_edge(0, 5), Some(edge_storage.edges_index(0) + 2));
+    assert_eq!(edge_storage.find_edge(0, 6), None);
+    assert_eq!(edge_storage.find_edge(0, 10), None);
+
+    edge_storage.disconnect(0, 2);
+    assert_eq!(edge_storage.edges_as_slice(0), &[pack(1, 0), pack(5, 0), pack(5, 2), pack(7, 1), pack(9, 0)]);
+
+    // Vertex 9 takes over the handle of vertex 1
+    edge_storage.remove_vertex_entry(1);
+    assert_eq!(edge_storage.edges_as_slice(0), &[pack(1, 0), pack(5, 0), pack(5, 2), pack(7, 1)]);
+    assert_eq!(edge_storage.edge_weight(0, 7), Some(1));
+}
+
+#[test]
+fn edge_storage_edge_data_test(){
+    let mut edge_storage = EdgeStorage::<String>::with_edge_data(0);
+    let a = edge_storage.create_vertex_entry(1);
+    let b = edge_storage.create_vertex_entry(0);
+    let c = edge_storage.create_vertex_entry(0);
+    let d = edge_storage.create_vertex_entry(0);
+    edge_storage.connect_with_data(a, b, 1, "ab".to_string());
+    // Grows the block of a
+    edge_storage.connect_with_data(a, c, 2, "ac".to_string());
+    edge_storage.connect_with_data(a, d, 3, "ad".to_string());
+    edge_storage.connect(a, a);
+
+    let collect = |edge_storage: &EdgeStorage<String>| edge_storage.edges_with_data_iter(a)
+        .map(|(edge, data)| (vh(*edge), data.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(collect(&edge_storage), vec![(b, "ab".to_string()), (c, "ac".to_string()), (d, "ad".to_string()), (a, String::new())]);
+
+    edge_storage.disconnect(a, b);
+    assert_eq!(collect(&edge_storage), vec![(a, String::new()), (c, "ac".to_string()), (d, "ad".to_string())]);
+
+    // d takes over the handle of c
+    edge_storage.remove_vertex_entry(c);
+    assert_eq!(collect(&edge_storage), vec![(a, String::new()), (c, "ad".to_string())]);
+
+    let index = edge_storage.edges_index(a);
+    *edge_storage.edge_data_mut(index) = "aa".to_string();
+    edge_storage.shrink_to_fit();
+    assert_eq!(edge_storage.edge_data_as_slice(a), &["aa".to_string(), "ad".to_string()]);
+}
+
+#[test]
+fn edge_storage_sorted_edge_data_test(){
+    let mut edge_storage = EdgeStorage::<u32>::with_edge_data(0);
+    for _ in 0..4 {
+        edge_storage.create_vertex_entry(0);
+    }
+    edge_storage.set_sorted_adjacency(true);
+    edge_storage.connect_with_data(0, 3, 0, 30);
+    edge_storage.connect_with_data(0, 1, 0, 10);
+    edge_storage.connect_with_data(0, 2, 0, 20);
+    assert_eq!(edge_storage.edge_data_as_slice(0), &[10, 20, 30]);
+
+    edge_storage.disconnect(0, 1);
+    assert_eq!(edge_storage.edge_data_as_slice(0), &[20, 30]);
+
+    let transposed = edge_storage.transposed();
+    assert_eq!(transposed.edges_with_data_iter(3).map(|(edge, data)| (vh(*edge), *data)).collect::<Vec<_>>(), vec![(0, 30)]);
+}
+
+
+#[test]
+fn edge_storage_edges_test(){
+    let mut edge_storage = EdgeStorage::new();
+    assert_eq!(edge_storage.iter().count(), 0);
+    assert_eq!(edge_storage.iter_mut().count(), 0);
+    assert_eq!(edge_storage.edges().count(), 0);
+    assert_eq!(edge_storage.edges_mut().count(), 0);
+
+    let a = edge_storage.create_vertex_entry(0);
+    let b = edge_storage.create_vertex_entry(2);
+    let c = edge_storage.create_vertex_entry(3);
+    let d = edge_storage.create_vertex_entry(0);
+    assert_eq!(edge_storage.edges().count(), 0);
+    edge_storage.connect_weighted(b, a, 1);
+    edge_storage.connect_weighted(b, d, 2);
+    edge_storage.connect_weighted(c, b, 3);
+    assert_eq!(edge_storage.edges().collect::<Vec<_>>(), vec![(b, a, 1), (b, d, 2), (c, b, 3)]);
+
+    for (from, edge) in edge_storage.edges_mut() {
+        *edge = pack(vh(*edge), from as Weight);
+    }
+    assert_eq!(edge_storage.edges().collect::<Vec<_>>(), vec![(b, a, b as Weight), (b, d, b as Weight), (c, b, c as Weight)]);
+
+    let mut csr = EdgeStorage::new();
+    csr.create_vertex_entry(0);
+    let csr = csr.freeze();
+    assert_eq!(csr.edges().count(), 0);
+    let frozen = edge_storage.freeze();
+    assert_eq!(frozen.edges().map(|(from, to, _)| (from, to)).collect::<Vec<_>>(), vec![(b, a), (b, d), (c, b)]);
+
+    // The weight comes from the payload, not from the packed edge
+    let mut weighted = PayloadWeighted::<u64>::with_edge_data(0);
+    let x = weighted.create_vertex_entry(1);
+    weighted.connect_with_data(x, x, 1, 700);
+    assert_eq!(weighted.edges().collect::<Vec<_>>(), vec![(x, x, 700)]);
+    let mut counted = EdgeStorage::<u64>::with_edge_data(0);
+    let x = counted.create_vertex_entry(1);
+    counted.connect_with_data(x, x, 1, 700);
+    assert_eq!(counted.edges().collect::<Vec<_>>(), vec![(x, x, 1)]);
+}
+
+#[test]
+fn edge_storage_widths_test() {
+    use crate::handles::{Width, W16, W32};
+    let mut small = EdgeStorage::<(), W16>::default();
+    let mut medium = EdgeStorage::<(), W32>::default();
+    let mut large = EdgeStorage::new();
+    assert_eq!((size_of::<<W16 as Width>::Edge>(), size_of::<<W32 as Width>::Edge>(), size_of::<Edge>()), (2, 4, 8));
+
+    let (a, b) = (small.create_vertex_entry(1), small.create_vertex_entry(0));
+    small.connect_weighted(a, b, -3);
+    assert_eq!(small.edge_weight(a, b), Some(-3i8));
+    assert!(matches!(small.try_connect_weighted(a, b, 200), Err(Error::WeightOutOfRange)));
+    assert!(matches!(small.try_connect(a, 7), Err(Error::InvalidHandle(7))));
+
+    let (c, d) = (medium.create_vertex_entry(1), medium.create_vertex_entry(0));
+    medium.connect_weighted(c, d, 1000);
+    assert_eq!(medium.edges().collect::<Vec<_>>(), vec![(c, d, 1000i16)]);
+
+    let (e, f) = (large.create_vertex_entry(1), large.create_vertex_entry(0));
+    large.connect_weighted(e, f, 100_000);
+    assert_eq!(large.edge_weight(e, f), Some(100_000));
+
+    // Removing a vertex retargets the moved one with the handles of the width
+    let g = small.create_vertex_entry(1);
+    small.connect(g, b);
+    assert_eq!(small.remove_vertex_entry(a), g);
+    assert!(small.has_edge(a, b));
+
+    // The handle space ends at NONE, the number of edges is only bounded by the offsets
+    for _ in small.vertices_len()..W16::index(W16::NONE) {
+        small.try_create_vertex_entry(300).unwrap();
+    }
+    assert!(matches!(small.try_create_vertex_entry(0), Err(Error::HandleSpaceExhausted)));
+    assert!(small.edges.capacity() > u8::MAX as usize);
 }
\ No newline at end of file
@@ -0,0 +1,122 @@
+use eta_algorithms::data_structs::array::Array;
+use eta_algorithms::data_structs::queue::Queue;
+use crate::algorithms::dfs_bfs::{bfs, dfs};
+use crate::algorithms::dfs_bfs::ControlFlow::Resume;
+use crate::algorithms::dinic::mark_levels;
+use crate::algorithms::path_finding::dijkstra;
+use crate::graph::Error;
+use crate::handles::{pack, set_wgt, vh, vh_pack, wgt};
+use crate::handles::types::VHandle;
+use crate::traits::{EdgeStore, StoreVertex, WeightStore, WeightedEdgeConnect};
+use crate::tests::utils::collect_path;
+use crate::weighted_graph::WeightedGraph;
+
+#[derive(Debug, PartialEq)]
+enum Kind {
+    Town,
+    Depot,
+}
+
+#[test]
+pub fn filtered_view_path_finding_test(){
+    let mut graph = WeightedGraph::new();
+    let s = graph.graph.create((Kind::Town, "s"), 3);
+    let a = graph.create_and_connect_weighted(s, (Kind::Town, "a"), 1, 1);
+    let depot = graph.create_and_connect_weighted(s, (Kind::Depot, "depot"), 1, 1);
+    let b = graph.create_and_connect_weighted(s, (Kind::Town, "b"), 4, 1);
+    let t = graph.create_and_connect_weighted(a, (Kind::Town, "t"), 1, 0);
+    graph.graph.edge_storage.connect_weighted(depot, t, 2);
+    graph.graph.edge_storage.connect_weighted(b, t, 4);
+    let before: Vec<_> = graph.graph.edge_storage.iter().copied().collect();
+
+    let closed = graph.graph.edge_storage.find_edge(a, t).unwrap();
+    let vertices_len = graph.graph.vertices.len();
+    let kinds: Vec<_> = graph.graph.vertices.iter().map(|(kind, _)| *kind == Kind::Town).collect();
+    let mut view = graph.graph.filtered_view(|handle| kinds[handle as usize], |_, index, _| index != closed);
+
+    assert_eq!(view.edges_len(s), 2);
+    assert!(view.edges_is_empty(a));
+    assert!(!view.has_edge(s, depot));
+    assert_eq!(view.edge_weight(s, b), Some(4));
+    assert_eq!(view.try_create_vertex_entry(0), Err(Error::Frozen));
+
+    assert_eq!(collect_path(dijkstra(&mut view, s, t, vertices_len).unwrap()), vec![s, b, t]);
+
+    let mut reached = Vec::new();
+    bfs(&mut view, vh_pack(s), vertices_len, |edge, _| {
+        reached.push(vh(*edge));
+        Resume
+    });
+    assert_eq!(reached, vec![s, a, b, t]);
+
+    let mut reached = Vec::new();
+    dfs(&mut view, vh_pack(s), vertices_len, |edge| {
+        reached.push(vh(*edge));
+        Resume
+    }, |_| {});
+    reached.sort();
+    assert_eq!(reached, vec![s, a, b, t]);
+
+    assert_eq!(graph.graph.edge_storage.iter().copied().collect::<Vec<_>>(), before);
+    assert_eq!(collect_path(dijkstra(&mut graph.graph.edge_storage, s, t, vertices_len).unwrap()), vec![s, a, t]);
+}
+
+#[test]
+pub fn filtered_view_write_through_test(){
+    let mut graph = WeightedGraph::new();
+    let a = graph.graph.create("a", 2);
+    let b = graph.create_and_connect_weighted(a, "b", 1, 0);
+    let c = graph.create_and_connect_weighted(a, "c", 2, 0);
+
+    let mut view = graph.graph.filtered_view(|_| true, |_, _, edge| wgt(edge) > 1);
+    let index = view.find_edge(a, c).unwrap();
+    view.set_weight(index, 5);
+    assert_eq!(view.edge_weight(a, c), Some(5));
+    dfs(&mut view, vh_pack(a), 3, |edge| {
+        *edge = set_wgt(*edge, 7);
+        Resume
+    }, |_| {});
+    assert_eq!(view.edge_weight(a, c), Some(7));
+    assert_eq!(view.iter().count(), 1);
+
+    // Weights go through to the wrapped storage, writes to the packed edges stay in the copy
+    assert_eq!(graph.edge_weight(a, b), Some(1));
+    assert_eq!(graph.edge_weight(a, c), Some(5));
+}
+
+#[test]
+pub fn filtered_view_slice_test(){
+    let mut graph = WeightedGraph::new();
+    let a = graph.graph.create("a", 3);
+    let b = graph.create_and_connect_weighted(a, "b", 1, 0);
+    graph.create_and_connect_weighted(a, "c", 2, 0);
+    let d = graph.create_and_connect_weighted(a, "d", 3, 0);
+    let original = graph.graph.edge_storage.find_edge(a, d).unwrap();
+
+    let view = graph.graph.filtered_view(|_| true, |_, _, edge| wgt(edge) != 2);
+    let index = view.edges_index(a);
+    assert_eq!(view.edges_as_slice(a), &[pack(b, 1), pack(d, 3)]);
+    assert_eq!(view.edges_len(a), 2);
+    assert_eq!(view.edge_indices(a).collect::<Vec<_>>(), vec![index, index + 1]);
+    assert_eq!(view[index + 1], pack(d, 3));
+    assert_eq!(view.original_index(index + 1), original);
+    assert_eq!(view.weight(index + 1), 3);
+}
+
+#[test]
+pub fn filtered_view_levels_test(){
+    let mut graph = WeightedGraph::new();
+    let s = graph.graph.create("s", 2);
+    let a = graph.create_and_connect_weighted(s, "a", 1, 1);
+    let b = graph.create_and_connect_weighted(s, "b", 1, 1);
+    let c = graph.create_and_connect_weighted(b, "c", 1, 1);
+    let t = graph.create_and_connect_weighted(a, "t", 1, 0);
+    graph.graph.edge_storage.connect_weighted(c, t, 1);
+    let vertices_len = graph.graph.vertices.len();
+
+    let mut view = graph.graph.filtered_view(|handle| handle != a, |_, _, _| true);
+    let mut levels = Array::new_with_default(vertices_len, usize::MAX);
+    let mut queue = Queue::<VHandle>::new_pow2_sized(vertices_len);
+    mark_levels(s, t, &mut view, &mut queue, &mut levels).unwrap();
+    assert_eq!(levels.as_slice(), &[0, usize::MAX, 1, 2, 3]);
+}
\ No newline at end of file
@@ -0,0 +1,127 @@
+use crate::algorithms::dfs_bfs::{bfs, dfs};
+use crate::algorithms::dfs_bfs::ControlFlow::Resume;
+use crate::algorithms::dinic::DinicGraph;
+use crate::algorithms::path_finding::dijkstra;
+use crate::edge_storage::{EdgeStorage, GrowthPolicy};
+use crate::graph::{Error, Graph};
+use crate::handles::{vh, vh_pack, wgt};
+use crate::handles::types::VHandle;
+use crate::traits::{EdgeConnect, EdgeStore, StoreVertex, WeightedEdgeConnect};
+use crate::vertex_storage::VertexStorage;
+use crate::weighted_graph::WeightedGraph;
+
+fn diamond() -> WeightedGraph<&'static str, VertexStorage<&'static str>, EdgeStorage> {
+    let mut graph = WeightedGraph::with_reserve(5);
+    let s = graph.graph.create("s", 2);
+    let a = graph.create_and_connect_weighted(s, "a", 4, 1);
+    let b = graph.create_and_connect_weighted(s, "b", 1, 1);
+    let t = graph.create_and_connect_weighted(a, "t", 3, 0);
+    graph.graph.edge_storage.connect_weighted(b, t, 2);
+    graph
+}
+
+#[test]
+pub fn frozen_graph_layout_test(){
+    let graph = diamond().graph;
+    let expected: Vec<Vec<_>> = (0..4).map(|vertex| graph.edge_storage.edges_as_slice(vertex).to_vec()).collect();
+
+    let frozen = graph.freeze();
+    assert_eq!(frozen.edge_storage.offsets, vec![0, 2, 3, 4, 4]);
+    assert_eq!(frozen.edge_storage.edges.capacity(), 4);
+    assert_eq!(frozen.edge_storage.vertices_len(), 4);
+    for vertex in 0..4 {
+        assert_eq!(frozen.edge_storage.edges_as_slice(vertex), expected[vertex as usize].as_slice());
+        assert_eq!(frozen.edge_storage.edges_capacity(vertex), expected[vertex as usize].len());
+    }
+    assert_eq!(frozen.edge_storage.edge_weight(0, 2), Some(1));
+    assert_eq!(frozen.edge_storage.iter().count(), 4);
+
+    let mut edge_storage = frozen.edge_storage;
+    assert_eq!(edge_storage.try_create_vertex_entry(1), Err(Error::Frozen));
+}
+
+#[test]
+pub fn frozen_graph_algorithms_test(){
+    let mut frozen = diamond().graph.freeze();
+    let len = frozen.vertices.len();
+
+    let mut path = dijkstra(&mut frozen.edge_storage, 0, 3, len).unwrap();
+    let path: Vec<_> = std::iter::from_fn(|| path.pop()).map(|vertex| frozen.vertices[vertex]).collect();
+    assert_eq!(path, vec!["s", "b", "t"]);
+
+    let mut visited = Vec::new();
+    bfs(&mut frozen.edge_storage, vh_pack(0), len, |edge, _| {
+        visited.push(vh(*edge));
+        Resume
+    });
+    assert_eq!(visited, vec![0, 1, 2, 3]);
+
+    let mut visited = Vec::new();
+    dfs(&mut frozen.edge_storage, vh_pack(0), len, |edge| {
+        visited.push(vh(*edge));
+        Resume
+    }, |_| {});
+    assert_eq!(visited.len(), 4);
+
+    let dinic_graph = DinicGraph::from(&frozen.vertices, &frozen.edge_storage, 0, 3);
+    let flows: Vec<_> = dinic_graph.edge_storage.iter().map(|edge| wgt(*edge)).collect();
+    assert_eq!(flows, vec![3, 1, 3, 1]);
+}
+
+#[test]
+pub fn frozen_graph_thaw_test(){
+    let mut graph = Graph::with_edge_data(0);
+    let a = graph.create("a", 2);
+    let b = graph.create("b", 0);
+    graph.edge_storage.connect_with_data(a, b, 1, "ab");
+    graph.edge_storage.connect_with_data(a, a, 2, "aa");
+
+    let frozen = graph.freeze();
+    assert_eq!(frozen.edge_storage.edge_data_as_slice(a), &["ab", "aa"]);
+
+    let mut graph = frozen.thaw();
+    assert_eq!(graph.edge_storage.edges_capacity(a), 2);
+    let c = graph.create("c", 0);
+    graph.edge_storage.connect_with_data(b, c, 3, "bc");
+    graph.edge_storage.connect(a, c);
+    let edges: Vec<(VHandle, &str)> = graph.edge_storage.edges_with_data_iter(a).map(|(edge, data)| (vh(*edge), *data)).collect();
+    assert_eq!(edges, vec![(b, "ab"), (a, "aa"), (c, "")]);
+    assert_eq!(graph.edge_storage.edge_data_as_slice(b), &["bc"]);
+    assert!(graph.generational(c).is_ok());
+}
+
+#[test]
+pub fn frozen_graph_thaw_keeps_settings_test(){
+    let mut graph = Graph::new();
+    let a = graph.create("a", 2);
+    let b = graph.create("b", 0);
+    let c = graph.create("c", 0);
+    graph.edge_storage.set_sorted_adjacency(true);
+    graph.edge_storage.set_growth_policy(GrowthPolicy::Strict);
+    graph.edge_storage.connect(a, c);
+    graph.edge_storage.connect(a, b);
+
+    let mut graph = graph.freeze().thaw();
+    assert!(graph.edge_storage.is_sorted_adjacency());
+    assert_eq!(graph.edge_storage.growth_policy(), GrowthPolicy::Strict);
+    graph.edge_storage.disconnect(a, b);
+    graph.edge_storage.connect(a, a);
+    let targets: Vec<VHandle> = graph.edge_storage.edges_iter(a).map(|edge| vh(*edge)).collect();
+    assert_eq!(targets, vec![a, c]);
+}
+#[test]
+pub fn frozen_graph_thaw_keeps_generations_test(){
+    let mut graph = Graph::new();
+    let a = graph.create("a", 1);
+    let b = graph.create("b", 1);
+    let c = graph.create("c", 1);
+    let stale = graph.generational(c).unwrap();
+    graph.remove_vertex(a);
+
+    let mut graph = graph.freeze().thaw();
+    let moved = graph.generational(a).unwrap();
+    let b = graph.generational(b).unwrap();
+    assert_eq!(graph.connect_checked(moved, b), Ok(()));
+    assert_eq!(graph.vertices[vh(graph.edge_storage.edges_as_slice(a)[0])], "b");
+    assert!(graph.connect_checked(stale, b).is_err());
+}
\ No newline at end of file
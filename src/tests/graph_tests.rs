@@ -1,11 +1,13 @@
 use std::cmp::{min};
-use std::mem::size_of;
+use std::mem::{size_of, size_of_val};
 use std::time::{Instant};
 use crate::{graph};
-use crate::graph::{Graph};
+use crate::edge_storage::GrowthPolicy;
+use crate::graph::{Error, Graph};
 use crate::handles::types::{VHandle, Weight};
-use crate::handles::{vh, wgt};
+use crate::handles::{pack, vh, wgt, GHandle, Width, NONE, W16, W32};
 use crate::traits::{EdgeConnect, EdgeStore, StoreVertex};
+use crate::observer::GraphObserver;
 use crate::weighted_graph::WeightedGraph;
 
 #[test]
@@ -104,6 +106,7 @@ pub fn graph_with_capacity_test(){
 #[should_panic]
 pub fn graph_edge_overflow_test(){
     let mut graph = graph::Graph::with_reserve(3);
+    graph.edge_storage.set_growth_policy(GrowthPolicy::Strict);
     let count = 4;
     let a = graph.create_leaf(0);
 
@@ -112,6 +115,25 @@ pub fn graph_edge_overflow_test(){
     }
 }
 
+#[test]
+pub fn graph_edge_growth_test(){
+    let mut graph = graph::Graph::with_reserve(3);
+    let count: usize = 20;
+    let a = graph.create_leaf(0);
+    let b = graph.create_leaf(0);
+    graph.edge_storage.connect(b, a);
+
+    for i in 0..count {
+        graph.create_and_connect_0(a, i+1);
+    }
+    assert_eq!(graph.edge_storage.edges_len(a), count);
+    assert!(graph.edge_storage.edges_capacity(a) >= count);
+    for (i, edge) in graph.edge_storage.edges_iter(a).enumerate() {
+        assert_eq!(graph.vertices[vh(*edge)], i + 1);
+    }
+    assert_eq!(graph.edge_storage.edges_len(b), 1);
+    assert_eq!(vh(graph.edge_storage.edges_as_slice(b)[0]), a);
+}
 
 #[test]
 pub fn graph_mutability_test(){
@@ -301,4 +323,235 @@ pub fn graph_weight_test(){
             _ => continue,
         }
     }
+}
+#[test]
+pub fn graph_remove_vertex_test(){
+    let mut graph = Graph::new();
+    let a = graph.create("a", 3);
+    let b = graph.create_and_connect(a, "b", 2);
+    let c = graph.create_and_connect(a, "c", 1);
+    let d = graph.create_and_connect(a, "d", 1);
+    graph.edge_storage.connect(b, c);
+    graph.edge_storage.connect(b, d);
+    graph.edge_storage.connect(d, b);
+
+    let remap = graph.remove_vertex(b);
+    assert_eq!(remap.apply(b), NONE);
+    assert_eq!(remap.apply(d), b);
+    assert_eq!(remap.apply(a), a);
+    assert_eq!(remap.apply(c), c);
+
+    let d = remap.apply(d);
+    assert_eq!(graph.vertices.len(), 3);
+    assert_eq!(graph.vertices[d], "d");
+    assert_eq!(graph.edge_storage.edges_len(a), 2);
+    assert_eq!(graph.edge_storage.edges_len(d), 0);
+
+    let mut targets: Vec<VHandle> = graph.edge_storage.edges_iter(a).map(|edge| vh(*edge)).collect();
+    targets.sort();
+    assert_eq!(targets, vec![d, c]);
+    for edge in graph.edge_storage.edges_iter(a) {
+        assert_ne!(graph.vertices[vh(*edge)], "b");
+    }
+}
+
+#[test]
+pub fn graph_remove_last_vertex_test(){
+    let mut graph = Graph::new();
+    let a = graph.create("a", 1);
+    let b = graph.create_and_connect(a, "b", 0);
+
+    let remap = graph.remove_vertex(b);
+    assert_eq!(remap.moved, NONE);
+    assert_eq!(remap.apply(a), a);
+    assert_eq!(graph.vertices.len(), 1);
+    assert_eq!(graph.edge_storage.edges_len(a), 0);
+}
+
+#[test]
+pub fn graph_generational_handle_test(){
+    let mut graph = Graph::new();
+    let a = graph.create("a", 2);
+    let b = graph.create_and_connect(a, "b", 0);
+    let c = graph.create_and_connect(a, "c", 0);
+    let ga = graph.generational(a).unwrap();
+    let gb = graph.generational(b).unwrap();
+    let gc = graph.generational(c).unwrap();
+
+    assert_eq!(*graph.get(gb).unwrap(), "b");
+    assert_eq!(graph.edges_checked(ga).unwrap().len(), 2);
+
+    graph.remove_vertex_checked(gb).unwrap();
+    assert_eq!(graph.get(gb), Err(Error::StaleHandle));
+    // `c` was moved into the slot of `b`, its old handle is stale
+    assert_eq!(graph.get(gc), Err(Error::StaleHandle));
+    assert_eq!(graph.edges_checked(gc), Err(Error::StaleHandle));
+    assert_eq!(graph.get(GHandle{handle: c + 1, generation: 0}), Err(Error::InvalidHandle(c + 1)));
+    assert_eq!(graph.connect_checked(ga, gb), Err(Error::StaleHandle));
+    assert_eq!(graph.edges_checked(ga).unwrap().len(), 1);
+
+    let gc = graph.generational(b).unwrap();
+    assert_eq!(*graph.get(gc).unwrap(), "c");
+
+    // Reusing the vacated slot must not revive old handles
+    let d = graph.create_leaf("d");
+    assert_eq!(d, c);
+    assert_eq!(graph.get(graph.generational(d).unwrap()), Ok(&"d"));
+    assert_eq!(graph.edge_storage.edges_checked(GHandle{handle: c, generation: 0}), Err(Error::StaleHandle));
+}
+
+#[test]
+pub fn graph_try_mutation_test(){
+    let mut graph = Graph::new();
+    graph.edge_storage.set_growth_policy(GrowthPolicy::Strict);
+    let a = graph.try_create("a", 1).unwrap();
+    let b = graph.try_create("b", 0).unwrap();
+
+    assert_eq!(graph.try_connect(a, 7), Err(Error::InvalidHandle(7)));
+    assert_eq!(graph.try_connect(7, a), Err(Error::InvalidHandle(7)));
+    assert_eq!(graph.try_connect(a, b), Ok(()));
+    assert_eq!(graph.try_connect(a, a), Err(Error::CapacityExceeded{handle: a, capacity: 1, required: 2}));
+    assert_eq!(graph.edge_storage.edges_len(a), 1);
+
+    assert_eq!(graph.try_disconnect(b, a), Err(Error::NoEdge{from: b, to: a}));
+    assert_eq!(graph.try_disconnect(a, b), Ok(()));
+    assert_eq!(graph.try_disconnect(a, b), Err(Error::NoEdge{from: a, to: b}));
+}
+
+#[test]
+pub fn graph_try_connect_weighted_test(){
+    let mut wgraph = WeightedGraph::new();
+    let a = wgraph.try_create("a", 3).unwrap();
+    let b = wgraph.try_create("b", 0).unwrap();
+
+    assert_eq!(wgraph.try_connect_weighted(a, b, Weight::MAX as i64 + 1), Err(Error::WeightOutOfRange));
+    assert_eq!(wgraph.try_connect_weighted(a, b, 12u64), Ok(()));
+    assert_eq!(wgraph.try_connect_weighted(a, b, -3), Ok(()));
+    assert_eq!(wgraph.graph.edge_storage.edges_as_slice(a), &[pack(b, 12), pack(b, -3)]);
+    assert_eq!(wgraph.try_connect(a, 9), Err(Error::InvalidHandle(9)));
+    assert_eq!(wgraph.try_disconnect(b, a), Err(Error::NoEdge{from: b, to: a}));
+    assert_eq!(wgraph.try_disconnect(a, b), Ok(()));
+}
+
+#[test]
+pub fn graph_weighted_edge_lookup_test(){
+    let mut wgraph = WeightedGraph::new();
+    let root = wgraph.graph.create("root", 2);
+    let a = wgraph.create_and_connect_weighted(root, "a", 5, 0);
+    let b = wgraph.graph.create("b", 0);
+
+    assert!(wgraph.has_edge(root, a));
+    assert!(!wgraph.has_edge(root, b));
+    assert_eq!(wgraph.find_edge(root, a), Some(wgraph.graph.edge_storage.edges_index(root)));
+    assert_eq!(wgraph.edge_weight(root, a), Some(5));
+    assert_eq!(wgraph.set_edge_weight(root, a, -2), Ok(()));
+    assert_eq!(wgraph.edge_weight(root, a), Some(-2));
+    assert_eq!(wgraph.set_edge_weight(root, b, 1), Err(Error::NoEdge{from: root, to: b}));
+}
+
+#[test]
+pub fn graph_from_edges_test(){
+    let edges = vec![(0, 1, 5), (2, 0, 1), (0, 2, -3), (1, 2, 7)];
+    let graph = Graph::from_edges(["a", "b", "c"], edges).unwrap();
+    assert_eq!(graph.vertices.len(), 3);
+    assert_eq!(graph.edge_storage.edges_as_slice(0), &[pack(1, 5), pack(2, -3)]);
+    assert_eq!(graph.edge_storage.edges_as_slice(1), &[pack(2, 7)]);
+    assert_eq!(graph.edge_storage.edges_as_slice(2), &[pack(0, 1)]);
+    for vertex in 0..3 {
+        assert_eq!(graph.edge_storage.edges_capacity(vertex), graph.edge_storage.edges_len(vertex));
+    }
+    assert_eq!(graph.edge_storage.edges.capacity(), 4);
+
+    let mut graph = graph;
+    let d = graph.create("d", 0);
+    graph.edge_storage.connect(1, d);
+    assert_eq!(graph.edge_storage.edges_as_slice(1), &[pack(2, 7), pack(d, 0)]);
+
+    let result = WeightedGraph::from_edges(["a", "b"], [(0, 1, 1), (1, 2, 1)]);
+    assert_eq!(result.err(), Some(Error::InvalidHandle(2)));
+}
+
+/// Records the handles and weights reported at the width `W`.
+struct WidthRecorder<W: Width> {
+    created: Vec<W::VHandle>,
+    connected: Vec<(W::VHandle, W::VHandle, W::Weight)>,
+}
+
+impl<VertexType, W: Width> GraphObserver<VertexType, W> for WidthRecorder<W> {
+    fn vertex_created(&mut self, handle: W::VHandle, _value: &VertexType) {
+        self.created.push(handle);
+    }
+    fn connected(&mut self, from: W::VHandle, to: W::VHandle, weight: W::Weight) {
+        self.connected.push((from, to, weight));
+    }
+}
+
+/// Runs the same edits on a graph of the width `W`.
+fn graph_width_steps<W: Width>() {
+    let mut graph: WeightedGraph<&str, _, _, _, W> = WeightedGraph::with_width(2);
+    let a = graph.graph.create("a", 2);
+    let b = graph.graph.create("b", 2);
+    let c = graph.graph.create("c", 2);
+    graph.try_connect_weighted(a, b, 100).unwrap();
+    graph.try_connect_weighted(b, c, -7).unwrap();
+    let weight = graph.edge_weight(b, c).unwrap();
+    assert_eq!(Into::<i64>::into(weight), -7);
+    assert_eq!(graph.try_connect_weighted(c, a, i64::MAX), Err(Error::WeightOutOfRange));
+
+    let transposed = graph.transpose();
+    assert_eq!(transposed.edge_weight(c, b), Some(weight));
+    assert!(!transposed.has_edge(b, c));
+
+    let stale = graph.graph.generational(a).unwrap();
+    let moved = graph.graph.generational(c).unwrap();
+    let remap = graph.graph.remove_vertex(a);
+    assert_eq!((remap.removed, remap.moved), (a, c));
+    assert_eq!(graph.graph.get(stale), Err(Error::StaleHandle));
+    assert_eq!(graph.graph.get(moved), Err(Error::StaleHandle));
+    let c = remap.apply(c);
+    assert_eq!(graph.graph.get(graph.graph.generational(c).unwrap()), Ok(&"c"));
+    assert_eq!(graph.edge_weight(b, c), Some(weight));
+
+    let thawed = graph.graph.freeze().thaw();
+    assert_eq!(thawed.edge_storage.edges_as_slice(b), &[W::pack(c, weight)]);
+
+    let mut graph: Graph<&str, _, _, _, W> = Graph::with_width(1).with_observer(WidthRecorder { created: Vec::new(), connected: Vec::new() });
+    let a = graph.create("a", 1);
+    let mut transaction = graph.transaction();
+    let b = transaction.create_and_connect(a, "b", 0);
+    let log = transaction.commit();
+    assert_eq!(graph.observer.created, vec![a, b]);
+    assert_eq!(graph.observer.connected, vec![(a, b, W::Weight::default())]);
+    graph.undo(log);
+    assert_eq!(graph.vertices.len(), 1);
+    assert!(graph.edge_storage.edges_is_empty(a));
+
+    let mut graph: Graph<&str, _, _, _, W> = Graph::default();
+    let mut tree = graph.tree_view();
+    let root = tree.create_node("root");
+    let child = tree.create_child(root, "child");
+    assert_eq!(tree.get_parent(child), root);
+    assert_eq!(tree.get_parent(root), W::NONE);
+}
+
+#[test]
+pub fn graph_widths_test(){
+    graph_width_steps::<W16>();
+    graph_width_steps::<W32>();
+
+    // A 16 and a 32 bit graph side by side, their edges keep their own width
+    let small: Graph<u8, _, _, _, W16> = Graph::from_edges_with_width([1, 2], [(0, 1, -128)]).unwrap();
+    let medium: Graph<u8, _, _, _, W32> = Graph::from_edges_with_width([1, 2], [(0, 1, -32768)]).unwrap();
+    assert_eq!(size_of_val(small.edge_storage.edges_as_slice(0)), 2);
+    assert_eq!(size_of_val(medium.edge_storage.edges_as_slice(0)), 4);
+    assert_eq!(W16::wgt(small.edge_storage.edges_as_slice(0)[0]), i8::MIN);
+    assert_eq!(W32::wgt(medium.edge_storage.edges_as_slice(0)[0]), i16::MIN);
+
+    let mut small: WeightedGraph<u8, _, _, _, W16> = WeightedGraph::with_width(1);
+    let mut medium: WeightedGraph<u8, _, _, _, W32> = WeightedGraph::with_width(1);
+    let (a, b) = (small.graph.create(1, 1), small.graph.create(2, 1));
+    assert_eq!(small.try_connect_weighted(a, b, 200), Err(Error::WeightOutOfRange));
+    let (a, b) = (medium.graph.create(1, 1), medium.graph.create(2, 1));
+    assert_eq!(medium.try_connect_weighted(a, b, 200), Ok(()));
+    assert_eq!(medium.edge_weight(a, b), Some(200));
 }
\ No newline at end of file
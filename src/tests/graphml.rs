@@ -0,0 +1,151 @@
+use crate::formats::graphml::{AttributeType, GraphMlParser, Value};
+use crate::formats::ParseError;
+use crate::handles::{vh, wgt, W16, W32};
+use crate::traits::{EdgeStore, StoreVertex};
+use crate::undirected_graph::UndirectedGraph;
+use crate::weighted_graph::WeightedGraph;
+
+#[derive(Debug, Clone, PartialEq)]
+struct City {
+    name: String,
+    population: i64,
+}
+
+#[test]
+pub fn graphml_export_test(){
+    let mut graph = WeightedGraph::new();
+    let a = graph.graph.create(City { name: "A & B".to_string(), population: 10 }, 1);
+    graph.create_and_connect_weighted(a, City { name: "C".to_string(), population: 0 }, 7, 0);
+
+    let expected = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">
+  <key id=\"d0\" for=\"node\" attr.name=\"name\" attr.type=\"string\"/>
+  <key id=\"d1\" for=\"node\" attr.name=\"population\" attr.type=\"long\"/>
+  <key id=\"d2\" for=\"edge\" attr.name=\"weight\" attr.type=\"long\"/>
+  <graph edgedefault=\"directed\">
+    <node id=\"n0\">
+      <data key=\"d0\">A &amp; B</data>
+      <data key=\"d1\">10</data>
+    </node>
+    <node id=\"n1\">
+      <data key=\"d0\">C</data>
+    </node>
+    <edge source=\"n0\" target=\"n1\">
+      <data key=\"d2\">7</data>
+    </edge>
+  </graph>
+</graphml>
+";
+    let output = graph.graphml()
+        .vertex_key("name", AttributeType::String, |_, city| Some(Value::String(city.name.clone())))
+        .vertex_key("population", AttributeType::Long, |_, city| (city.population > 0).then_some(Value::Long(city.population)))
+        .to_string();
+    assert_eq!(output, expected);
+
+    let parsed = GraphMlParser::new(|_, data| City {
+        name: data.get("name").and_then(Value::as_str).unwrap_or_default().to_string(),
+        population: data.get("population").and_then(Value::as_i64).unwrap_or(0),
+    }).weight_attribute("weight").parse(&output).unwrap();
+    assert!(parsed.directed);
+    assert_eq!(parsed.graph.graph.vertices[parsed.names["n0"]], graph.graph.vertices[0]);
+    assert_eq!(parsed.graph.graph.vertices[parsed.names["n1"]], graph.graph.vertices[1]);
+    assert_eq!(parsed.graph.edge_weight(parsed.names["n0"], parsed.names["n1"]), Some(7));
+}
+
+#[test]
+pub fn graphml_undirected_test(){
+    let mut graph = UndirectedGraph::new();
+    let a = graph.create("a", 2);
+    let b = graph.create_and_connect_weighted(a, "b", 3, 1);
+    graph.connect_weighted(a, a, 1);
+
+    let output = graph.graph.graphml().directed(false).weights(true).to_string();
+    assert!(output.contains("<graph edgedefault=\"undirected\">"));
+    assert_eq!(output.matches("<edge ").count(), 2);
+
+    let parsed = GraphMlParser::new(|id, _| id.to_string()).weight_attribute("weight").parse(&output).unwrap();
+    assert!(!parsed.directed);
+    for vertex in [a, b] {
+        let handle = parsed.names[&format!("n{}", vertex)];
+        let mut edges: Vec<_> = parsed.graph.graph.edge_storage.edges_iter(handle)
+            .map(|edge| (parsed.graph.graph.vertices[vh(*edge)].clone(), wgt(*edge)))
+            .collect();
+        let mut expected: Vec<_> = graph.graph.edge_storage.edges_iter(vertex)
+            .map(|edge| (format!("n{}", vh(*edge)), wgt(*edge)))
+            .collect();
+        edges.sort();
+        expected.sort();
+        assert_eq!(edges, expected);
+    }
+}
+
+#[test]
+pub fn graphml_import_test(){
+    // Shaped like the output of NetworkX and yEd
+    let input = "<?xml version='1.0' encoding='utf-8'?>
+<!-- generated -->
+<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\" xmlns:y=\"http://www.yworks.com/xml/graphml\">
+  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"><default>unnamed</default></key>
+  <key id=\"w\" for=\"edge\" attr.name=\"weight\" attr.type=\"double\"><default>1.0</default></key>
+  <key id=\"g\" for=\"node\" yfiles.type=\"nodegraphics\"/>
+  <graph id=\"G\" edgedefault=\"undirected\">
+    <data key=\"w\">5</data>
+    <edge source=\"x\" target=\"y\" directed=\"true\"><data key=\"w\">4.0</data></edge>
+    <node id=\"x\"><data key=\"label\">X &lt;1&gt;</data><data key=\"g\"><y:ShapeNode><y:Fill color=\"#FFCC00\"/></y:ShapeNode></data></node>
+    <node id=\"y\"/>
+    <node id=\"z\"><data key=\"label\"><![CDATA[Z & co]]></data></node>
+    <edge source=\"y\" target=\"z\"/>
+  </graph>
+  <graph edgedefault=\"directed\"><node id=\"ignored\"/></graph>
+</graphml>";
+    let parsed = GraphMlParser::new(|_, data| data.get("label").and_then(Value::as_str).unwrap().to_string())
+        .weight_attribute("weight")
+        .parse(input)
+        .unwrap();
+    let graph = &parsed.graph;
+    assert!(!parsed.directed);
+    assert_eq!(graph.graph.vertices.len(), 3);
+    let (x, y, z) = (parsed.names["x"], parsed.names["y"], parsed.names["z"]);
+    assert_eq!(graph.graph.vertices[x], "X <1>");
+    assert_eq!(graph.graph.vertices[y], "unnamed");
+    assert_eq!(graph.graph.vertices[z], "Z & co");
+    assert_eq!(graph.edge_weight(x, y), Some(4));
+    assert!(!graph.has_edge(y, x));
+    assert_eq!(graph.edge_weight(y, z), Some(1));
+    assert_eq!(graph.edge_weight(z, y), Some(1));
+}
+
+#[test]
+pub fn graphml_error_test(){
+    let error = |input: &str| GraphMlParser::new(|id, _| id.to_string()).weight_attribute("weight").parse(input).err().unwrap();
+    let wrap = |body: &str| format!("<graphml>\n<key id=\"w\" for=\"edge\" attr.name=\"weight\" attr.type=\"long\"/>\n<graph>\n{}\n</graph>\n</graphml>", body);
+    assert_eq!(error(&wrap("<node id=\"a\"/>\n<edge source=\"a\" target=\"b\"/>")), ParseError::new(5, "edge to the undeclared node `b`"));
+    assert_eq!(error(&wrap("<node id=\"a\"/>\n<edge source=\"a\" target=\"a\"><data key=\"w\">x</data></edge>")), ParseError::new(5, "`x` is not a valid long"));
+    assert_eq!(error(&wrap("<node id=\"a\"/>\n<edge source=\"a\" target=\"a\"><data key=\"w\">99999999999</data></edge>")), ParseError::new(5, "`99999999999` is not a valid weight"));
+    assert_eq!(error(&wrap("<node id=\"a\"><data key=\"q\">1</data></node>")), ParseError::new(4, "undeclared key `q`"));
+    assert_eq!(error(&wrap("<node id=\"a\"/>\n<node id=\"a\"/>")), ParseError::new(5, "duplicate node id `a`"));
+    assert_eq!(error(&wrap("<node>")), ParseError::new(4, "`<node>` is missing `id`"));
+    assert_eq!(error(&wrap("<hyperedge/>")), ParseError::new(4, "hyperedges are not supported"));
+    assert_eq!(error(&wrap("<node id=\"a\">\n</edge>")), ParseError::new(5, "expected `</node>`, found `</edge>`"));
+    assert_eq!(error("<graphml>\n<graph>"), ParseError::new(2, "missing `</graph>`"));
+    assert_eq!(error("<graph/>"), ParseError::new(1, "expected `<graphml>`"));
+    assert_eq!(error("<graphml/>").message, "missing `<graph>`");
+}
+
+#[test]
+pub fn graphml_parse_widths_test(){
+    let document = |weight: &str| format!("<graphml>
+  <key id=\"w\" for=\"edge\" attr.name=\"weight\" attr.type=\"double\"/>
+  <graph edgedefault=\"directed\">
+    <node id=\"a\"/>
+    <node id=\"b\"/>
+    <edge source=\"a\" target=\"b\"><data key=\"w\">{}</data></edge>
+  </graph>
+</graphml>", weight);
+    let parser = GraphMlParser::new(|id, _| id.to_string()).weight_attribute("weight");
+    let small = parser.parse_with_width::<W16>(&document("-12.0")).unwrap();
+    let medium = parser.parse_with_width::<W32>(&document("1000.0")).unwrap();
+    assert_eq!(small.graph.edge_weight(small.names["a"], small.names["b"]), Some(-12));
+    assert_eq!(medium.graph.edge_weight(medium.names["a"], medium.names["b"]), Some(1000));
+    assert_eq!(parser.parse_with_width::<W16>(&document("1000.0")).err(), Some(ParseError::new(6, "`1000` is not a valid weight")));
+}
\ No newline at end of file
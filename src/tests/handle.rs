@@ -136,4 +136,20 @@ pub fn vh_pack_test(){
     let handle = vh_pack(5);
     assert_eq!(vh(handle), 5);
     assert_eq!(wgt(handle), 0);
-}
\ No newline at end of file
+}
+#[test]
+pub fn handle_widths_test(){
+    use crate::handles::{w16, w32};
+    let handle = w16::pack(u8::MAX - 1, i8::MIN);
+    assert_eq!(w16::vh(handle), u8::MAX - 1);
+    assert_eq!(w16::wgt(handle), i8::MIN);
+    let handle = w16::set_wgt(w16::set_vh(handle, 3), -1);
+    assert_eq!((w16::vh(handle), w16::wgt(handle)), (3, -1));
+    assert_eq!(w16::vhu(w16::vh_pack(w16::NONE)), u8::MAX as usize);
+
+    let handle = w32::pack(1231, -8997);
+    assert_eq!(w32::vh(handle), 1231);
+    assert_eq!(w32::wgt(handle), -8997);
+    let handle = w32::set_wgt(w32::set_vh(handle, u16::MAX), i16::MAX);
+    assert_eq!((w32::vh(handle), w32::wgt(handle)), (u16::MAX, i16::MAX));
+}
@@ -1,4 +1,6 @@
 #[cfg(test)]
+mod utils;
+#[cfg(test)]
 mod graph_tests;
 #[cfg(test)]
 mod tree_tests;
@@ -14,3 +16,28 @@ mod bfs;
 mod dfs;
 #[cfg(test)]
 mod dinic;
+#[cfg(test)]
+mod undirected_graph;
+
+#[cfg(test)]
+mod frozen_graph;
+#[cfg(test)]
+mod dot;
+#[cfg(test)]
+mod graphml;
+#[cfg(test)]
+mod edge_lists;
+#[cfg(test)]
+mod adjacency_matrix;
+#[cfg(test)]
+mod filtered_view;
+#[cfg(test)]
+mod reversed_view;
+#[cfg(test)]
+mod observer;
+#[cfg(test)]
+mod transaction;
+#[cfg(all(test, feature = "serde"))]
+mod serialization;
+#[cfg(all(test, feature = "mmap"))]
+mod snapshot;
\ No newline at end of file
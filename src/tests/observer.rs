@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use crate::graph::{Graph, VertexRemap};
+use crate::handles::types::{VHandle, Weight};
+use crate::handles::NONE;
+use crate::observer::GraphObserver;
+use crate::weighted_graph::WeightedGraph;
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Created(VHandle),
+    Removed(VertexRemap),
+    Connected(VHandle, VHandle, Weight),
+    Disconnected(VHandle, VHandle),
+    WeightChanged(VHandle, VHandle, Weight),
+    ChildAdded(VHandle, VHandle),
+}
+
+#[derive(Default)]
+struct Recorder {
+    events: Vec<Event>,
+}
+
+impl<VertexType> GraphObserver<VertexType> for Recorder {
+    fn vertex_created(&mut self, handle: VHandle, _value: &VertexType) {
+        self.events.push(Event::Created(handle));
+    }
+    fn vertex_removed(&mut self, remap: VertexRemap) {
+        self.events.push(Event::Removed(remap));
+    }
+    fn connected(&mut self, from: VHandle, to: VHandle, weight: Weight) {
+        self.events.push(Event::Connected(from, to, weight));
+    }
+    fn disconnected(&mut self, from: VHandle, to: VHandle) {
+        self.events.push(Event::Disconnected(from, to));
+    }
+    fn weight_changed(&mut self, from: VHandle, to: VHandle, weight: Weight) {
+        self.events.push(Event::WeightChanged(from, to, weight));
+    }
+    fn child_added(&mut self, parent: VHandle, child: VHandle) {
+        self.events.push(Event::ChildAdded(parent, child));
+    }
+}
+
+/// Secondary name index kept in sync by the observer.
+#[derive(Default)]
+struct NameIndex {
+    names: HashMap<String, VHandle>,
+}
+
+impl GraphObserver<String> for NameIndex {
+    fn vertex_created(&mut self, handle: VHandle, value: &String) {
+        self.names.insert(value.clone(), handle);
+    }
+    fn vertex_removed(&mut self, remap: VertexRemap) {
+        self.names.retain(|_, handle| *handle != remap.removed);
+        for handle in self.names.values_mut() {
+            *handle = remap.apply(*handle);
+        }
+    }
+}
+
+#[test]
+pub fn graph_observer_test(){
+    let mut graph = Graph::new().with_observer(Recorder::default());
+    let a = graph.create("a", 2);
+    let b = graph.create_and_connect(a, "b", 1);
+    graph.connect(b, a);
+    graph.disconnect(a, b);
+    graph.disconnect(a, b);
+    assert!(graph.try_disconnect(a, b).is_err());
+    let remap = graph.remove_vertex(a);
+
+    assert_eq!(graph.observer.events, vec![
+        Event::Created(a),
+        Event::Created(b),
+        Event::Connected(a, b, 0),
+        Event::Connected(b, a, 0),
+        Event::Disconnected(a, b),
+        Event::Removed(remap),
+    ]);
+}
+
+#[test]
+pub fn weighted_graph_observer_test(){
+    let mut graph = WeightedGraph::new().with_observer(Recorder::default());
+    let a = graph.graph.create("a", 2);
+    let b = graph.create_and_connect_weighted(a, "b", 3, 0);
+    graph.try_connect_weighted(a, a, 4).unwrap();
+    assert!(graph.try_connect_weighted(a, b, i64::MAX).is_err());
+    graph.set_edge_weight(a, b, 5).unwrap();
+    assert!(graph.set_edge_weight(b, a, 1).is_err());
+
+    assert_eq!(graph.graph.observer.events, vec![
+        Event::Created(a),
+        Event::Created(b),
+        Event::Connected(a, b, 3),
+        Event::Connected(a, a, 4),
+        Event::WeightChanged(a, b, 5),
+    ]);
+}
+
+#[test]
+pub fn tree_observer_test(){
+    let mut graph = Graph::new().with_observer(Recorder::default());
+    let mut tree = graph.tree_view();
+    let root = tree.create_node("root");
+    let child = tree.create_child(root, "child");
+    assert_eq!(tree.get_parent(root), NONE);
+
+    assert_eq!(graph.observer.events, vec![
+        Event::Created(root),
+        Event::Created(child),
+        Event::ChildAdded(root, child),
+    ]);
+}
+
+#[test]
+pub fn name_index_observer_test(){
+    let mut graph = Graph::new().with_observer(NameIndex::default());
+    let a = graph.create("a".to_string(), 0);
+    graph.create("b".to_string(), 0);
+    let c = graph.create("c".to_string(), 0);
+    graph.remove_vertex(a);
+
+    assert_eq!(graph.observer.names.len(), 2);
+    assert_eq!(graph.vertices[graph.observer.names["b"]], "b");
+    assert_eq!(graph.observer.names["c"], a);
+    assert_ne!(graph.observer.names["c"], c);
+}
\ No newline at end of file
@@ -0,0 +1,78 @@
+use crate::adjacency_matrix::AdjacencyMatrix;
+use crate::algorithms::dfs_bfs::bfs;
+use crate::algorithms::dfs_bfs::ControlFlow::Resume;
+use crate::algorithms::path_finding::dijkstra;
+use crate::graph::Error;
+use crate::handles::{pack, vh, vh_pack, wgt};
+use crate::traits::{EdgeStore, StoreVertex, WeightStore, WeightedEdgeConnect};
+use crate::views::reversed::ReversedView;
+use crate::tests::utils::collect_path;
+use crate::weighted_graph::WeightedGraph;
+
+#[test]
+pub fn graph_transpose_test(){
+    let mut graph = WeightedGraph::new();
+    let a = graph.graph.create("a", 2);
+    let b = graph.create_and_connect_weighted(a, "b", 3, 1);
+    let c = graph.create_and_connect_weighted(a, "c", 4, 0);
+    graph.graph.edge_storage.connect_weighted(b, c, 5);
+
+    let transposed = graph.transpose();
+    assert_eq!(transposed.graph.vertices.as_slice(), graph.graph.vertices.as_slice());
+    assert!(transposed.graph.edge_storage.edges_is_empty(a));
+    assert_eq!(transposed.graph.edge_storage.edges_as_slice(b), &[pack(a, 3)]);
+    assert_eq!(transposed.graph.edge_storage.edges_as_slice(c), &[pack(a, 4), pack(b, 5)]);
+    assert_eq!(transposed.transpose().graph.edge_storage.iter().count(), 3);
+}
+
+#[test]
+pub fn reversed_view_test(){
+    let mut graph = WeightedGraph::new();
+    let s = graph.graph.create("s", 2);
+    let a = graph.create_and_connect_weighted(s, "a", 1, 1);
+    let b = graph.create_and_connect_weighted(s, "b", 5, 1);
+    let t = graph.create_and_connect_weighted(a, "t", 7, 0);
+    graph.graph.edge_storage.connect_weighted(b, t, 1);
+    let lonely = graph.graph.create("lonely", 1);
+    graph.graph.edge_storage.connect_weighted(lonely, s, 1);
+
+    let vertices_len = graph.graph.vertices.len();
+    let mut view = graph.graph.reversed_view();
+    assert_eq!(view.edges_len(t), 2);
+    assert_eq!(view.edge_weight(t, b), Some(1));
+    assert!(!view.has_edge(s, t));
+    assert_eq!(view.try_create_vertex_entry(0), Err(Error::Frozen));
+
+    assert_eq!(collect_path(dijkstra(&mut view, t, s, vertices_len).unwrap()), vec![t, b, s]);
+
+    let mut reached = Vec::new();
+    bfs(&mut view, vh_pack(t), vertices_len, |edge, _| {
+        reached.push(vh(*edge));
+        Resume
+    });
+    reached.sort();
+    assert_eq!(reached, vec![s, a, b, t, lonely]);
+
+    let index = view.find_edge(t, b).unwrap();
+    view.set_weight(index, 9);
+    assert_eq!(wgt(view[index]), 9);
+    assert_eq!(view.edge_weight(t, b), Some(9));
+    assert!(view.edges().any(|(from, to, weight)| (from, to, weight) == (t, b, 9)));
+    assert_eq!(graph.edge_weight(b, t), Some(9));
+    assert_eq!(graph.graph.edge_storage.iter().count(), 5);
+}
+
+#[test]
+pub fn reversed_view_adjacency_matrix_test(){
+    let mut edges = AdjacencyMatrix::new();
+    let a = edges.create_vertex_entry(0);
+    let b = edges.create_vertex_entry(0);
+    let c = edges.create_vertex_entry(0);
+    edges.connect_weighted(a, c, 2);
+    edges.connect_weighted(b, c, 3);
+
+    let view = ReversedView::new(&mut edges, 3);
+    assert_eq!(view.edges_iter(c).map(|edge| vh(*edge)).collect::<Vec<_>>(), vec![a, b]);
+    assert_eq!(view.weight(view.find_edge(c, b).unwrap()), 3);
+    assert!(view.edges_is_empty(a));
+}
\ No newline at end of file
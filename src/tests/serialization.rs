@@ -0,0 +1,122 @@
+use crate::edge_storage::{EdgeStorage, GrowthPolicy, PayloadWeighted};
+use crate::graph::Graph;
+use crate::handles::{pack, Width, NONE, W16, W32};
+use crate::traits::{EdgeConnect, EdgeStore, Generational, StoreVertex, WeightedEdgeConnect};
+use crate::weighted_graph::WeightedGraph;
+
+#[test]
+pub fn serde_graph_round_trip_test(){
+    let mut graph = WeightedGraph::with_reserve(2);
+    let a = graph.graph.create("a", 1);
+    let b = graph.create_and_connect_weighted(a, "b", -4, 0);
+    let c = graph.create_and_connect_weighted(a, "c", 9, 3);
+    graph.graph.edge_storage.connect_weighted(c, a, 1);
+    graph.graph.edge_storage.set_growth_policy(GrowthPolicy::Strict);
+    graph.graph.edge_storage.enable_in_edges();
+    let removed = graph.graph.create("removed", 0);
+    graph.graph.remove_vertex(removed);
+
+    let json = serde_json::to_string(&graph).unwrap();
+    let restored: WeightedGraph<String, _, EdgeStorage> = serde_json::from_str(&json).unwrap();
+    let (original, restored) = (&graph.graph, &restored.graph);
+    assert_eq!(restored.vertices.as_slice(), &["a", "b", "c"]);
+    assert_eq!(restored.edge_storage.edges.capacity(), original.edge_storage.edges.capacity());
+    for vertex in [a, b, c] {
+        assert_eq!(restored.edge_storage.edges_as_slice(vertex), original.edge_storage.edges_as_slice(vertex));
+        assert_eq!(restored.edge_storage.edges_index(vertex), original.edge_storage.edges_index(vertex));
+        assert_eq!(restored.edge_storage.edges_capacity(vertex), original.edge_storage.edges_capacity(vertex));
+        assert_eq!(restored.edge_storage.generation(vertex), original.edge_storage.generation(vertex));
+        assert_eq!(restored.vertices.generation(vertex), original.vertices.generation(vertex));
+    }
+    assert_eq!(restored.edge_storage.growth_policy(), GrowthPolicy::Strict);
+    assert_eq!(restored.edge_storage.in_edges_iter(a).copied().collect::<Vec<_>>(), vec![pack(c, 1)]);
+    assert_eq!(serde_json::to_string(restored).unwrap(), json);
+}
+
+#[test]
+pub fn serde_edge_data_round_trip_test(){
+    let mut graph = Graph::with_edge_data(0);
+    let a = graph.create("a", 2);
+    let b = graph.create("b", 0);
+    graph.edge_storage.connect_with_data(a, b, 0, 2.5f64);
+    graph.edge_storage.connect(a, NONE);
+
+    let json = serde_json::to_string(&graph).unwrap();
+    let restored: Graph<String, _, EdgeStorage<f64>> = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.edge_storage.edges_as_slice(a), &[pack(b, 0), pack(NONE, 0)]);
+    assert_eq!(restored.edge_storage.edge_data_as_slice(a), &[2.5, 0.0]);
+}
+
+#[test]
+pub fn serde_weighted_payload_round_trip_test(){
+    let mut graph = WeightedGraph{graph: Graph::with_payload_weights(0)};
+    let a = graph.graph.create("a", 1);
+    let b = graph.graph.create("b", 0);
+    graph.graph.edge_storage.connect_with_data(a, b, 0, 7.5f64);
+
+    let json = serde_json::to_string(&graph).unwrap();
+    let restored: WeightedGraph<String, _, PayloadWeighted<f64>> = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.graph.edge_storage.edge_data_as_slice(a), &[7.5]);
+    assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+}
+
+fn storage_json() -> serde_json::Value {
+    let mut edge_storage = EdgeStorage::new();
+    let a = edge_storage.create_vertex_entry(2);
+    let b = edge_storage.create_vertex_entry(1);
+    edge_storage.connect(a, b);
+    serde_json::to_value(&edge_storage).unwrap()
+}
+
+fn load(json: serde_json::Value) -> String {
+    match serde_json::from_value::<EdgeStorage>(json) {
+        Ok(_) => String::new(),
+        Err(error) => error.to_string(),
+    }
+}
+
+#[test]
+pub fn serde_validation_test(){
+    assert_eq!(load(storage_json()), "");
+
+    let mut json = storage_json();
+    json["vertex_entries"][1]["offset"] = 2.into();
+    json["vertex_entries"][1]["capacity"] = 5.into();
+    assert_eq!(load(json), "edge block out of bounds");
+
+    let mut json = storage_json();
+    json["vertex_entries"][1]["offset"] = 1.into();
+    assert_eq!(load(json), "edge blocks overlap");
+
+    let mut json = storage_json();
+    json["vertex_entries"][0]["len"] = 3.into();
+    assert_eq!(load(json), "vertex entry length exceeds its capacity");
+
+    let mut json = storage_json();
+    json["vertex_entries"][1]["len"] = 1.into();
+    assert_eq!(load(json), "edge count does not match the vertex entries");
+
+    let mut json = storage_json();
+    json["edges"][0] = 7.into();
+    assert_eq!(load(json), "edge points at a missing vertex");
+
+    let mut graph = Graph::new();
+    graph.create("a", 0);
+    let mut json = serde_json::to_value(&graph).unwrap();
+    json["vertices"]["data"] = serde_json::json!(["a", "b"]);
+    json["vertices"]["generations"] = serde_json::json!([0, 0]);
+    assert!(serde_json::from_value::<Graph<String, _, EdgeStorage>>(json).is_err());
+}
+
+#[test]
+pub fn serde_widths_round_trip_test(){
+    let small: WeightedGraph<String, _, _, _, W16> = WeightedGraph::from_edges_with_width(["a".to_string(), "b".to_string()], [(0, 1, -5)]).unwrap();
+    let medium: WeightedGraph<String, _, _, _, W32> = WeightedGraph::from_edges_with_width(["c".to_string(), "d".to_string()], [(1, 0, 1000)]).unwrap();
+
+    let restored: WeightedGraph<String, _, EdgeStorage<(), W16>, _, W16> = serde_json::from_str(&serde_json::to_string(&small).unwrap()).unwrap();
+    assert_eq!(restored.graph.vertices.as_slice(), &["a", "b"]);
+    assert_eq!(restored.graph.edge_storage.edges_as_slice(0), &[W16::pack(1, -5)]);
+    let restored: WeightedGraph<String, _, EdgeStorage<(), W32>, _, W32> = serde_json::from_str(&serde_json::to_string(&medium).unwrap()).unwrap();
+    assert_eq!(restored.graph.vertices.as_slice(), &["c", "d"]);
+    assert_eq!(restored.graph.edge_storage.edges_as_slice(1), &[W32::pack(0, 1000)]);
+}
\ No newline at end of file
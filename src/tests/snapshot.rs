@@ -0,0 +1,134 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use crate::algorithms::dfs_bfs::bfs;
+use crate::algorithms::dfs_bfs::ControlFlow::Resume;
+use crate::algorithms::path_finding::dijkstra;
+use crate::graph::Error;
+use crate::handles::{vh, vh_pack, Width, W16, W32};
+use crate::snapshot::{fnv1a, MappedEdgeStorage, FNV_OFFSET, HEADER_LEN};
+use crate::traits::{EdgeStore, WeightStore, WeightedEdgeConnect};
+use crate::weighted_graph::WeightedGraph;
+
+fn snapshot_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("eta_graph_{}_{}.snapshot", name, std::process::id()))
+}
+
+fn write_diamond(name: &str) -> PathBuf {
+    let mut graph = WeightedGraph::new();
+    let s = graph.graph.create("s", 2);
+    let a = graph.create_and_connect_weighted(s, "a", 4, 1);
+    let b = graph.create_and_connect_weighted(s, "b", 1, 1);
+    let t = graph.create_and_connect_weighted(a, "t", 3, 0);
+    graph.graph.edge_storage.connect_weighted(b, t, 2);
+    graph.graph.create("isolated", 0);
+
+    let path = snapshot_path(name);
+    let mut file = fs::File::create(&path).unwrap();
+    graph.graph.edge_storage.write_snapshot(&mut file).unwrap();
+    path
+}
+
+#[test]
+pub fn snapshot_roundtrip_test(){
+    let path = write_diamond("roundtrip");
+    let mut mapped = MappedEdgeStorage::open(&path).unwrap();
+    assert_eq!(mapped.vertices_len(), 5);
+    assert_eq!(mapped.edges_len(0), 2);
+    assert_eq!(mapped.edges_capacity(0), 2);
+    assert_eq!(mapped.edges_len(3), 0);
+    assert_eq!(mapped.edges_len(4), 0);
+    assert_eq!(mapped.edge_weight(0, 2), Some(1));
+    assert_eq!(mapped.iter().count(), 4);
+
+    let mut path_found = dijkstra(&mut mapped, 0, 3, 5).unwrap();
+    let mut order = Vec::new();
+    while !path_found.is_empty() {
+        order.push(path_found.pop().unwrap());
+    }
+    assert_eq!(order, vec![0, 2, 3]);
+
+    // Traversals which need mutable edges and modifications go through a copy
+    let mut copy = mapped.to_edge_storage();
+    let mut visited = Vec::new();
+    bfs(&mut copy, vh_pack(0), 5, |edge, _| {
+        visited.push(vh(*edge));
+        Resume
+    });
+    assert_eq!(visited, vec![0, 1, 2, 3]);
+    copy.set_edge_weight(0, 1, 9).unwrap();
+    assert_eq!(copy.edge_weight(0, 1), Some(9));
+    assert_eq!(copy.edges_capacity(0), 2);
+    copy.connect_weighted(4, 0, 1);
+    assert_eq!(copy.edges_len(4), 1);
+
+    assert_eq!(mapped.edge_weight(0, 1), Some(4));
+    assert_eq!(mapped.try_create_vertex_entry(1), Err(Error::Frozen));
+    drop(mapped);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+#[should_panic]
+pub fn snapshot_read_only_test(){
+    let path = write_diamond("read_only");
+    let mut mapped = MappedEdgeStorage::open(&path).unwrap();
+    fs::remove_file(path).unwrap();
+    mapped.set_edge_weight(0, 1, 9).unwrap();
+}
+
+#[test]
+pub fn snapshot_corruption_test(){
+    let path = write_diamond("corruption");
+    let original = fs::read(&path).unwrap();
+    let open_with = |bytes: &[u8]| {
+        fs::write(&path, bytes).unwrap();
+        MappedEdgeStorage::open(&path).err().map(|error| error.kind())
+    };
+
+    let mut body = original.clone();
+    *body.last_mut().unwrap() ^= 1;
+    assert_eq!(open_with(&body), Some(io::ErrorKind::InvalidData));
+
+    let mut width = original.clone();
+    width[12] ^= 0xff;
+    assert_eq!(open_with(&width), Some(io::ErrorKind::InvalidData));
+
+    let mut magic = original.clone();
+    magic[0] = b'X';
+    assert_eq!(open_with(&magic), Some(io::ErrorKind::InvalidData));
+
+    // Vertex 1 claims the block of vertex 0, the checksum is fixed so only the overlap is wrong
+    let mut overlap = original.clone();
+    overlap[HEADER_LEN + 12 + 8..HEADER_LEN + 12 + 12].copy_from_slice(&0u32.to_le_bytes());
+    let checksum = fnv1a(FNV_OFFSET, &overlap[HEADER_LEN..]);
+    overlap[32..40].copy_from_slice(&checksum.to_le_bytes());
+    fs::write(&path, &overlap).unwrap();
+    assert_eq!(MappedEdgeStorage::open(&path).err().unwrap().to_string(), "snapshot edge blocks overlap");
+
+    assert_eq!(open_with(&original[..original.len() - 1]), Some(io::ErrorKind::InvalidData));
+    assert_eq!(open_with(&original[..HEADER_LEN / 2]), Some(io::ErrorKind::InvalidData));
+    assert_eq!(open_with(&original), None);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+pub fn snapshot_widths_test(){
+    let small: WeightedGraph<u8, _, _, _, W16> = WeightedGraph::from_edges_with_width([1, 2, 3], [(0, 1, -2), (1, 2, 7)]).unwrap();
+    let medium: WeightedGraph<u8, _, _, _, W32> = WeightedGraph::from_edges_with_width([1, 2, 3], [(0, 1, -300), (1, 2, 7)]).unwrap();
+    let (small_path, medium_path) = (snapshot_path("width_16"), snapshot_path("width_32"));
+    small.graph.edge_storage.write_snapshot(&mut fs::File::create(&small_path).unwrap()).unwrap();
+    medium.graph.edge_storage.write_snapshot(&mut fs::File::create(&medium_path).unwrap()).unwrap();
+
+    let mapped = MappedEdgeStorage::<W16>::open_with_width(&small_path).unwrap();
+    assert_eq!(mapped.edges_as_slice(0), &[W16::pack(1, -2)]);
+    assert_eq!(mapped.weight(mapped.edges_index(0)), -2);
+    let mapped = MappedEdgeStorage::<W32>::open_with_width(&medium_path).unwrap();
+    assert_eq!(mapped.edges_as_slice(0), &[W32::pack(1, -300)]);
+    assert_eq!(mapped.to_edge_storage().edges_as_slice(1), medium.graph.edge_storage.edges_as_slice(1));
+
+    assert_eq!(MappedEdgeStorage::<W32>::open_with_width(&small_path).err().map(|error| error.kind()), Some(io::ErrorKind::InvalidData));
+    assert_eq!(MappedEdgeStorage::open(&medium_path).err().map(|error| error.kind()), Some(io::ErrorKind::InvalidData));
+    fs::remove_file(small_path).unwrap();
+    fs::remove_file(medium_path).unwrap();
+}
\ No newline at end of file
@@ -0,0 +1,129 @@
+use crate::graph::{Error, Graph};
+use crate::handles::pack;
+use crate::handles::types::{Edge, VHandle};
+use crate::traits::{EdgeStore, StoreVertex, WeightedEdgeConnect};
+use crate::transaction::Operation;
+use crate::weighted_graph::WeightedGraph;
+
+fn sorted_edges<Edges: EdgeStore>(edges: &Edges, handle: VHandle) -> Vec<Edge> {
+    let mut edges: Vec<_> = edges.edges_iter(handle).copied().collect();
+    edges.sort();
+    edges
+}
+
+#[test]
+pub fn transaction_rollback_test(){
+    let mut graph = WeightedGraph::new();
+    let a = graph.graph.create("a", 3);
+    let b = graph.create_and_connect_weighted(a, "b", 2, 1);
+    graph.graph.edge_storage.connect_weighted(a, b, 5);
+    let edges_a = sorted_edges(&graph.graph.edge_storage, a);
+
+    {
+        let mut transaction = graph.graph.transaction();
+        let c = transaction.create_and_connect(a, "c", 1);
+        transaction.connect_weighted(c, b, 7);
+        transaction.disconnect(a, b);
+        transaction.set_edge_weight(a, b, 9).unwrap();
+        assert_eq!(transaction.set_edge_weight(b, a, 1), Err(Error::NoEdge { from: b, to: a }));
+        transaction.disconnect(b, a);
+        assert_eq!(transaction.graph().vertices.len(), 3);
+        assert_eq!(transaction.log().len(), 5);
+    }
+    assert_eq!(graph.graph.vertices.len(), 2);
+    assert_eq!(sorted_edges(&graph.graph.edge_storage, a), edges_a);
+    assert!(graph.graph.edge_storage.edges_is_empty(b));
+
+    let mut transaction = graph.graph.transaction();
+    transaction.disconnect(a, b);
+    transaction.disconnect(a, b);
+    transaction.rollback();
+    assert_eq!(sorted_edges(&graph.graph.edge_storage, a), edges_a);
+}
+
+#[test]
+pub fn transaction_commit_test(){
+    let mut graph = Graph::new();
+    let a = graph.create("a", 1);
+
+    let mut transaction = graph.transaction();
+    let b = transaction.create_and_connect(a, "b", 0);
+    transaction.connect(b, b);
+    let log = transaction.commit();
+    assert_eq!(log, vec![
+        Operation::CreateVertex(b),
+        Operation::Connect { from: a, to: b, weight: 0 },
+        Operation::Connect { from: b, to: b, weight: 0 },
+    ]);
+    assert_eq!(graph.vertices.len(), 2);
+    assert_eq!(graph.edge_storage.edges_as_slice(a), &[pack(b, 0)]);
+
+    graph.undo(log);
+    assert_eq!(graph.vertices.len(), 1);
+    assert!(graph.edge_storage.edges_is_empty(a));
+}
+
+#[test]
+pub fn transaction_adjacency_matrix_test(){
+    let mut graph = WeightedGraph::with_adjacency_matrix(2);
+    let a = graph.graph.create("a", 0);
+    let b = graph.create_and_connect_weighted(a, "b", 3, 0);
+
+    let mut transaction = graph.graph.transaction();
+    transaction.connect_weighted(a, b, 8);
+    transaction.connect_weighted(b, a, 1);
+    assert_eq!(transaction.log()[0], Operation::SetWeight { from: a, to: b, previous: 3, weight: 8 });
+    drop(transaction);
+
+    assert_eq!(graph.edge_weight(a, b), Some(3));
+    assert!(!graph.has_edge(b, a));
+}
+
+#[test]
+pub fn transaction_edge_data_test(){
+    let mut graph = Graph::with_edge_data(0);
+    let a = graph.create("a", 1);
+    let b = graph.create("b", 0);
+    graph.edge_storage.connect_with_data(a, b, 2, "keep");
+
+    let mut transaction = graph.transaction();
+    transaction.connect_weighted(a, b, 2);
+    transaction.connect_weighted(a, b, 3);
+    drop(transaction);
+
+    assert_eq!(graph.edge_storage.edges_len(a), 1);
+    assert_eq!(graph.edge_storage.edge_data_as_slice(a), &["keep"]);
+}
+#[test]
+pub fn transaction_remove_vertex_test(){
+    let mut graph = WeightedGraph::new();
+    let a = graph.graph.create("a", 2);
+    let b = graph.create_and_connect_weighted(a, "b", 1, 3);
+    let c = graph.create_and_connect_weighted(b, "c", 2, 1);
+    let d = graph.create_and_connect_weighted(a, "d", 3, 3);
+    graph.graph.edge_storage.connect_weighted(b, a, 4);
+    graph.graph.edge_storage.connect_weighted(b, d, 5);
+    graph.graph.edge_storage.connect_weighted(d, b, 6);
+    graph.graph.edge_storage.connect_weighted(d, d, 7);
+    graph.graph.edge_storage.connect_weighted(c, d, 8);
+    let snapshot = |graph: &WeightedGraph<&str, _, _>| (0..4).map(|vertex| sorted_edges(&graph.graph.edge_storage, vertex)).collect::<Vec<_>>();
+    let before = snapshot(&graph);
+
+    let mut transaction = graph.graph.transaction();
+    let remap = transaction.remove_vertex(b);
+    assert_eq!(remap.moved, d);
+    transaction.connect_weighted(b, a, 9);
+    transaction.remove_vertex(c);
+    assert_eq!(transaction.graph().vertices.as_slice(), &["a", "d"]);
+    drop(transaction);
+    assert_eq!(graph.graph.vertices.as_slice(), &["a", "b", "c", "d"]);
+    assert_eq!(snapshot(&graph), before);
+
+    let mut transaction = graph.graph.transaction();
+    transaction.remove_vertex(d);
+    let log = transaction.commit();
+    assert_eq!(graph.graph.vertices.len(), 3);
+    graph.graph.undo(log);
+    assert_eq!(graph.graph.vertices.as_slice(), &["a", "b", "c", "d"]);
+    assert_eq!(snapshot(&graph), before);
+}
\ No newline at end of file
@@ -0,0 +1,113 @@
+use crate::algorithms::dfs_bfs::bfs;
+use crate::algorithms::dfs_bfs::ControlFlow::Resume;
+use crate::algorithms::path_finding::dijkstra;
+use crate::edge_storage::GrowthPolicy;
+use crate::graph::Error;
+use crate::handles::{pack, vh, vh_pack, W16, W32};
+use crate::traits::{EdgeStore, StoreVertex};
+use crate::undirected_graph::UndirectedGraph;
+
+#[test]
+pub fn undirected_connect_test(){
+    let mut graph = UndirectedGraph::new();
+    let a = graph.create("a", 2);
+    let b = graph.create("b", 2);
+    let c = graph.create("c", 2);
+    graph.connect_weighted(a, b, 3);
+    graph.connect_weighted(b, c, 4);
+    graph.connect(c, c);
+
+    assert_eq!(graph.graph.edge_storage.edges_as_slice(a), &[pack(b, 3)]);
+    assert_eq!(graph.graph.edge_storage.edges_as_slice(b), &[pack(a, 3), pack(c, 4)]);
+    assert_eq!(graph.graph.edge_storage.edges_as_slice(c), &[pack(b, 4), pack(c, 0)]);
+    assert_eq!(graph.edges().collect::<Vec<_>>(), vec![(a, b, 3), (b, c, 4), (c, c, 0)]);
+    assert_eq!(graph.edge_count(), 3);
+
+    graph.disconnect(c, b);
+    graph.disconnect(c, c);
+    assert_eq!(graph.degree(b), 1);
+    assert_eq!(graph.degree(c), 0);
+    assert_eq!(graph.edges().collect::<Vec<_>>(), vec![(a, b, 3)]);
+}
+
+#[test]
+pub fn undirected_parallel_disconnect_test(){
+    let mut graph = UndirectedGraph::new();
+    let a = graph.create("a", 2);
+    let b = graph.create("b", 2);
+    graph.connect_weighted(a, b, 1);
+    graph.connect_weighted(a, b, 2);
+
+    graph.graph.edge_storage.edges_as_mut_slice(a).swap(0, 1);
+    graph.disconnect(a, b);
+    assert_eq!(graph.graph.edge_storage.edges_as_slice(a), &[pack(b, 1)]);
+    assert_eq!(graph.graph.edge_storage.edges_as_slice(b), &[pack(a, 1)]);
+}
+
+#[test]
+pub fn undirected_try_connect_rollback_test(){
+    let mut graph = UndirectedGraph::new();
+    let a = graph.create("a", 2);
+    let b = graph.create("b", 1);
+    graph.connect_weighted(a, b, 1);
+    graph.graph.edge_storage.set_growth_policy(GrowthPolicy::Strict);
+
+    assert!(matches!(graph.try_connect_weighted(a, b, 2), Err(Error::CapacityExceeded{..})));
+    assert_eq!(graph.graph.edge_storage.edges_as_slice(a), &[pack(b, 1)]);
+    assert_eq!(graph.graph.edge_storage.edges_as_slice(b), &[pack(a, 1)]);
+}
+
+#[test]
+pub fn undirected_algorithms_test(){
+    let mut graph = UndirectedGraph::new();
+    let a = graph.create("a", 2);
+    let b = graph.create_and_connect_weighted(a, "b", 1, 2);
+    let c = graph.create_and_connect_weighted(b, "c", 1, 2);
+    let d = graph.create_and_connect_weighted(c, "d", 5, 1);
+    graph.connect_weighted(a, c, 5);
+
+    let mut visited = Vec::new();
+    bfs(&mut graph.graph.edge_storage, vh_pack(d), graph.graph.vertices.len(), |edge, _| {
+        visited.push(graph.graph.vertices[vh(*edge)]);
+        Resume
+    });
+    assert_eq!(visited, vec!["d", "c", "b", "a"]);
+
+    let mut path = dijkstra(&mut graph.graph.edge_storage, d, a, graph.graph.vertices.len()).unwrap();
+    let mut values = Vec::new();
+    while let Some(handle) = path.pop() {
+        values.push(graph.graph.vertices[handle]);
+    }
+    assert_eq!(values, vec!["d", "c", "b", "a"]);
+}
+
+#[test]
+pub fn undirected_edge_weight_test(){
+    let mut graph = UndirectedGraph::new();
+    let a = graph.create("a", 1);
+    let b = graph.create("b", 1);
+    graph.connect_weighted(a, b, 3);
+
+    assert!(graph.has_edge(b, a));
+    assert_eq!(graph.set_edge_weight(b, a, 8), Ok(()));
+    assert_eq!(graph.edge_weight(a, b), Some(8));
+    assert_eq!(graph.edge_weight(b, a), Some(8));
+}
+
+#[test]
+pub fn undirected_widths_test(){
+    let mut small: UndirectedGraph<&str, _, _, W16> = UndirectedGraph::with_width(2);
+    let mut medium: UndirectedGraph<&str, _, _, W32> = UndirectedGraph::with_width(2);
+    let (a, b) = (small.create("a", 2), small.create("b", 2));
+    let (c, d) = (medium.create("c", 2), medium.create("d", 2));
+    assert_eq!(small.try_connect_weighted(a, b, 300), Err(Error::WeightOutOfRange));
+    small.try_connect_weighted(a, b, -3).unwrap();
+    medium.try_connect_weighted(c, d, 300).unwrap();
+
+    assert_eq!(small.edges().collect::<Vec<_>>(), vec![(a, b, -3)]);
+    assert_eq!(medium.edges().collect::<Vec<_>>(), vec![(c, d, 300)]);
+    assert_eq!(small.edge_weight(b, a), Some(-3));
+    assert_eq!(medium.edge_weight(d, c), Some(300));
+    small.disconnect(b, a);
+    assert_eq!((small.edge_count(), medium.edge_count()), (0, 1));
+}
\ No newline at end of file
@@ -0,0 +1,11 @@
+use eta_algorithms::data_structs::stack::Stack;
+use crate::handles::types::VHandle;
+
+/// Pops the path returned by the path finding into a `Vec`, from the start to the target.
+pub(crate) fn collect_path(mut path: Stack<VHandle>) -> Vec<VHandle> {
+    let mut handles = Vec::new();
+    while let Some(handle) = path.pop() {
+        handles.push(handle);
+    }
+    handles
+}
\ No newline at end of file
@@ -1,48 +1,239 @@
 use std::ops::{Index, IndexMut};
 use eta_algorithms::data_structs::fat_ptr::{FatPtr, FatPtrMut};
-use crate::handles::types::{Edge, VHandle, Weight, Ci};
+use crate::graph::Error;
+use crate::handles::{GHandle, Generation, Width, W64};
+use crate::handles::types::{VHandle, Ci};
 
 pub trait StoreVertex: Index<VHandle, Output=Self::VertexType> + IndexMut<VHandle, Output=Self::VertexType>{
     type VertexType;
     fn is_empty(&self) -> bool;
     fn len(&self) -> usize;
     fn push(&mut self, val: Self::VertexType);
+    /// Removes the vertex by moving the last vertex into its slot.
+    fn swap_remove(&mut self, handle: VHandle) -> Self::VertexType;
     fn capacity(&self) -> usize;
     fn iter(&self) -> std::slice::Iter<Self::VertexType>;
     fn iter_mut(&mut self) -> std::slice::IterMut<Self::VertexType>;
     fn as_slice(&self) -> &[Self::VertexType];
 }
 
-pub trait EdgeConnect {
-    fn connect_edges(&mut self, src: VHandle, targets: &[Edge]);
-    fn disconnect(&mut self, src_handle: VHandle, handle: VHandle);
-    fn connect(&mut self, from: VHandle, to: VHandle);
+/// Storage which tracks slot generations and can validate [GHandle]s.
+pub trait Generational<W: Width = W64> {
+    /// Returns the current generation of the slot, or `None` if the handle is out of range.
+    fn generation(&self, handle: W::VHandle) -> Option<Generation>;
+
+    /// Pairs the handle with the current generation of its slot.
+    #[inline(always)]
+    fn generational(&self, handle: W::VHandle) -> Result<GHandle<W::VHandle>, Error> {
+        match self.generation(handle) {
+            Some(generation) => Ok(GHandle { handle, generation }),
+            None => Err(Error::InvalidHandle(handle.into())),
+        }
+    }
+
+    /// Returns the generation counter of the slot even if it is vacant, or `None` if the slot never existed.
+    #[inline(always)]
+    fn slot_generation(&self, handle: W::VHandle) -> Option<Generation> {
+        self.generation(handle)
+    }
+
+    /// Returns the plain handle if the generational handle still refers to a live vertex.
+    /// Generations are compared first, so the handle of a vertex moved out of its slot is stale, not invalid.
+    #[inline(always)]
+    fn validate(&self, handle: GHandle<W::VHandle>) -> Result<W::VHandle, Error> {
+        if self.slot_generation(handle.handle).is_some_and(|generation| generation != handle.generation) {
+            return Err(Error::StaleHandle);
+        }
+        match self.generation(handle.handle) {
+            Some(_) => Ok(handle.handle),
+            None => Err(Error::InvalidHandle(handle.handle.into())),
+        }
+    }
 }
 
-pub trait WeightedEdgeConnect {
-    fn connect_weighted(&mut self, from: VHandle, to: VHandle, weight: Weight);
+pub trait EdgeConnect<W: Width = W64> {
+    fn connect_edges(&mut self, src: W::VHandle, targets: &[W::Edge]);
+    /// Removes the first edge `src_handle -> handle`, returns whether there was one.
+    fn disconnect(&mut self, src_handle: W::VHandle, handle: W::VHandle) -> bool;
+    fn connect(&mut self, from: W::VHandle, to: W::VHandle);
+    fn try_connect_edges(&mut self, src: W::VHandle, targets: &[W::Edge]) -> Result<(), Error>;
+    /// Fails with [Error::NoEdge] if there is no edge to disconnect.
+    fn try_disconnect(&mut self, src_handle: W::VHandle, handle: W::VHandle) -> Result<(), Error>;
+    fn try_connect(&mut self, from: W::VHandle, to: W::VHandle) -> Result<(), Error>;
 }
 
-pub trait EdgeStore: Index<usize, Output=Edge> + IndexMut<usize, Output=Edge>{
-    fn create_vertex_entry(&mut self, size: Ci) -> VHandle;
-    fn edges_as_slice(&self, handle: VHandle) -> &[Edge];
-    fn edges_as_mut_slice(&mut self, handle: VHandle) -> &mut [Edge];
-    fn edges_as_ptr(&self, handle: VHandle) -> FatPtr<Edge>;
-    fn edges_as_mut_ptr(&mut self, handle: VHandle) -> FatPtrMut<Edge>;
-    fn edges_is_empty(&self, handle: VHandle) -> bool;
-    fn edges_len(&self, handle: VHandle) -> usize;
-    fn edges_capacity(&self, handle: VHandle) -> usize;
-    fn edges_index(&self, handle: VHandle) -> usize;
-    fn iter(&self) -> impl Iterator<Item=&Edge>;
-    fn iter_mut (&mut self) -> impl Iterator<Item=&mut Edge>;
-    fn edges_iter(&self, handle: VHandle) -> impl Iterator<Item=&Edge>;
-    fn edges_iter_mut(&mut self, handle: VHandle) -> impl Iterator<Item=&mut Edge>;
+pub trait WeightedEdgeConnect<W: Width = W64> {
+    fn connect_weighted(&mut self, from: W::VHandle, to: W::VHandle, weight: W::Weight);
+    /// Accepts any integer type, fails with [Error::WeightOutOfRange] if it does not fit into the weight of the width.
+    fn try_connect_weighted<T: TryInto<W::Weight>>(&mut self, from: W::VHandle, to: W::VHandle, weight: T) -> Result<(), Error>;
+}
+
+/// Block per vertex of packed edges. `W` is the width of the edges, see [Width].
+pub trait EdgeStore<W: Width = W64>: Index<usize, Output=W::Edge> + IndexMut<usize, Output=W::Edge>{
+    fn create_vertex_entry(&mut self, size: Ci) -> W::VHandle;
+    fn try_create_vertex_entry(&mut self, size: Ci) -> Result<W::VHandle, Error>;
+    /// Removes the entry of `handle` together with every edge pointing at it.
+    /// The last entry is moved into the freed slot and edges pointing at it are retargeted.
+    /// Returns the previous handle of the moved entry, or `NONE` if nothing was moved.
+    fn remove_vertex_entry(&mut self, handle: W::VHandle) -> W::VHandle;
+    /// Number of vertex entries, handles `0..vertices_len()` are valid.
+    fn vertices_len(&self) -> usize;
+    fn edges_as_slice(&self, handle: W::VHandle) -> &[W::Edge];
+    fn edges_as_mut_slice(&mut self, handle: W::VHandle) -> &mut [W::Edge];
+    fn edges_as_ptr(&self, handle: W::VHandle) -> FatPtr<W::Edge>;
+    fn edges_as_mut_ptr(&mut self, handle: W::VHandle) -> FatPtrMut<W::Edge>;
+    fn edges_is_empty(&self, handle: W::VHandle) -> bool;
+    fn edges_len(&self, handle: W::VHandle) -> usize;
+    fn edges_capacity(&self, handle: W::VHandle) -> usize;
+    fn edges_index(&self, handle: W::VHandle) -> usize;
+    fn iter(&self) -> impl Iterator<Item=&W::Edge>;
+    fn iter_mut (&mut self) -> impl Iterator<Item=&mut W::Edge>;
+    fn edges_iter(&self, handle: W::VHandle) -> impl Iterator<Item=&W::Edge>;
+    fn edges_iter_mut(&mut self, handle: W::VHandle) -> impl Iterator<Item=&mut W::Edge>;
 
     /// Extremely unsafe function. Use when you know what you are doing. Recommended to use if you dynamically
     /// want multiple non-overlapping iterators to edges.
     /// # Safety
     /// when using non-overlapping iterators
-    unsafe fn edges_iter_mut_unchecked(&mut self, handle: VHandle) -> impl Iterator<Item=&mut Edge>;
+    unsafe fn edges_iter_mut_unchecked(&mut self, handle: W::VHandle) -> impl Iterator<Item=&mut W::Edge>;
+
+    /// Global indices of the edges of `handle`, usable with the storage indexing and [WeightStore].
+    /// Views which hide edges skip their indices, so algorithms should prefer this over `edges_index..edges_index + edges_len`.
+    #[inline(always)]
+    fn edge_indices(&self, handle: W::VHandle) -> impl Iterator<Item=usize> {
+        let index = self.edges_index(handle);
+        index..index + self.edges_len(handle)
+    }
+
+    /// First index of [EdgeStore::edge_indices] at or after `index`. Lets traversals like `dfs` walk a block
+    /// through the storage indexing without holding a borrow of the storage.
+    #[inline(always)]
+    fn next_edge_index(&self, handle: W::VHandle, index: usize) -> Option<usize> {
+        match index < self.edges_index(handle) + self.edges_len(handle) {
+            true => Some(index),
+            false => None,
+        }
+    }
+
+    /// Every packed edge of the storage together with its source, ordered by source. See [WeightStore::edges]
+    /// for the `(source, target, weight)` view.
+    fn edges_mut(&mut self) -> impl Iterator<Item=(W::VHandle, &mut W::Edge)> {
+        let storage: *mut Self = self;
+        (0..self.vertices_len()).map(W::handle).flat_map(move |from| {
+            // Safety: every vertex is visited once, so the block iterators don't overlap
+            unsafe { (*storage).edges_iter_mut_unchecked(from) }.map(move |edge| (from, edge))
+        })
+    }
+
+    /// Returns the index of the first edge `from -> to`. The index can be used with the storage indexing.
+    #[inline(always)]
+    fn find_edge(&self, from: W::VHandle, to: W::VHandle) -> Option<usize> {
+        let position = self.edges_as_slice(from).iter().position(|edge| W::vh(*edge) == to)?;
+        Some(self.edges_index(from) + position)
+    }
+
+    #[inline(always)]
+    fn has_edge(&self, from: W::VHandle, to: W::VHandle) -> bool {
+        self.find_edge(from, to).is_some()
+    }
+
+    /// Returns the weight of the first edge `from -> to`.
+    #[inline(always)]
+    fn edge_weight(&self, from: W::VHandle, to: W::VHandle) -> Option<W::Weight> {
+        self.find_edge(from, to).map(|index| W::wgt(self[index]))
+    }
+
+    /// Sets the weight of the first edge `from -> to`.
+    fn set_edge_weight(&mut self, from: W::VHandle, to: W::VHandle, weight: W::Weight) -> Result<(), Error> {
+        let index = self.find_edge(from, to).ok_or(Error::NoEdge { from: from.into(), to: to.into() })?;
+        self[index] = W::set_wgt(self[index], weight);
+        Ok(())
+    }
+}
+/// Weight usable by the path finding and flow algorithms.
+pub trait EdgeWeight: Copy + PartialOrd {
+    fn zero() -> Self;
+    /// Largest value, used for unreached vertices and unlimited capacities.
+    fn infinity() -> Self;
+    /// Addition which saturates at [EdgeWeight::infinity].
+    fn plus(self, other: Self) -> Self;
+    /// Subtraction which saturates instead of overflowing.
+    fn minus(self, other: Self) -> Self;
+}
+
+macro_rules! edge_weight_int_impl {
+    ($($type:ty),*) => {
+        $(impl EdgeWeight for $type {
+            #[inline(always)]
+            fn zero() -> Self { 0 }
+            #[inline(always)]
+            fn infinity() -> Self { <$type>::MAX }
+            #[inline(always)]
+            fn plus(self, other: Self) -> Self { self.saturating_add(other) }
+            #[inline(always)]
+            fn minus(self, other: Self) -> Self { self.saturating_sub(other) }
+        })*
+    };
+}
+
+macro_rules! edge_weight_float_impl {
+    ($($type:ty),*) => {
+        $(impl EdgeWeight for $type {
+            #[inline(always)]
+            fn zero() -> Self { 0.0 }
+            #[inline(always)]
+            fn infinity() -> Self { <$type>::INFINITY }
+            #[inline(always)]
+            fn plus(self, other: Self) -> Self { self + other }
+            #[inline(always)]
+            fn minus(self, other: Self) -> Self { self - other }
+        })*
+    };
+}
+
+edge_weight_int_impl!(i8, i16, i32, i64, u8, u16, u32, u64, usize);
+edge_weight_float_impl!(f32, f64);
+
+/// Edge storage exposing a weight for every edge, addressed by the global edge index, see [EdgeStore::edges_index].
+pub trait WeightStore<W: Width = W64>: EdgeStore<W> {
+    type WeightType: EdgeWeight;
+    fn weight(&self, index: usize) -> Self::WeightType;
+    fn set_weight(&mut self, index: usize, weight: Self::WeightType);
+
+    /// Every edge of the storage as `(source, target, weight)`, ordered by source, with the weight from [WeightStore::weight].
+    /// Targets are not filtered, tree headers and other placeholders show up as `NONE`.
+    #[inline(always)]
+    fn edges(&self) -> impl Iterator<Item=(W::VHandle, W::VHandle, Self::WeightType)> {
+        (0..self.vertices_len()).map(W::handle)
+            .flat_map(move |from| self.edge_indices(from).map(move |index| (from, W::vh(self[index]), self.weight(index))))
+    }
+}
+
+/// Where a storage with edge payloads reads the weight of an edge from, see [Packed](crate::edge_storage::Packed)
+/// and [Payload](crate::edge_storage::Payload).
+pub trait WeightSource<EdgeType, W: Width = W64> {
+    type WeightType: EdgeWeight;
+    fn weight(edge: W::Edge, data: &EdgeType) -> Self::WeightType;
+    fn set_weight(edge: &mut W::Edge, data: &mut EdgeType, weight: Self::WeightType);
+}
+
+/// Edge storage whose blocks take any edge, including [NONE](crate::handles::NONE) targets and targets
+/// rewritten through [IndexMut]. [Tree](crate::views::tree::Tree) keeps the root and parent of a node in the first edges of its block.
+pub trait PlaceholderEdges<W: Width = W64>: EdgeStore<W> {}
+
+pub trait EdgeManipulate<W: Width = W64>: EdgeStore<W> + EdgeConnect<W> + Clone{
+    /// Removes the last edge of `from` equal to the packed `edge`, which is the latest connected one unless the block
+    /// was reordered. The default moves it in front of the other edges to its target and disconnects, storages
+    /// with edge payloads override it to move the payloads too.
+    fn disconnect_last(&mut self, from: W::VHandle, edge: W::Edge) {
+        let len = self.edges_len(from);
+        let edges = &mut self.edges_as_mut_slice(from)[..len];
+        let first = edges.iter().position(|existing| W::vh(*existing) == W::vh(edge));
+        let last = edges.iter().rposition(|existing| *existing == edge);
+        if let (Some(first), Some(last)) = (first, last) {
+            edges.swap(first, last);
+            self.disconnect(from, W::vh(edge));
+        }
+    }
 }
-pub trait EdgeManipulate: EdgeStore + EdgeConnect + Clone{}
-pub trait WeightedEdgeManipulate: EdgeManipulate + WeightedEdgeConnect {}
\ No newline at end of file
+pub trait WeightedEdgeManipulate<W: Width = W64>: EdgeManipulate<W> + WeightedEdgeConnect<W> {}
\ No newline at end of file
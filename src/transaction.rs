@@ -0,0 +1,225 @@
+use std::mem::take;
+use crate::graph::{Error, Graph, VertexRemap};
+use crate::handles::types::Ci;
+use crate::handles::{Width, W64};
+use crate::observer::GraphObserver;
+use crate::traits::{EdgeWeight, StoreVertex, WeightedEdgeManipulate};
+
+/// Entry of the undo log of a [Transaction], with the handles and weights of the edge width `W`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation<VertexType, W: Width = W64> {
+    CreateVertex(W::VHandle),
+    Connect { from: W::VHandle, to: W::VHandle, weight: W::Weight },
+    Disconnect { from: W::VHandle, to: W::VHandle, weight: W::Weight },
+    SetWeight { from: W::VHandle, to: W::VHandle, previous: W::Weight, weight: W::Weight },
+    /// The removed vertex with its edges and the edges pointing at it, `moved` as in [VertexRemap].
+    RemoveVertex { handle: W::VHandle, value: VertexType, edges: Vec<W::Edge>, incoming: Vec<(W::VHandle, W::Edge)>, moved: W::VHandle },
+}
+
+/// Edges of the other vertices pointing at `handle`.
+fn incoming_edges<EdgeStorageType: WeightedEdgeManipulate<W>, W: Width>(edge_storage: &EdgeStorageType, handle: W::VHandle) -> Vec<(W::VHandle, W::Edge)> {
+    let mut incoming = Vec::new();
+    for from in (0..edge_storage.vertices_len()).map(W::handle).filter(|from| *from != handle) {
+        incoming.extend(edge_storage.edges_iter(from).filter(|edge| W::vh(**edge) == handle).map(|edge| (from, *edge)));
+    }
+    incoming
+}
+
+impl<VertexType, W: Width> Operation<VertexType, W> {
+    /// Applies the inverse of the operation. Observers see the inverse operations.
+    pub(crate) fn revert<VertexStorageType, EdgeStorageType, Observer>(self, graph: &mut Graph<VertexType, VertexStorageType, EdgeStorageType, Observer, W>)
+    where
+        VertexStorageType: StoreVertex<VertexType=VertexType>,
+        EdgeStorageType: WeightedEdgeManipulate<W>,
+        Observer: GraphObserver<VertexType, W>,
+    {
+        let connect = |graph: &mut Graph<VertexType, VertexStorageType, EdgeStorageType, Observer, W>, from: W::VHandle, edge: W::Edge| {
+            graph.edge_storage.connect_weighted(from, W::vh(edge), W::wgt(edge));
+            graph.observer.connected(from, W::vh(edge), W::wgt(edge));
+        };
+        match self {
+            Operation::CreateVertex(handle) => {
+                graph.remove_vertex(handle);
+            }
+            Operation::Connect { from, to, weight } => {
+                let len = graph.edge_storage.edges_len(from);
+                graph.edge_storage.disconnect_last(from, W::pack(to, weight));
+                if graph.edge_storage.edges_len(from) < len {
+                    graph.observer.disconnected(from, to);
+                }
+            }
+            Operation::Disconnect { from, to, weight } => {
+                graph.edge_storage.connect_weighted(from, to, weight);
+                graph.observer.connected(from, to, weight);
+            }
+            Operation::SetWeight { from, to, previous, .. } => {
+                if graph.edge_storage.set_edge_weight(from, to, previous).is_ok() {
+                    graph.observer.weight_changed(from, to, previous);
+                }
+            }
+            Operation::RemoveVertex { handle, value, edges, incoming, moved } => {
+                if moved == W::NONE {
+                    graph.create(value, edges.len() as Ci);
+                } else {
+                    // The restored vertex is created last and takes the handle over by removing the moved vertex,
+                    // which is then created again at its old handle with its edges
+                    let last = moved;
+                    let moved_edges: Vec<W::Edge> = graph.edge_storage.edges_iter(handle)
+                        .map(|edge| if W::vh(*edge) == handle { W::set_vh(*edge, last) } else { *edge })
+                        .collect();
+                    let moved_incoming = incoming_edges(&graph.edge_storage, handle);
+                    graph.create(value, edges.len() as Ci);
+                    let (moved_value, _) = graph.take_vertex(handle);
+                    graph.create(moved_value, moved_edges.len() as Ci);
+                    for edge in moved_edges {
+                        connect(graph, last, edge);
+                    }
+                    for (from, edge) in moved_incoming {
+                        connect(graph, from, W::set_vh(edge, last));
+                    }
+                }
+                for edge in edges {
+                    connect(graph, handle, edge);
+                }
+                for (from, edge) in incoming {
+                    connect(graph, from, edge);
+                }
+            }
+        }
+    }
+}
+
+/// Batch of changes made through [Graph::transaction]. Every change is recorded in an undo log and reverted
+/// when the transaction is dropped without [Transaction::commit], newest first.
+///
+/// Rolling back restores the vertices and edges, but not the order of the edges within a block, the edge
+/// data of disconnected [crate::edge_storage::EdgeStorage] edges or changes made directly on the storages.
+/// A removed vertex gets its handle back, but generational handles of it and of the vertex moved into its
+/// handle go stale.
+pub struct Transaction<'a, VertexType, VertexStorageType, EdgeStorageType, Observer, W: Width = W64>
+where
+    VertexStorageType: StoreVertex<VertexType=VertexType>,
+    EdgeStorageType: WeightedEdgeManipulate<W>,
+    Observer: GraphObserver<VertexType, W>,
+    W: Width,
+{
+    graph: &'a mut Graph<VertexType, VertexStorageType, EdgeStorageType, Observer, W>,
+    log: Vec<Operation<VertexType, W>>,
+    committed: bool,
+}
+
+impl<'a, VertexType, VertexStorageType, EdgeStorageType, Observer, W> Transaction<'a, VertexType, VertexStorageType, EdgeStorageType, Observer, W>
+where
+    VertexStorageType: StoreVertex<VertexType=VertexType>,
+    EdgeStorageType: WeightedEdgeManipulate<W>,
+    Observer: GraphObserver<VertexType, W>,
+    W: Width,
+{
+    pub fn new(graph: &'a mut Graph<VertexType, VertexStorageType, EdgeStorageType, Observer, W>) -> Self {
+        Transaction {
+            graph,
+            log: Vec::new(),
+            committed: false,
+        }
+    }
+
+    /// The graph with the changes made so far, for validating the batch.
+    #[inline(always)]
+    pub fn graph(&self) -> &Graph<VertexType, VertexStorageType, EdgeStorageType, Observer, W> {
+        self.graph
+    }
+
+    #[inline(always)]
+    pub fn log(&self) -> &[Operation<VertexType, W>] {
+        &self.log
+    }
+
+    /// Keeps the changes and returns the undo log, which [Graph::undo] reverts.
+    pub fn commit(mut self) -> Vec<Operation<VertexType, W>> {
+        self.committed = true;
+        take(&mut self.log)
+    }
+
+    /// Reverts the changes, the same as dropping the transaction.
+    #[inline(always)]
+    pub fn rollback(self) {}
+
+    pub fn create(&mut self, val: VertexType, edge_count: Ci) -> W::VHandle {
+        let handle = self.graph.create(val, edge_count);
+        self.log.push(Operation::CreateVertex(handle));
+        handle
+    }
+
+    /// See [Graph::try_create].
+    pub fn try_create(&mut self, val: VertexType, edge_count: Ci) -> Result<W::VHandle, Error> {
+        let handle = self.graph.try_create(val, edge_count)?;
+        self.log.push(Operation::CreateVertex(handle));
+        Ok(handle)
+    }
+
+    pub fn create_and_connect(&mut self, from: W::VHandle, val: VertexType, edge_count: Ci) -> W::VHandle {
+        let handle = self.create(val, edge_count);
+        self.connect(from, handle);
+        handle
+    }
+
+    #[inline(always)]
+    pub fn connect(&mut self, from: W::VHandle, to: W::VHandle) {
+        self.connect_weighted(from, to, W::Weight::zero());
+    }
+
+    /// Storages which keep a single edge per pair overwrite the weight, which is logged as [Operation::SetWeight].
+    pub fn connect_weighted(&mut self, from: W::VHandle, to: W::VHandle, weight: W::Weight) {
+        let len = self.graph.edge_storage.edges_len(from);
+        let previous = self.graph.edge_storage.edge_weight(from, to);
+        self.graph.edge_storage.connect_weighted(from, to, weight);
+        self.graph.observer.connected(from, to, weight);
+        match previous {
+            Some(previous) if self.graph.edge_storage.edges_len(from) == len => {
+                self.log.push(Operation::SetWeight { from, to, previous, weight });
+            }
+            _ => self.log.push(Operation::Connect { from, to, weight }),
+        }
+    }
+
+    /// Removes the first edge `from -> to`, does nothing if there is none.
+    pub fn disconnect(&mut self, from: W::VHandle, to: W::VHandle) {
+        let removed = self.graph.edge_storage.edges_iter(from).find(|edge| W::vh(**edge) == to).copied();
+        if let Some(edge) = removed {
+            self.graph.disconnect(from, to);
+            self.log.push(Operation::Disconnect { from, to, weight: W::wgt(edge) });
+        }
+    }
+
+    /// See [Graph::remove_vertex]. The value and the edges of the vertex are kept in the log.
+    pub fn remove_vertex(&mut self, handle: W::VHandle) -> VertexRemap<W> {
+        let edges = self.graph.edge_storage.edges_iter(handle).copied().collect();
+        let incoming = incoming_edges(&self.graph.edge_storage, handle);
+        let (value, remap) = self.graph.take_vertex(handle);
+        self.log.push(Operation::RemoveVertex { handle, value, edges, incoming, moved: remap.moved });
+        remap
+    }
+
+    pub fn set_edge_weight(&mut self, from: W::VHandle, to: W::VHandle, weight: W::Weight) -> Result<(), Error> {
+        let previous = self.graph.edge_storage.edge_weight(from, to).ok_or(Error::NoEdge { from: from.into(), to: to.into() })?;
+        self.graph.edge_storage.set_edge_weight(from, to, weight)?;
+        self.graph.observer.weight_changed(from, to, weight);
+        self.log.push(Operation::SetWeight { from, to, previous, weight });
+        Ok(())
+    }
+}
+
+impl<'a, VertexType, VertexStorageType, EdgeStorageType, Observer, W> Drop for Transaction<'a, VertexType, VertexStorageType, EdgeStorageType, Observer, W>
+where
+    VertexStorageType: StoreVertex<VertexType=VertexType>,
+    EdgeStorageType: WeightedEdgeManipulate<W>,
+    Observer: GraphObserver<VertexType, W>,
+    W: Width,
+{
+    fn drop(&mut self) {
+        if !self.committed {
+            let log = take(&mut self.log);
+            self.graph.undo(log);
+        }
+    }
+}
\ No newline at end of file
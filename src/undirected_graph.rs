@@ -0,0 +1,179 @@
+use crate::edge_storage::EdgeStorage;
+use crate::graph::{Error, Graph};
+use crate::handles::types::Ci;
+use crate::handles::{Width, W64};
+use crate::observer::NoObserver;
+use crate::traits::{EdgeWeight, StoreVertex, WeightedEdgeManipulate};
+use crate::vertex_storage::VertexStorage;
+
+/// Undirected graph on top of the directed storage. Every edge `a - b` is stored as the two directed edges
+/// `a -> b` and `b -> a` with the same weight, self loops are stored once.
+/// The edge storage can be passed directly to `bfs`, `dfs` and `dijkstra`. `W` is the edge width, see [Graph].
+pub struct UndirectedGraph<VertexType, VertexStorageType, EdgeStorageType, W: Width = W64>
+where
+    EdgeStorageType: WeightedEdgeManipulate<W>,
+    VertexStorageType: StoreVertex<VertexType=VertexType>
+{
+    pub graph: Graph<VertexType, VertexStorageType, EdgeStorageType, NoObserver, W>,
+}
+
+impl<VertexType, VertexStorageType, EdgeStorageType, W> Clone for UndirectedGraph<VertexType, VertexStorageType, EdgeStorageType, W>
+where
+    EdgeStorageType: WeightedEdgeManipulate<W>,
+    VertexType: Clone,
+    VertexStorageType: StoreVertex<VertexType=VertexType> + Clone,
+    W: Width {
+    fn clone(&self) -> Self {
+        UndirectedGraph{
+            graph: self.graph.clone(),
+        }
+    }
+}
+
+impl<VertexType, W: Width> Default for UndirectedGraph<VertexType, VertexStorage<VertexType>, EdgeStorage<(), W>, W> {
+    fn default() -> Self {
+        Self::with_width(0)
+    }
+}
+
+impl<VertexType> UndirectedGraph<VertexType, VertexStorage<VertexType>, EdgeStorage>
+{
+    pub fn new() -> Self {
+        UndirectedGraph{
+            graph: Graph::new(),
+        }
+    }
+    pub fn new_large() -> Self {
+        UndirectedGraph{
+            graph: Graph::new_large(),
+        }
+    }
+    pub fn with_reserve(reserve: Ci) -> Self {
+        UndirectedGraph{
+            graph: Graph::with_reserve(reserve),
+        }
+    }
+}
+
+impl<VertexType, W: Width> UndirectedGraph<VertexType, VertexStorage<VertexType>, EdgeStorage<(), W>, W>
+{
+    /// See [Graph::with_width].
+    pub fn with_width(reserve: Ci) -> Self {
+        UndirectedGraph{
+            graph: Graph::with_width(reserve),
+        }
+    }
+}
+
+impl<VertexType, StoreVertexType, EdgeStorageType, W> UndirectedGraph<VertexType, StoreVertexType, EdgeStorageType, W>
+where
+    EdgeStorageType: WeightedEdgeManipulate<W>,
+    StoreVertexType: StoreVertex<VertexType=VertexType>,
+    W: Width {
+    /// `edge_count` is the expected degree of the vertex.
+    #[inline(always)]
+    pub fn create(&mut self, val: VertexType, edge_count: Ci) -> W::VHandle {
+        self.graph.create(val, edge_count)
+    }
+
+    pub fn create_and_connect_weighted(&mut self, src_vertex: W::VHandle, val: VertexType, weight: W::Weight, edge_count: Ci) -> W::VHandle {
+        let new_vertex = self.graph.create(val, edge_count);
+        self.connect_weighted(src_vertex, new_vertex, weight);
+        new_vertex
+    }
+
+    #[inline(always)]
+    pub fn connect(&mut self, a: W::VHandle, b: W::VHandle) {
+        self.connect_weighted(a, b, W::Weight::zero());
+    }
+
+    pub fn connect_weighted(&mut self, a: W::VHandle, b: W::VHandle, weight: W::Weight) {
+        self.graph.edge_storage.connect_weighted(a, b, weight);
+        if a != b {
+            self.graph.edge_storage.connect_weighted(b, a, weight);
+        }
+    }
+
+    /// Connects both directions or neither.
+    pub fn try_connect_weighted<T: TryInto<W::Weight>>(&mut self, a: W::VHandle, b: W::VHandle, weight: T) -> Result<(), Error> {
+        let weight = weight.try_into().map_err(|_| Error::WeightOutOfRange)?;
+        self.graph.edge_storage.try_connect_weighted(a, b, weight)?;
+        if a == b {
+            return Ok(());
+        }
+        if let Err(error) = self.graph.edge_storage.try_connect_weighted(b, a, weight) {
+            self.graph.edge_storage.disconnect_last(a, W::pack(b, weight));
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// Removes one edge between the vertices. If there are parallel edges, the reverse direction
+    /// with the same weight is removed, so both directions stay consistent.
+    pub fn disconnect(&mut self, a: W::VHandle, b: W::VHandle) {
+        let edges = &mut self.graph.edge_storage;
+        let weight = match edges.edges_as_slice(a).iter().find(|edge| W::vh(**edge) == b) {
+            Some(edge) => W::wgt(*edge),
+            None => return,
+        };
+        edges.disconnect(a, b);
+        if a == b {
+            return;
+        }
+
+        // disconnect removes the first edge to `a`, so move the one with the matching weight to the front
+        let len = edges.edges_len(b);
+        let reverse = &mut edges.edges_as_mut_slice(b)[..len];
+        let first = reverse.iter().position(|edge| W::vh(*edge) == a);
+        let matching = reverse.iter().position(|edge| W::vh(*edge) == a && W::wgt(*edge) == weight);
+        if let (Some(first), Some(matching)) = (first, matching) {
+            reverse.swap(first, matching);
+        }
+        edges.disconnect(b, a);
+    }
+
+    #[inline(always)]
+    pub fn has_edge(&self, a: W::VHandle, b: W::VHandle) -> bool {
+        self.graph.edge_storage.has_edge(a, b)
+    }
+
+    #[inline(always)]
+    pub fn edge_weight(&self, a: W::VHandle, b: W::VHandle) -> Option<W::Weight> {
+        self.graph.edge_storage.edge_weight(a, b)
+    }
+
+    /// Sets the weight of one edge between the vertices in both directions.
+    pub fn set_edge_weight(&mut self, a: W::VHandle, b: W::VHandle, weight: W::Weight) -> Result<(), Error> {
+        let edges = &mut self.graph.edge_storage;
+        let previous = edges.edge_weight(a, b).ok_or(Error::NoEdge { from: a.into(), to: b.into() })?;
+        edges.set_edge_weight(a, b, weight)?;
+        if a == b {
+            return Ok(());
+        }
+        let reverse = edges.edges_iter_mut(b).find(|edge| W::vh(**edge) == a && W::wgt(**edge) == previous);
+        if let Some(edge) = reverse {
+            *edge = W::set_wgt(*edge, weight);
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn degree(&self, handle: W::VHandle) -> usize {
+        self.graph.edge_storage.edges_len(handle)
+    }
+
+    /// Iterates every edge once as `(a, b, weight)` with `a <= b`.
+    pub fn edges(&self) -> impl Iterator<Item=(W::VHandle, W::VHandle, W::Weight)> + '_ {
+        let edge_storage = &self.graph.edge_storage;
+        (0..self.graph.vertices.len()).map(W::handle).flat_map(move |src| {
+            edge_storage.edges_iter(src)
+                .filter(move |edge| W::vh(**edge) >= src)
+                .map(move |edge| (src, W::vh(*edge), W::wgt(*edge)))
+        })
+    }
+
+    #[inline(always)]
+    pub fn edge_count(&self) -> usize {
+        self.edges().count()
+    }
+}
@@ -1,17 +1,15 @@
-use crate::handles::types::VHandle;
-use crate::handles::{vh, wgt};
-use crate::traits::{EdgeStore, StoreVertex};
+use std::fmt::Display;
+use crate::formats::dot::Dot;
+use crate::traits::{StoreVertex, WeightStore};
 
+/// Prints the graph with its weights to stdout, in the DOT format.
+#[deprecated(note = "use formats::dot::Dot, which can also write to any writer")]
 pub fn print_graph<VertexType, VertexStorageType, EdgeStorageType>(vertices: &VertexStorageType, edges: &EdgeStorageType)
 where
     VertexStorageType: StoreVertex<VertexType=VertexType>,
-    EdgeStorageType: EdgeStore,
-    VertexType: std::fmt::Debug + std::fmt::Display,
+    EdgeStorageType: WeightStore,
+    EdgeStorageType::WeightType: Display,
+    VertexType: Display,
 {
-    for (vertex, val) in vertices.iter().enumerate(){
-        for edge in edges.edges_iter(vertex as VHandle){
-            println!("{} --{}-> {}", *val ,wgt(*edge), vertices[vh(*edge)]);
-        }
-        println!("---");
-    }
+    print!("{}", Dot::new(vertices, edges).weights(true));
 }
\ No newline at end of file
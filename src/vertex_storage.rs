@@ -1,10 +1,13 @@
 use std::ops::{Index, IndexMut};
 use std::slice::{Iter, IterMut};
+use crate::graph::Error;
+use crate::handles::{GHandle, Generation, Generations};
 use crate::handles::types::VHandle;
-use crate::traits::{StoreVertex};
+use crate::traits::{Generational, StoreVertex};
 
 pub struct VertexStorage<VertexType> {
     data: Vec<VertexType>,
+    generations: Generations,
 }
 impl<VertexType> Default for VertexStorage<VertexType> {
     fn default() -> Self {
@@ -16,12 +19,28 @@ impl <VertexType> VertexStorage<VertexType> {
     pub fn new() -> Self {
         VertexStorage {
             data: Vec::new(),
+            generations: Generations::new(),
         }
     }
 
     #[inline(always)]
     pub fn push(&mut self, val: VertexType) {
         self.data.push(val);
+        self.generations.push(self.data.len() - 1);
+    }
+
+    /// Returns the value if the handle still refers to a live vertex.
+    #[inline(always)]
+    pub fn get(&self, handle: GHandle) -> Result<&VertexType, Error> {
+        let handle = self.validate(handle)?;
+        Ok(&self.data[handle as usize])
+    }
+
+    /// Returns the value if the handle still refers to a live vertex.
+    #[inline(always)]
+    pub fn get_mut(&mut self, handle: GHandle) -> Result<&mut VertexType, Error> {
+        let handle = self.validate(handle)?;
+        Ok(&mut self.data[handle as usize])
     }
 }
 
@@ -46,6 +65,7 @@ where VertexType: Clone {
     fn clone(&self) -> Self {
         VertexStorage {
             data: self.data.clone(),
+            generations: self.generations.clone(),
         }
     }
 }
@@ -62,7 +82,13 @@ impl <VertexType> StoreVertex for VertexStorage<VertexType>
     }
     #[inline(always)]
     fn push(&mut self, val: VertexType) {
-        self.data.push(val);
+        VertexStorage::push(self, val);
+    }
+    #[inline(always)]
+    fn swap_remove(&mut self, handle: VHandle) -> VertexType {
+        let val = self.data.swap_remove(handle as usize);
+        self.generations.swap_remove(handle as usize, self.data.len());
+        val
     }
     #[inline(always)]
     fn capacity(&self) -> usize {
@@ -81,4 +107,48 @@ impl <VertexType> StoreVertex for VertexStorage<VertexType>
     fn as_slice(&self) -> &[VertexType] {
         self.data.as_slice()
     }
+}
+
+impl<VertexType> Generational for VertexStorage<VertexType> {
+    #[inline(always)]
+    fn generation(&self, handle: VHandle) -> Option<Generation> {
+        self.generations.get(handle as usize, self.data.len())
+    }
+
+    #[inline(always)]
+    fn slot_generation(&self, handle: VHandle) -> Option<Generation> {
+        self.generations.slot(handle as usize)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "VertexStorage")]
+struct VertexStorageFormat<Values> {
+    data: Values,
+    generations: Generations,
+}
+
+#[cfg(feature = "serde")]
+impl<VertexType: serde::Serialize> serde::Serialize for VertexStorage<VertexType> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        VertexStorageFormat {
+            data: &self.data,
+            generations: self.generations.clone(),
+        }.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, VertexType: serde::Deserialize<'de>> serde::Deserialize<'de> for VertexStorage<VertexType> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let format = VertexStorageFormat::<Vec<VertexType>>::deserialize(deserializer)?;
+        if format.generations.len() < format.data.len() {
+            return Err(serde::de::Error::custom("missing generations"));
+        }
+        Ok(VertexStorage {
+            data: format.data,
+            generations: format.generations,
+        })
+    }
 }
\ No newline at end of file
@@ -0,0 +1,231 @@
+use std::cell::OnceCell;
+use std::ops::{Index, IndexMut};
+use eta_algorithms::data_structs::fat_ptr::{FatPtr, FatPtrMut};
+use crate::edge_storage::EdgeStorage;
+use crate::graph::Error;
+use crate::handles::types::{Ci, Edge, VHandle};
+use crate::handles::{set_wgt, vh, wgt, NONE};
+use crate::traits::{EdgeStore, WeightStore};
+
+/// Subgraph of an edge storage. Edges are visible if both endpoints pass `vertex_filter` and the edge passes
+/// `edge_filter`, which gets the source vertex, the global edge index and the edge. Edges pointing at `NONE`
+/// only go through the edge filter.
+///
+/// The visible edges are copied into compacted blocks on the first access, the filters run only then. The copy keeps
+/// the index of the original edge next to every visible one, so indices, lengths and slices of the view agree and
+/// index based algorithms like `mark_levels` of Dinic work on it. [WeightStore] goes through that index, so weights
+/// are read from and written to the wrapped storage, [WeightStore::set_weight] also refreshes the packed weight of
+/// the copy. The packed edges themselves are a copy, writes to them do not reach the wrapped storage.
+/// The view can't add or remove vertices.
+pub struct FilteredView<'a, EdgeStorageType, VertexFilter, EdgeFilter>
+where
+    EdgeStorageType: EdgeStore,
+    VertexFilter: Fn(VHandle) -> bool,
+    EdgeFilter: Fn(VHandle, usize, Edge) -> bool,
+{
+    edges: &'a mut EdgeStorageType,
+    vertex_filter: VertexFilter,
+    edge_filter: EdgeFilter,
+    vertices_count: usize,
+    filtered: OnceCell<EdgeStorage<usize>>,
+}
+
+impl<'a, EdgeStorageType, VertexFilter, EdgeFilter> FilteredView<'a, EdgeStorageType, VertexFilter, EdgeFilter>
+where
+    EdgeStorageType: EdgeStore,
+    VertexFilter: Fn(VHandle) -> bool,
+    EdgeFilter: Fn(VHandle, usize, Edge) -> bool,
+{
+    pub fn new(edges: &'a mut EdgeStorageType, vertices_count: usize, vertex_filter: VertexFilter, edge_filter: EdgeFilter) -> Self {
+        FilteredView {
+            edges,
+            vertex_filter,
+            edge_filter,
+            vertices_count,
+            filtered: OnceCell::new(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn contains_vertex(&self, handle: VHandle) -> bool {
+        (self.vertex_filter)(handle)
+    }
+
+    /// Index of the edge in the wrapped storage which the visible edge at `index` comes from.
+    #[inline(always)]
+    pub fn original_index(&self, index: usize) -> usize {
+        *self.filtered().edge_data(index)
+    }
+
+    #[inline(always)]
+    fn filtered(&self) -> &EdgeStorage<usize> {
+        self.filtered.get_or_init(|| self.build())
+    }
+
+    #[inline(always)]
+    fn filtered_mut(&mut self) -> &mut EdgeStorage<usize> {
+        self.filtered();
+        self.filtered.get_mut().unwrap()
+    }
+
+    #[inline(always)]
+    fn is_visible(&self, from: VHandle, index: usize) -> bool {
+        let edge = self.edges[index];
+        (vh(edge) == NONE || (self.vertex_filter)(vh(edge))) && (self.edge_filter)(from, index, edge)
+    }
+
+    fn build(&self) -> EdgeStorage<usize> {
+        let visible = |vertex: VHandle| {
+            let index = self.edges.edges_index(vertex);
+            let end = if (self.vertex_filter)(vertex) { index + self.edges.edges_len(vertex) } else { index };
+            (index..end).filter(move |index| self.is_visible(vertex, *index))
+        };
+        let mut filtered = EdgeStorage::with_edge_data(0);
+        for vertex in 0..self.vertices_count as VHandle {
+            filtered.create_vertex_entry(visible(vertex).count() as Ci);
+        }
+        for vertex in 0..self.vertices_count as VHandle {
+            for index in visible(vertex) {
+                let edge = self.edges[index];
+                filtered.connect_with_data(vertex, vh(edge), wgt(edge), index);
+            }
+        }
+        filtered
+    }
+}
+
+impl<'a, EdgeStorageType, VertexFilter, EdgeFilter> EdgeStore for FilteredView<'a, EdgeStorageType, VertexFilter, EdgeFilter>
+where
+    EdgeStorageType: EdgeStore,
+    VertexFilter: Fn(VHandle) -> bool,
+    EdgeFilter: Fn(VHandle, usize, Edge) -> bool,
+{
+    fn create_vertex_entry(&mut self, _size: Ci) -> VHandle {
+        panic!("Filtered view can't be modified");
+    }
+
+    #[inline(always)]
+    fn try_create_vertex_entry(&mut self, _size: Ci) -> Result<VHandle, Error> {
+        Err(Error::Frozen)
+    }
+
+    fn remove_vertex_entry(&mut self, _handle: VHandle) -> VHandle {
+        panic!("Filtered view can't be modified");
+    }
+
+    #[inline(always)]
+    fn vertices_len(&self) -> usize {
+        self.vertices_count
+    }
+
+    #[inline(always)]
+    fn edges_as_slice(&self, handle: VHandle) -> &[Edge] {
+        self.filtered().edges_as_slice(handle)
+    }
+
+    #[inline(always)]
+    fn edges_as_mut_slice(&mut self, handle: VHandle) -> &mut [Edge] {
+        self.filtered_mut().edges_as_mut_slice(handle)
+    }
+
+    #[inline(always)]
+    fn edges_as_ptr(&self, handle: VHandle) -> FatPtr<Edge> {
+        self.filtered().edges_as_ptr(handle)
+    }
+
+    #[inline(always)]
+    fn edges_as_mut_ptr(&mut self, handle: VHandle) -> FatPtrMut<Edge> {
+        self.filtered_mut().edges_as_mut_ptr(handle)
+    }
+
+    #[inline(always)]
+    fn edges_is_empty(&self, handle: VHandle) -> bool {
+        self.filtered().edges_is_empty(handle)
+    }
+
+    #[inline(always)]
+    fn edges_len(&self, handle: VHandle) -> usize {
+        self.filtered().edges_len(handle)
+    }
+
+    #[inline(always)]
+    fn edges_capacity(&self, handle: VHandle) -> usize {
+        self.filtered().edges_capacity(handle)
+    }
+
+    #[inline(always)]
+    fn edges_index(&self, handle: VHandle) -> usize {
+        self.filtered().edges_index(handle)
+    }
+
+    #[inline(always)]
+    fn iter(&self) -> impl Iterator<Item=&Edge> {
+        self.filtered().iter()
+    }
+
+    #[inline(always)]
+    fn iter_mut(&mut self) -> impl Iterator<Item=&mut Edge> {
+        self.filtered_mut().iter_mut()
+    }
+
+    #[inline(always)]
+    fn edges_iter(&self, handle: VHandle) -> impl Iterator<Item=&Edge> {
+        self.filtered().edges_iter(handle)
+    }
+
+    #[inline(always)]
+    fn edges_iter_mut(&mut self, handle: VHandle) -> impl Iterator<Item=&mut Edge> {
+        self.filtered_mut().edges_iter_mut(handle)
+    }
+
+    #[inline(always)]
+    unsafe fn edges_iter_mut_unchecked(&mut self, handle: VHandle) -> impl Iterator<Item=&mut Edge> {
+        self.filtered_mut().edges_iter_mut_unchecked(handle)
+    }
+}
+
+impl<'a, EdgeStorageType, VertexFilter, EdgeFilter> Index<usize> for FilteredView<'a, EdgeStorageType, VertexFilter, EdgeFilter>
+where
+    EdgeStorageType: EdgeStore,
+    VertexFilter: Fn(VHandle) -> bool,
+    EdgeFilter: Fn(VHandle, usize, Edge) -> bool,
+{
+    type Output = Edge;
+    #[inline(always)]
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.filtered()[index]
+    }
+}
+
+impl<'a, EdgeStorageType, VertexFilter, EdgeFilter> IndexMut<usize> for FilteredView<'a, EdgeStorageType, VertexFilter, EdgeFilter>
+where
+    EdgeStorageType: EdgeStore,
+    VertexFilter: Fn(VHandle) -> bool,
+    EdgeFilter: Fn(VHandle, usize, Edge) -> bool,
+{
+    #[inline(always)]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.filtered_mut()[index]
+    }
+}
+
+impl<'a, EdgeStorageType, VertexFilter, EdgeFilter> WeightStore for FilteredView<'a, EdgeStorageType, VertexFilter, EdgeFilter>
+where
+    EdgeStorageType: WeightStore,
+    VertexFilter: Fn(VHandle) -> bool,
+    EdgeFilter: Fn(VHandle, usize, Edge) -> bool,
+{
+    type WeightType = EdgeStorageType::WeightType;
+    #[inline(always)]
+    fn weight(&self, index: usize) -> Self::WeightType {
+        self.edges.weight(self.original_index(index))
+    }
+    #[inline(always)]
+    fn set_weight(&mut self, index: usize, weight: Self::WeightType) {
+        let original = self.original_index(index);
+        self.edges.set_weight(original, weight);
+        let packed = wgt(self.edges[original]);
+        let filtered = self.filtered_mut();
+        filtered[index] = set_wgt(filtered[index], packed);
+    }
+}
\ No newline at end of file
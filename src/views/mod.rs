@@ -1 +1,3 @@
-pub mod tree;
\ No newline at end of file
+pub mod tree;
+pub mod filtered;
+pub mod reversed;
\ No newline at end of file
@@ -0,0 +1,188 @@
+use std::cell::OnceCell;
+use std::ops::{Index, IndexMut};
+use eta_algorithms::data_structs::fat_ptr::{FatPtr, FatPtrMut};
+use crate::edge_storage::EdgeStorage;
+use crate::graph::Error;
+use crate::handles::types::{Ci, Edge, VHandle};
+use crate::handles::{set_wgt, vh, wgt};
+use crate::traits::{EdgeStore, WeightStore};
+
+/// Transpose of an edge storage, every edge `a -> b` is seen as `b -> a` with the same weight.
+///
+/// The reverse adjacency is built on the first access and keeps the index of the original edge next to
+/// every reversed one. [WeightStore] goes through that index, so weights are read from and written to the
+/// wrapped storage, [WeightStore::set_weight] also refreshes the packed weight of the copy. The packed edges
+/// themselves are a copy, writes to them do not reach the wrapped storage.
+/// The view can't add or remove vertices.
+pub struct ReversedView<'a, EdgeStorageType: EdgeStore> {
+    edges: &'a mut EdgeStorageType,
+    vertices_count: usize,
+    reversed: OnceCell<EdgeStorage<usize>>,
+}
+
+impl<'a, EdgeStorageType: EdgeStore> ReversedView<'a, EdgeStorageType> {
+    pub fn new(edges: &'a mut EdgeStorageType, vertices_count: usize) -> Self {
+        ReversedView {
+            edges,
+            vertices_count,
+            reversed: OnceCell::new(),
+        }
+    }
+
+    /// Index of the edge in the wrapped storage which the reversed edge at `index` comes from.
+    #[inline(always)]
+    pub fn original_index(&self, index: usize) -> usize {
+        *self.reversed().edge_data(index)
+    }
+
+    #[inline(always)]
+    fn reversed(&self) -> &EdgeStorage<usize> {
+        self.reversed.get_or_init(|| self.build())
+    }
+
+    #[inline(always)]
+    fn reversed_mut(&mut self) -> &mut EdgeStorage<usize> {
+        self.reversed();
+        self.reversed.get_mut().unwrap()
+    }
+
+    fn build(&self) -> EdgeStorage<usize> {
+        let mut in_degrees = vec![0 as Ci; self.vertices_count];
+        for vertex in 0..self.vertices_count as VHandle {
+            for edge in self.edges.edges_iter(vertex) {
+                if let Some(degree) = in_degrees.get_mut(vh(*edge) as usize) {
+                    *degree += 1;
+                }
+            }
+        }
+        let mut reversed = EdgeStorage::with_edge_data(0);
+        for degree in in_degrees {
+            reversed.create_vertex_entry(degree);
+        }
+        for vertex in 0..self.vertices_count as VHandle {
+            for index in self.edges.edge_indices(vertex) {
+                let edge = self.edges[index];
+                if (vh(edge) as usize) < self.vertices_count {
+                    reversed.connect_with_data(vh(edge), vertex, wgt(edge), index);
+                }
+            }
+        }
+        reversed
+    }
+}
+
+impl<'a, EdgeStorageType: EdgeStore> EdgeStore for ReversedView<'a, EdgeStorageType> {
+    fn create_vertex_entry(&mut self, _size: Ci) -> VHandle {
+        panic!("Reversed view can't be modified");
+    }
+
+    #[inline(always)]
+    fn try_create_vertex_entry(&mut self, _size: Ci) -> Result<VHandle, Error> {
+        Err(Error::Frozen)
+    }
+
+    fn remove_vertex_entry(&mut self, _handle: VHandle) -> VHandle {
+        panic!("Reversed view can't be modified");
+    }
+
+    #[inline(always)]
+    fn vertices_len(&self) -> usize {
+        self.vertices_count
+    }
+
+    #[inline(always)]
+    fn edges_as_slice(&self, handle: VHandle) -> &[Edge] {
+        self.reversed().edges_as_slice(handle)
+    }
+
+    #[inline(always)]
+    fn edges_as_mut_slice(&mut self, handle: VHandle) -> &mut [Edge] {
+        self.reversed_mut().edges_as_mut_slice(handle)
+    }
+
+    #[inline(always)]
+    fn edges_as_ptr(&self, handle: VHandle) -> FatPtr<Edge> {
+        self.reversed().edges_as_ptr(handle)
+    }
+
+    #[inline(always)]
+    fn edges_as_mut_ptr(&mut self, handle: VHandle) -> FatPtrMut<Edge> {
+        self.reversed_mut().edges_as_mut_ptr(handle)
+    }
+
+    #[inline(always)]
+    fn edges_is_empty(&self, handle: VHandle) -> bool {
+        self.reversed().edges_is_empty(handle)
+    }
+
+    #[inline(always)]
+    fn edges_len(&self, handle: VHandle) -> usize {
+        self.reversed().edges_len(handle)
+    }
+
+    #[inline(always)]
+    fn edges_capacity(&self, handle: VHandle) -> usize {
+        self.reversed().edges_capacity(handle)
+    }
+
+    #[inline(always)]
+    fn edges_index(&self, handle: VHandle) -> usize {
+        self.reversed().edges_index(handle)
+    }
+
+    #[inline(always)]
+    fn iter(&self) -> impl Iterator<Item=&Edge> {
+        self.reversed().iter()
+    }
+
+    #[inline(always)]
+    fn iter_mut(&mut self) -> impl Iterator<Item=&mut Edge> {
+        self.reversed_mut().iter_mut()
+    }
+
+    #[inline(always)]
+    fn edges_iter(&self, handle: VHandle) -> impl Iterator<Item=&Edge> {
+        self.reversed().edges_iter(handle)
+    }
+
+    #[inline(always)]
+    fn edges_iter_mut(&mut self, handle: VHandle) -> impl Iterator<Item=&mut Edge> {
+        self.reversed_mut().edges_iter_mut(handle)
+    }
+
+    #[inline(always)]
+    unsafe fn edges_iter_mut_unchecked(&mut self, handle: VHandle) -> impl Iterator<Item=&mut Edge> {
+        self.reversed_mut().edges_iter_mut_unchecked(handle)
+    }
+}
+
+impl<'a, EdgeStorageType: EdgeStore> Index<usize> for ReversedView<'a, EdgeStorageType> {
+    type Output = Edge;
+    #[inline(always)]
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.reversed()[index]
+    }
+}
+
+impl<'a, EdgeStorageType: EdgeStore> IndexMut<usize> for ReversedView<'a, EdgeStorageType> {
+    #[inline(always)]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.reversed_mut()[index]
+    }
+}
+
+impl<'a, EdgeStorageType: WeightStore> WeightStore for ReversedView<'a, EdgeStorageType> {
+    type WeightType = EdgeStorageType::WeightType;
+    #[inline(always)]
+    fn weight(&self, index: usize) -> Self::WeightType {
+        self.edges.weight(self.original_index(index))
+    }
+    #[inline(always)]
+    fn set_weight(&mut self, index: usize, weight: Self::WeightType) {
+        let original = self.original_index(index);
+        self.edges.set_weight(original, weight);
+        let packed = wgt(self.edges[original]);
+        let reversed = self.reversed_mut();
+        reversed[index] = set_wgt(reversed[index], packed);
+    }
+}
\ No newline at end of file
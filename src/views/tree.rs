@@ -1,71 +1,94 @@
 
-use crate::handles::{NONE, vh_pack, vh};
-use crate::handles::types::{Edge, VHandle};
-use crate::traits::{EdgeConnect, EdgeStore, StoreVertex};
+use std::marker::PhantomData;
+use crate::handles::{Width, W64};
+use crate::observer::{GraphObserver, NoObserver};
+use crate::traits::{EdgeConnect, EdgeStore, PlaceholderEdges, StoreVertex};
 
-pub struct Tree<'a, VertexType, VertexStorageType, EdgeStorageType>
+/// The handles are those of the edge width `W` of the storage.
+pub struct Tree<'a, VertexType, VertexStorageType, EdgeStorageType, Observer = NoObserver, W: Width = W64>
 where
-    VertexStorageType: StoreVertex<VertexType=VertexType>,{
+    VertexStorageType: StoreVertex<VertexType=VertexType>,
+    Observer: GraphObserver<VertexType, W>,{
     pub nodes: &'a mut EdgeStorageType,
     pub values: &'a mut VertexStorageType,
+    pub observer: Observer,
+    width: PhantomData<W>,
 }
 const ROOT_OFFSET: usize = 0;
 const PARENT_OFFSET: usize = 1;
-const TREE_HEADER_ELEMENTS: usize = 2;
+pub(crate) const TREE_HEADER_ELEMENTS: usize = 2;
 
 
 
-impl <'a, VertexType, VertexStorageType, EdgeStorageType> Tree<'a, VertexType, VertexStorageType, EdgeStorageType>
+impl <'a, VertexType, VertexStorageType, EdgeStorageType, W> Tree<'a, VertexType, VertexStorageType, EdgeStorageType, NoObserver, W>
 where
-    EdgeStorageType: EdgeStore + EdgeConnect,
-    VertexStorageType: StoreVertex<VertexType=VertexType>
+    EdgeStorageType: EdgeStore<W> + EdgeConnect<W> + PlaceholderEdges<W>,
+    VertexStorageType: StoreVertex<VertexType=VertexType>,
+    W: Width,
 {
     #[cfg_attr(not(debug_assertions), inline(always))]
     pub fn new(edges: &'a mut EdgeStorageType, vertices: &'a mut VertexStorageType) -> Self {
+        Self::with_observer(edges, vertices, NoObserver)
+    }
+}
+
+impl <'a, VertexType, VertexStorageType, EdgeStorageType, Observer, W> Tree<'a, VertexType, VertexStorageType, EdgeStorageType, Observer, W>
+where
+    EdgeStorageType: EdgeStore<W> + EdgeConnect<W> + PlaceholderEdges<W>,
+    VertexStorageType: StoreVertex<VertexType=VertexType>,
+    Observer: GraphObserver<VertexType, W>,
+    W: Width,
+{
+    /// Pass `&mut observer` to keep using the observer after the tree is dropped.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn with_observer(edges: &'a mut EdgeStorageType, vertices: &'a mut VertexStorageType, observer: Observer) -> Self {
         Tree {
             nodes: edges,
             values: vertices,
-
+            observer,
+            width: PhantomData,
         }
     }
     #[inline(always)]
-    pub fn get_children(&self, parent: VHandle) -> &[Edge] {
+    pub fn get_children(&self, parent: W::VHandle) -> &[W::Edge] {
         &self.nodes.edges_as_slice(parent)[TREE_HEADER_ELEMENTS..]
     }
 
-    pub fn add_child(&mut self, parent: VHandle, child: VHandle){
+    pub fn add_child(&mut self, parent: W::VHandle, child: W::VHandle){
         self.nodes.connect(parent, child);
         let child_edge = self.nodes.edges_index(child);
-        self.nodes[child_edge + PARENT_OFFSET] = vh_pack(parent);
-        self.nodes[child_edge + ROOT_OFFSET] = vh_pack(self.get_root(parent));
+        self.nodes[child_edge + PARENT_OFFSET] = W::vh_pack(parent);
+        self.nodes[child_edge + ROOT_OFFSET] = W::vh_pack(self.get_root(parent));
+        self.observer.child_added(parent, child);
     }
 
-    fn create_vertex(&mut self, val: VertexType) -> VHandle {
+    fn create_vertex(&mut self, val: VertexType) -> W::VHandle {
         self.values.push(val);
         self.nodes.create_vertex_entry(0);
-        let vertex = self.values.len() -1;
-        vertex as VHandle
+        let vertex = W::handle(self.values.len() - 1);
+        self.observer.vertex_created(vertex, &self.values[vertex.into()]);
+        vertex
     }
     #[inline(always)]
-    pub fn get_root(&self, vertex: VHandle) -> VHandle {
-        vh(self.nodes[self.nodes.edges_index(vertex) + ROOT_OFFSET])
+    pub fn get_root(&self, vertex: W::VHandle) -> W::VHandle {
+        W::vh(self.nodes[self.nodes.edges_index(vertex) + ROOT_OFFSET])
     }
     #[inline(always)]
-    pub fn get_parent(&self, vertex: VHandle) -> VHandle {
-        vh(self.nodes[self.nodes.edges_index(vertex) + PARENT_OFFSET])
+    pub fn get_parent(&self, vertex: W::VHandle) -> W::VHandle {
+        W::vh(self.nodes[self.nodes.edges_index(vertex) + PARENT_OFFSET])
     }
 
-    pub fn create_node(&mut self, val: VertexType) -> VHandle {
+    pub fn create_node(&mut self, val: VertexType) -> W::VHandle {
         let vertex = self.create_vertex(val);
 
         self.nodes.connect(vertex, vertex); // root
-        self.nodes.connect(vertex, NONE); // parent
+        self.nodes.connect(vertex, W::NONE); // parent
 
         vertex
     }
 
     #[inline(always)]
-    pub fn create_child(&mut self, parent: VHandle, val: VertexType) -> VHandle {
+    pub fn create_child(&mut self, parent: W::VHandle, val: VertexType) -> W::VHandle {
         let child = self.create_node(val);
         self.add_child(parent, child);
         child
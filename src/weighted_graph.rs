@@ -1,22 +1,32 @@
+use std::fmt::Display;
+use crate::adjacency_matrix::AdjacencyMatrix;
 use crate::edge_storage::EdgeStorage;
-use crate::graph::{Graph};
+use crate::formats::dot::Dot;
+use crate::formats::graphml::{GraphMl, KeyType};
+use crate::graph::{Error, Graph};
 use crate::handles::types::{VHandle, Ci, Weight};
-use crate::traits::{StoreVertex, WeightedEdgeManipulate};
+use crate::handles::{Width, W64};
+use crate::observer::{GraphObserver, NoObserver};
+use crate::traits::{StoreVertex, WeightStore, WeightedEdgeManipulate};
 use crate::vertex_storage::VertexStorage;
 
-pub struct WeightedGraph<VertexType, VertexStorageType, EdgeStorageType>
+/// `W` is the edge width, see [Graph].
+pub struct WeightedGraph<VertexType, VertexStorageType, EdgeStorageType, Observer = NoObserver, W: Width = W64>
 where
-    EdgeStorageType: WeightedEdgeManipulate,
-    VertexStorageType: StoreVertex<VertexType=VertexType>
+    EdgeStorageType: WeightedEdgeManipulate<W>,
+    VertexStorageType: StoreVertex<VertexType=VertexType>,
+    Observer: GraphObserver<VertexType, W>,
 {
-    pub graph: Graph<VertexType, VertexStorageType, EdgeStorageType>,
+    pub graph: Graph<VertexType, VertexStorageType, EdgeStorageType, Observer, W>,
 }
 
-impl<VertexType, VertexStorageType, EdgeStorageType> Clone for WeightedGraph<VertexType, VertexStorageType, EdgeStorageType>
+impl<VertexType, VertexStorageType, EdgeStorageType, Observer, W> Clone for WeightedGraph<VertexType, VertexStorageType, EdgeStorageType, Observer, W>
 where
-    EdgeStorageType: WeightedEdgeManipulate,
+    EdgeStorageType: WeightedEdgeManipulate<W>,
     VertexType: Clone,
-    VertexStorageType: StoreVertex<VertexType=VertexType> + Clone {
+    VertexStorageType: StoreVertex<VertexType=VertexType> + Clone,
+    Observer: GraphObserver<VertexType, W> + Clone,
+    W: Width {
     fn clone(&self) -> Self {
         WeightedGraph{
             graph: self.graph.clone(),
@@ -24,9 +34,9 @@ where
     }
 }
 
-impl<VertexType> Default for WeightedGraph<VertexType, VertexStorage<VertexType>, EdgeStorage> {
+impl<VertexType, W: Width> Default for WeightedGraph<VertexType, VertexStorage<VertexType>, EdgeStorage<(), W>, NoObserver, W> {
     fn default() -> Self {
-        Self::new()
+        Self::with_width(0)
     }
 }
 
@@ -47,19 +57,198 @@ impl<VertexType> WeightedGraph<VertexType, VertexStorage<VertexType>, EdgeStorag
             graph: Graph::with_reserve(reserve),
         }
     }
+
+    /// See [Graph::from_edges].
+    pub fn from_edges<Values, Edges>(values: Values, edges: Edges) -> Result<Self, Error>
+    where
+        Values: IntoIterator<Item=VertexType>,
+        Edges: IntoIterator<Item=(VHandle, VHandle, Weight)>,
+    {
+        Ok(WeightedGraph{
+            graph: Graph::from_edges(values, edges)?,
+        })
+    }
+}
+
+impl<VertexType, W: Width> WeightedGraph<VertexType, VertexStorage<VertexType>, EdgeStorage<(), W>, NoObserver, W>
+{
+    /// See [Graph::with_width].
+    pub fn with_width(reserve: Ci) -> Self {
+        WeightedGraph{
+            graph: Graph::with_width(reserve),
+        }
+    }
+
+    /// See [Graph::from_edges_with_width].
+    pub fn from_edges_with_width<Values, Edges>(values: Values, edges: Edges) -> Result<Self, Error>
+    where
+        Values: IntoIterator<Item=VertexType>,
+        Edges: IntoIterator<Item=(W::VHandle, W::VHandle, W::Weight)>,
+    {
+        Ok(WeightedGraph{
+            graph: Graph::from_edges_with_width(values, edges)?,
+        })
+    }
 }
-impl<VertexType, StoreVertexType, EdgeStorageType> WeightedGraph<VertexType, StoreVertexType, EdgeStorageType>
+
+impl<VertexType, VertexStorageType, EdgeType, Source, Observer, W> WeightedGraph<VertexType, VertexStorageType, EdgeStorage<EdgeType, W, Source>, Observer, W>
 where
-    EdgeStorageType: WeightedEdgeManipulate,
-    StoreVertexType: StoreVertex<VertexType=VertexType> {
-    pub fn create_and_connect_weighted(&mut self, src_vertex: VHandle, val: VertexType, weight: Weight, edge_count: Ci) -> VHandle {
+    VertexStorageType: StoreVertex<VertexType=VertexType> + Clone,
+    EdgeType: Default + Clone,
+    Observer: GraphObserver<VertexType, W>,
+    W: Width,
+{
+    /// See [Graph::transpose].
+    pub fn transpose(&self) -> WeightedGraph<VertexType, VertexStorageType, EdgeStorage<EdgeType, W, Source>, NoObserver, W> {
+        WeightedGraph{
+            graph: self.graph.transpose(),
+        }
+    }
+}
+impl<VertexType> WeightedGraph<VertexType, VertexStorage<VertexType>, AdjacencyMatrix>
+{
+    /// See [Graph::with_adjacency_matrix].
+    pub fn with_adjacency_matrix(vertices: usize) -> Self {
+        WeightedGraph{
+            graph: Graph::with_adjacency_matrix(vertices),
+        }
+    }
+}
+
+impl<VertexType, StoreVertexType, EdgeStorageType, Observer, W> WeightedGraph<VertexType, StoreVertexType, EdgeStorageType, Observer, W>
+where
+    EdgeStorageType: WeightedEdgeManipulate<W>,
+    StoreVertexType: StoreVertex<VertexType=VertexType>,
+    Observer: GraphObserver<VertexType, W>,
+    W: Width {
+    /// See [Graph::with_observer].
+    pub fn with_observer<NewObserver: GraphObserver<VertexType, W>>(self, observer: NewObserver) -> WeightedGraph<VertexType, StoreVertexType, EdgeStorageType, NewObserver, W> {
+        WeightedGraph{
+            graph: self.graph.with_observer(observer),
+        }
+    }
+
+    pub fn create_and_connect_weighted(&mut self, src_vertex: W::VHandle, val: VertexType, weight: W::Weight, edge_count: Ci) -> W::VHandle {
         let new_vertex = self.graph.create(val, edge_count);
-        self.graph.edge_storage.connect_weighted(src_vertex, new_vertex, weight);
+        self.connect_weighted(src_vertex, new_vertex, weight);
         new_vertex
     }
 
-    pub fn create_and_connect_weighted_0(&mut self, src_vertex: VHandle, val: VertexType, weight: Weight) -> VHandle {
+    /// See [Graph::try_create].
+    #[inline(always)]
+    pub fn try_create(&mut self, val: VertexType, edge_count: Ci) -> Result<W::VHandle, Error> {
+        self.graph.try_create(val, edge_count)
+    }
+
+    /// See [Graph::try_connect].
+    #[inline(always)]
+    pub fn try_connect(&mut self, from: W::VHandle, to: W::VHandle) -> Result<(), Error> {
+        self.graph.try_connect(from, to)
+    }
+
+    /// See [Graph::try_disconnect].
+    #[inline(always)]
+    pub fn try_disconnect(&mut self, from: W::VHandle, to: W::VHandle) -> Result<(), Error> {
+        self.graph.try_disconnect(from, to)
+    }
+
+    pub fn create_and_connect_weighted_0(&mut self, src_vertex: W::VHandle, val: VertexType, weight: W::Weight) -> W::VHandle {
         self.create_and_connect_weighted(src_vertex, val, weight, 0)
     }
 
+    #[inline(always)]
+    pub fn has_edge(&self, from: W::VHandle, to: W::VHandle) -> bool {
+        self.graph.edge_storage.has_edge(from, to)
+    }
+
+    /// Returns the index of the first edge `from -> to` in the edge storage.
+    #[inline(always)]
+    pub fn find_edge(&self, from: W::VHandle, to: W::VHandle) -> Option<usize> {
+        self.graph.edge_storage.find_edge(from, to)
+    }
+
+    #[inline(always)]
+    pub fn edge_weight(&self, from: W::VHandle, to: W::VHandle) -> Option<W::Weight> {
+        self.graph.edge_storage.edge_weight(from, to)
+    }
+
+    #[inline(always)]
+    pub fn set_edge_weight(&mut self, from: W::VHandle, to: W::VHandle, weight: W::Weight) -> Result<(), Error> {
+        self.graph.edge_storage.set_edge_weight(from, to, weight)?;
+        self.graph.observer.weight_changed(from, to, weight);
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn connect_weighted(&mut self, from: W::VHandle, to: W::VHandle, weight: W::Weight) {
+        self.graph.edge_storage.connect_weighted(from, to, weight);
+        self.graph.observer.connected(from, to, weight);
+    }
+
+    /// Accepts any integer type, fails with [Error::WeightOutOfRange] if it does not fit into the weight of the width.
+    #[inline(always)]
+    pub fn try_connect_weighted<T: TryInto<W::Weight>>(&mut self, from: W::VHandle, to: W::VHandle, weight: T) -> Result<(), Error> {
+        let weight = weight.try_into().map_err(|_| Error::WeightOutOfRange)?;
+        self.graph.edge_storage.try_connect_weighted(from, to, weight)?;
+        self.graph.observer.connected(from, to, weight);
+        Ok(())
+    }
+}
+
+/// Exports, which read the edges at the default width.
+impl<VertexType, StoreVertexType, EdgeStorageType, Observer> WeightedGraph<VertexType, StoreVertexType, EdgeStorageType, Observer>
+where
+    EdgeStorageType: WeightedEdgeManipulate,
+    StoreVertexType: StoreVertex<VertexType=VertexType>,
+    Observer: GraphObserver<VertexType> {
+    /// DOT export with the weights as edge labels, see [Dot].
+    #[inline(always)]
+    pub fn dot(&self) -> Dot<'_, VertexType, StoreVertexType, EdgeStorageType>
+    where
+        EdgeStorageType: WeightStore,
+        EdgeStorageType::WeightType: Display,
+        VertexType: Display,
+    {
+        self.graph.dot().weights(true)
+    }
+
+    /// GraphML export with the weights as the `weight` edge key, see [GraphMl].
+    #[inline(always)]
+    pub fn graphml(&self) -> GraphMl<'_, VertexType, StoreVertexType, EdgeStorageType>
+    where
+        EdgeStorageType: WeightStore,
+        EdgeStorageType::WeightType: KeyType,
+    {
+        self.graph.graphml().weights(true)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<VertexType, EdgeType, Source, W> serde::Serialize for WeightedGraph<VertexType, VertexStorage<VertexType>, EdgeStorage<EdgeType, W, Source>, NoObserver, W>
+where
+    VertexType: serde::Serialize,
+    EdgeType: serde::Serialize + Default + Clone,
+    W: Width,
+    W::Edge: serde::Serialize,
+{
+    #[inline(always)]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.graph.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, VertexType, EdgeType, Source, W> serde::Deserialize<'de> for WeightedGraph<VertexType, VertexStorage<VertexType>, EdgeStorage<EdgeType, W, Source>, NoObserver, W>
+where
+    VertexType: serde::Deserialize<'de>,
+    EdgeType: serde::Deserialize<'de> + Default + Clone,
+    W: Width,
+    W::Edge: serde::Deserialize<'de>,
+{
+    #[inline(always)]
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(WeightedGraph{
+            graph: Graph::deserialize(deserializer)?,
+        })
+    }
 }
\ No newline at end of file